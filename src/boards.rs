@@ -1,621 +1,1434 @@
 //! Defines the Kanban Board structure along with associated helper methods
 
 use crate::{
-    constants::KANBAN_BOARD_FILE, error::AppError, links::TaskToSubtaskMap, subtasks::SubTaskItem,
-    tasks::TaskItem, utils::create_app_dirs, TaskPriority, TaskStatus, TimeStamp,
+    constants::SPRINT_LENGTH_DAYS,
+    dependencies::Dependencies,
+    error::AppError,
+    estimates::{EstimateTotals, EstimateUnit},
+    hierarchy::Hierarchy,
+    ids::IdSequence,
+    items::WorkItem,
+    output::{self, Record, Section},
+    people::{validate_handle, Person},
+    sprints::Sprint,
+    storage::Storage,
+    swimlanes::{slugify, LaneStage, Swimlane, WipCheck, WipLimit},
+    tags::{validate_tag, TagColor},
+    worklog::{self, RunningTimer},
+    TaskPriority, TimeStamp,
 };
 use chrono::{prelude::*, Days};
-use cli_table::{Cell, Style, Table, TableDisplay};
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, path::Path};
+use std::collections::BTreeMap;
 
 /// Rust structure for Kanban Board
-#[derive(Debug, Serialize, Deserialize, Eq, PartialEq)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Eq, PartialEq)]
 pub struct KanbanBoard {
-    /// Using HashMaps to store list of Task and SubTask IDs in different swimlanes
-    boards: HashMap<TaskStatus, Vec<String>>,
+    /// Swimlanes in display order, each holding the list of Task and SubTask IDs in it
+    pub(crate) swimlanes: Vec<Swimlane>,
+
+    /// Sequence generating the Task and SubTask IDs of the board
+    pub(crate) id_sequence: IdSequence,
+
+    /// Deleted tasks and subtasks, which can still be restored until the trash is emptied
+    pub(crate) trash: Vec<HiddenItem>,
+
+    /// Completed tasks (and their subtasks) hidden from the done swimlanes
+    pub(crate) archive: Vec<HiddenItem>,
+
+    /// Colors given to tags used on the board (tags without a color are not kept here)
+    pub(crate) tag_colors: BTreeMap<String, TagColor>,
+
+    /// People who can be assigned to tasks and subtasks on the board, in the order they were added
+    pub(crate) people: Vec<Person>,
+
+    /// Dependencies between the tasks and subtasks of the board
+    pub(crate) dependencies: Dependencies,
+
+    /// Timers running on tasks and subtasks of the board, at most one per person
+    pub(crate) timers: Vec<RunningTimer>,
+
+    /// Sprints of the board, in the order they were created
+    pub(crate) sprints: Vec<Sprint>,
+}
+
+/// Places where tasks and subtasks are kept off the swimlanes without losing their history
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Shelf {
+    /// Deleted items, purged by `trash empty`
+    Trash,
+
+    /// Archived items, which are kept for good
+    Archive,
+}
+
+impl std::fmt::Display for Shelf {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Shelf::Trash => write!(f, "trash"),
+            Shelf::Archive => write!(f, "archive"),
+        }
+    }
+}
+
+/// Rust structure for a Task or SubTask taken off the swimlanes into the trash or the archive.
+/// The item itself and its notes stay in storage.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct HiddenItem {
+    /// Task or SubTask ID
+    pub item_id: String,
+
+    /// Slug of the swimlane the item is put back into when restored
+    pub swimlane: String,
+
+    /// Date the item was taken off the swimlanes
+    pub hidden_on: TimeStamp,
+
+    /// Task or SubTask ID the subtask was taken off along with (restored together with it)
+    pub hidden_with: Option<String>,
+}
+
+/// Task or SubTask as listed by `show task`, `show subtask` and the `filter` commands
+#[derive(Debug, Clone, Serialize)]
+pub struct ItemRecord {
+    /// Task or SubTask ID
+    pub id: String,
+
+    /// Kind of item ("task" or "subtask")
+    pub kind: String,
+
+    /// Task or SubTask Name
+    pub name: String,
+
+    /// Priority of the item
+    #[serde(serialize_with = "output::serialize_lowercase")]
+    pub priority: TaskPriority,
+
+    /// Date when the item is supposed to finish
+    #[serde(serialize_with = "output::serialize_optional_date")]
+    pub deadline: Option<NaiveDate>,
+
+    /// Slug of the swimlane the item is in
+    pub swimlane: String,
+
+    /// Tags put on the item
+    pub tags: Vec<String>,
+
+    /// Handle of the person the item is assigned to
+    pub assignee: Option<String>,
+
+    /// Estimates of the item and its subtasks (at every depth), added up per unit
+    pub estimate: EstimateTotals,
+
+    /// Number of subtasks directly under the item
+    pub subtasks: usize,
+
+    /// Task or SubTask a subtask is nested under (none for tasks)
+    pub parent: Option<String>,
+}
+
+impl ItemRecord {
+    /// Create the record for given task or subtask
+    pub fn from_item(
+        storage: &dyn Storage,
+        boards: &KanbanBoard,
+        hierarchy: &Hierarchy,
+        work_item: WorkItem,
+    ) -> Result<ItemRecord, AppError> {
+        Ok(ItemRecord {
+            estimate: WorkItem::rolled_up_estimate(storage, boards, hierarchy, &work_item)?,
+            kind: work_item.kind().to_string(),
+            subtasks: hierarchy.children(&work_item.id).len(),
+            id: work_item.id,
+            name: work_item.name,
+            priority: work_item.priority,
+            deadline: TimeStamp::to_optional_naivedate(work_item.deadline)?,
+            swimlane: work_item.status,
+            tags: work_item.tags,
+            assignee: work_item.assignee,
+            parent: work_item.parent,
+        })
+    }
+
+    /// Add the story points of given records (rolled up from their subtasks) to a heading, e.g.
+    /// "Done - 8 pts (2 left)"
+    pub fn heading_with_points(heading: &str, records: &[ItemRecord]) -> String {
+        let mut totals: EstimateTotals = EstimateTotals::default();
+        for record in records {
+            totals.points += record.estimate.points;
+            totals.points_remaining += record.estimate.points_remaining;
+        }
+        match totals.display_points() {
+            Some(s) => format!("{} - {}", heading, s),
+            None => heading.to_string(),
+        }
+    }
+}
+
+impl Record for ItemRecord {
+    fn cells(&self) -> Vec<String> {
+        let last_cell: String = match &self.parent {
+            Some(s) => s.clone(),
+            None => self.subtasks.to_string(),
+        };
+        vec![
+            self.id.clone(),
+            self.name.clone(),
+            self.priority.to_string(),
+            output::display_date(&self.deadline),
+            output::display_list(&self.tags),
+            self.assignee.clone().unwrap_or("None".to_string()),
+            self.estimate.to_string(),
+            last_cell,
+        ]
+    }
+}
+
+/// Task or subtask in the trash or the archive as listed by `trash list` and `archive list`
+#[derive(Debug, Clone, Serialize)]
+pub struct HiddenRecord {
+    /// Task or SubTask ID
+    pub id: String,
+
+    /// Task or SubTask Name (None if the item is no longer stored)
+    pub name: Option<String>,
+
+    /// Slug of the swimlane the item is restored into
+    pub swimlane: String,
+
+    /// Date when the item was deleted or archived
+    #[serde(serialize_with = "output::serialize_date")]
+    pub hidden_on: NaiveDate,
+
+    /// Task or SubTask ID the item was deleted or archived along with (None if on its own)
+    pub hidden_with: Option<String>,
+}
+
+impl Record for HiddenRecord {
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.id.clone(),
+            self.name.clone().unwrap_or("None".to_string()),
+            self.swimlane.clone(),
+            output::display_date(&Some(self.hidden_on)),
+            self.hidden_with.clone().unwrap_or("None".to_string()),
+        ]
+    }
+}
+
+/// Swimlane as listed by `lane list`
+#[derive(Debug, Clone, Serialize)]
+pub struct SwimlaneRecord {
+    /// Position of the swimlane on the board (starting at 1)
+    pub position: usize,
+
+    /// Slug used to refer to the swimlane in commands
+    pub slug: String,
+
+    /// Display name of the swimlane
+    pub name: String,
+
+    /// Stage of work which the swimlane represents
+    #[serde(serialize_with = "output::serialize_lowercase")]
+    pub stage: LaneStage,
+
+    /// Whether the swimlane holds tasks and subtasks waiting on their blockers
+    pub blocked: bool,
+
+    /// Number of tasks and subtasks in the swimlane
+    pub items: usize,
+
+    /// WIP limit of the swimlane (None if unlimited)
+    pub wip_limit: Option<WipLimit>,
+
+    /// Time logged on the tasks and subtasks in the swimlane, in minutes
+    pub minutes: u32,
+}
+
+impl Record for SwimlaneRecord {
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.position.to_string(),
+            self.slug.clone(),
+            self.name.clone(),
+            match self.blocked {
+                true => format!("{} (blocked)", self.stage),
+                false => self.stage.to_string(),
+            },
+            self.items.to_string(),
+            match self.wip_limit {
+                Some(s) => s.to_string(),
+                None => "None".to_string(),
+            },
+            worklog::format_duration(self.minutes),
+        ]
+    }
 }
 
 impl KanbanBoard {
-    /// Create new blank Kanban Board (for first time setup)
+    /// Create new Kanban Board with the default swimlanes (for first time setup)
     pub fn new() -> Self {
         KanbanBoard {
-            boards: HashMap::new(),
-        }
-    }
-
-    /// Display all tasks in given swimlane (to-do, in-progress, blocked, in-review, done).
-    /// If swimlane = "all", then tasks in all swimlanes in the Kanban Board are displayed.
-    pub fn show_tasks(&self, swimlanes: &str) -> Result<(), AppError> {
-        let tasks_link: TaskToSubtaskMap = TaskToSubtaskMap::load_from_file()?;
-        let swimlane_to_show: Vec<TaskStatus> = match swimlanes {
-            "all" => vec![TaskStatus::ToDo, TaskStatus::InProgress, TaskStatus::Blocked, TaskStatus::InReview, TaskStatus::Done],
-            "to-do" => vec![TaskStatus::ToDo],
-            "in-progress" => vec![TaskStatus::InProgress],
-            "blocked" => vec![TaskStatus::Blocked],
-            "in-review" => vec![TaskStatus::InReview],
-            "done" => vec![TaskStatus::Done],
-            _ => return Err(AppError::InvalidSwimlanePassed(
-                format!("{} \nPlease select from following options: \n1) all 2) to-do 3) in-progress 4) blocked 5) in-review 6) done\n", swimlanes.to_string())))
-        };
+            swimlanes: Swimlane::default_swimlanes(),
+            id_sequence: IdSequence::default(),
+            trash: Vec::new(),
+            archive: Vec::new(),
+            tag_colors: BTreeMap::new(),
+            people: Vec::new(),
+            dependencies: Dependencies::default(),
+            timers: Vec::new(),
+            sprints: Vec::new(),
+        }
+    }
 
-        for swimlane in swimlane_to_show {
-            let tasks: &Vec<String> = match self.boards.get(&swimlane) {
-                Some(s) => s,
-                None => {
-                    return Err(AppError::SwimlaneNotFoundError(format!(
-                        "Swimlane {} not found.",
-                        swimlane
-                    )))
-                }
-            };
-            println!("====================");
-            println!("{:#?}", swimlane.to_string());
-            println!("====================");
-
-            let mut display_table: Vec<Vec<String>> = Vec::new();
-            for task_id in tasks {
-                if TaskItem::check_if_file_exists(task_id)? == true {
-                    let task_item: TaskItem = TaskItem::get_task(task_id)?;
-                    let task_deadline: String = match task_item.task_deadline {
-                        Some(s) => s.to_naivedate()?.format("%b %e, %Y").to_string(),
-                        None => "None".to_string(),
-                    };
-                    let num_subtasks: String = tasks_link.get_num_subtasks(task_id).to_string();
-                    display_table.push(vec![
-                        task_id.clone(),
-                        task_item.task_name,
-                        task_item.task_priority.to_string(),
-                        task_deadline,
-                        num_subtasks,
-                    ])
+    /// Fetch all tasks in swimlane with given slug, grouped by swimlane along with the story points
+    /// of the tasks in each swimlane (rolled up from their subtasks).
+    /// If swimlane = "all", then tasks in all swimlanes in the Kanban Board are fetched.
+    pub fn list_tasks(
+        &self,
+        storage: &dyn Storage,
+        hierarchy: &Hierarchy,
+        swimlanes: &str,
+    ) -> Result<Vec<Section<ItemRecord>>, AppError> {
+        self.list_tasks_where(storage, hierarchy, swimlanes, |_| true)
+    }
+
+    /// Fetch the tasks planned into given sprint, grouped by swimlane (in all swimlanes of the
+    /// Kanban Board) along with the story points of the tasks in each swimlane
+    pub fn list_sprint_tasks(
+        &self,
+        storage: &dyn Storage,
+        hierarchy: &Hierarchy,
+        sprint: &Sprint,
+    ) -> Result<Vec<Section<ItemRecord>>, AppError> {
+        self.list_tasks_where(storage, hierarchy, "all", |x| sprint.contains(&x.id))
+    }
+
+    /// Fetch the tasks in swimlane with given slug which match given filter, grouped by swimlane
+    /// along with the story points of the tasks in each swimlane
+    fn list_tasks_where(
+        &self,
+        storage: &dyn Storage,
+        hierarchy: &Hierarchy,
+        swimlanes: &str,
+        filter: impl Fn(&ItemRecord) -> bool,
+    ) -> Result<Vec<Section<ItemRecord>>, AppError> {
+        let mut sections: Vec<Section<ItemRecord>> =
+            self.list_items(storage, hierarchy, swimlanes, |x| {
+                x.parent.is_none() && filter(x)
+            })?;
+        for section in sections.iter_mut() {
+            section.heading = ItemRecord::heading_with_points(&section.heading, &section.records);
+        }
+        Ok(sections)
+    }
+
+    /// Fetch all subtasks (at every depth) in swimlane with given slug, grouped by swimlane.
+    /// If swimlane = "all", then subtasks in all swimlanes in the Kanban Board are fetched.
+    pub fn list_subtasks(
+        &self,
+        storage: &dyn Storage,
+        hierarchy: &Hierarchy,
+        swimlanes: &str,
+    ) -> Result<Vec<Section<ItemRecord>>, AppError> {
+        self.list_items(storage, hierarchy, swimlanes, |x| x.parent.is_some())
+    }
+
+    /// Fetch the items in swimlane with given slug which match given filter, grouped by swimlane
+    fn list_items(
+        &self,
+        storage: &dyn Storage,
+        hierarchy: &Hierarchy,
+        swimlanes: &str,
+        filter: impl Fn(&ItemRecord) -> bool,
+    ) -> Result<Vec<Section<ItemRecord>>, AppError> {
+        let mut sections: Vec<Section<ItemRecord>> = Vec::new();
+
+        for swimlane in self.select_swimlanes(swimlanes)? {
+            let mut records: Vec<ItemRecord> = Vec::new();
+            for item_id in &swimlane.items {
+                if WorkItem::check_if_exists(storage, item_id)? {
+                    let work_item: WorkItem = WorkItem::get(storage, item_id)?;
+                    records.push(ItemRecord::from_item(storage, self, hierarchy, work_item)?);
                 }
             }
+            records.retain(|x| filter(x));
+            sections.push(Section {
+                heading: swimlane.header(),
+                records,
+            });
+        }
 
-            let display_table: TableDisplay = match display_table
-                .table()
-                .title(vec![
-                    "Task ID".cell().bold(true),
-                    "Task Name".cell().bold(true),
-                    "Priority".cell().bold(true),
-                    "Deadline".cell().bold(true),
-                    "Subtasks".cell().bold(true),
-                ])
-                .display()
-            {
-                Ok(s) => s,
-                Err(e) => return Err(AppError::TableDisplayParseError(e.to_string())),
-            };
+        Ok(sections)
+    }
 
-            println!("{}", display_table);
+    /// Fetch the tasks and subtasks in swimlanes not marked as done which match given deadline
+    /// keyword, grouped by swimlane.
+    ///
+    /// - `no-deadline`: Tasks and subtasks which don't have a deadline defined.
+    /// - `past-deadline`: Tasks and subtasks which are past their set deadline.
+    /// - `today`: Tasks and subtasks which have today's date as deadline.
+    /// - `tomorrow`: Tasks and subtasks which have tomorrow's date as deadline.
+    /// - `after-tomorrow`: Tasks and subtasks whose deadlines are upcoming after tomorrow.
+    pub fn find_by_deadline(
+        &self,
+        storage: &dyn Storage,
+        hierarchy: &Hierarchy,
+        keyword: &str,
+    ) -> Result<Vec<Section<ItemRecord>>, AppError> {
+        let today: NaiveDate = TimeStamp::new().to_naivedate()?;
+        let tomorrow: NaiveDate = today.checked_add_days(Days::new(1)).unwrap();
+        let matches = |deadline: Option<NaiveDate>| -> bool {
+            match (keyword, deadline) {
+                ("no-deadline", deadline) => deadline.is_none(),
+                ("past-deadline", Some(s)) => s < today,
+                ("today", Some(s)) => s == today,
+                ("tomorrow", Some(s)) => s == tomorrow,
+                ("after-tomorrow", Some(s)) => s > tomorrow,
+                _ => false,
+            }
+        };
+        match keyword {
+            "no-deadline" | "past-deadline" | "today" | "tomorrow" | "after-tomorrow" => {}
+            _ => return Err(AppError::InvalidDeadlineKeyword (
+                format!("{} \nPlease select from following options: \n1) past-deadline 2) today 3) tomorrow 4) after-tomorrow 5) no-deadline\n", keyword)))
         }
 
-        Ok(())
+        self.filter_items(storage, hierarchy, |x| matches(x.deadline))
     }
 
-    /// Display all subtasks in given swimlane (to-do, in-progress, blocked, in-review, done).
-    /// If swimlane = "all", then subtasks in all swimlanes in the Kanban Board are displayed.
-    pub fn show_subtasks(&self, swimlanes: &str) -> Result<(), AppError> {
-        let swimlane_to_show: Vec<TaskStatus> = match swimlanes {
-            "all" => vec![TaskStatus::ToDo, TaskStatus::InProgress, TaskStatus::Blocked, TaskStatus::InReview, TaskStatus::Done],
-            "to-do" => vec![TaskStatus::ToDo],
-            "in-progress" => vec![TaskStatus::InProgress],
-            "blocked" => vec![TaskStatus::Blocked],
-            "in-review" => vec![TaskStatus::InReview],
-            "done" => vec![TaskStatus::Done],
-            _ => return Err(AppError::InvalidSwimlanePassed(
-                format!("{} \nPlease select from following options: \n1) all 2) to-do 3) in-progress 4) blocked 5) in-review 6) done\n", swimlanes.to_string())))
+    /// Fetch the tasks and subtasks in swimlanes not marked as done which have given priority
+    /// keyword (high, medium, low), grouped by swimlane
+    pub fn find_by_priority(
+        &self,
+        storage: &dyn Storage,
+        hierarchy: &Hierarchy,
+        keyword: &str,
+    ) -> Result<Vec<Section<ItemRecord>>, AppError> {
+        let priority: TaskPriority = keyword.parse::<TaskPriority>()?;
+        self.filter_items(storage, hierarchy, |x| x.priority == priority)
+    }
+
+    /// Fetch the tasks and subtasks in swimlanes not marked as done which have given tag, grouped by
+    /// swimlane
+    pub fn find_by_tag(
+        &self,
+        storage: &dyn Storage,
+        hierarchy: &Hierarchy,
+        tag: &str,
+    ) -> Result<Vec<Section<ItemRecord>>, AppError> {
+        let tag: String = validate_tag(tag)?;
+        self.filter_items(storage, hierarchy, |x| x.tags.contains(&tag))
+    }
+
+    /// Fetch the tasks and subtasks in swimlanes not marked as done which are assigned to given
+    /// person (or to nobody if None), grouped by swimlane
+    pub fn find_by_assignee(
+        &self,
+        storage: &dyn Storage,
+        hierarchy: &Hierarchy,
+        handle: Option<&str>,
+    ) -> Result<Vec<Section<ItemRecord>>, AppError> {
+        let handle: Option<String> = match handle {
+            Some(s) => Some(self.get_person(s)?.handle.clone()),
+            None => None,
         };
+        self.filter_items(storage, hierarchy, |x| x.assignee == handle)
+    }
+
+    /// Fetch the tasks and subtasks in swimlanes not marked as done which are estimated in given
+    /// unit (or not estimated at all if None), counting the estimates of their subtasks, grouped by
+    /// swimlane
+    pub fn find_by_estimate(
+        &self,
+        storage: &dyn Storage,
+        hierarchy: &Hierarchy,
+        unit: Option<EstimateUnit>,
+    ) -> Result<Vec<Section<ItemRecord>>, AppError> {
+        self.filter_items(storage, hierarchy, |x| match unit {
+            Some(EstimateUnit::Points) => x.estimate.points > 0,
+            Some(EstimateUnit::Time) => x.estimate.minutes > 0,
+            None => x.estimate.is_empty(),
+        })
+    }
 
-        let tasks_link: TaskToSubtaskMap = TaskToSubtaskMap::load_from_file()?;
+    /// Fetch the tasks and subtasks in swimlanes not marked as done which match given filter,
+    /// grouped by swimlane
+    fn filter_items(
+        &self,
+        storage: &dyn Storage,
+        hierarchy: &Hierarchy,
+        filter: impl Fn(&ItemRecord) -> bool,
+    ) -> Result<Vec<Section<ItemRecord>>, AppError> {
+        let mut sections: Vec<Section<ItemRecord>> = Vec::new();
 
-        for swimlane in swimlane_to_show {
-            let tasks: &Vec<String> = match self.boards.get(&swimlane) {
-                Some(s) => s,
-                None => {
-                    return Err(AppError::SwimlaneNotFoundError(format!(
-                        "Swimlane {} not found.",
-                        swimlane
-                    )))
-                }
-            };
-            println!("====================");
-            println!("{:#?}", swimlane.to_string());
-            println!("====================");
-
-            let mut display_table: Vec<Vec<String>> = Vec::new();
-            for subtask_id in tasks {
-                if SubTaskItem::check_if_file_exists(subtask_id)? == true {
-                    let subtask_item: SubTaskItem = SubTaskItem::get_task(subtask_id)?;
-                    let subtask_deadline: String = match subtask_item.subtask_deadline {
-                        Some(s) => s.to_naivedate()?.format("%b %e, %Y").to_string(),
-                        None => "None".to_string(),
-                    };
-                    let task_id: String = match tasks_link.get_task_id(subtask_id) {
-                        Some(s) => s,
-                        None => "None".to_string(),
-                    };
-                    display_table.push(vec![
-                        subtask_id.clone(),
-                        subtask_item.subtask_name,
-                        subtask_item.subtask_priority.to_string(),
-                        subtask_deadline,
-                        task_id,
-                    ])
+        for swimlane in self.open_swimlanes() {
+            let mut records: Vec<ItemRecord> = Vec::new();
+            for item_id in &swimlane.items {
+                if WorkItem::check_if_exists(storage, item_id)? {
+                    let work_item: WorkItem = WorkItem::get(storage, item_id)?;
+                    records.push(ItemRecord::from_item(storage, self, hierarchy, work_item)?);
                 }
             }
+            records.retain(|x| filter(x));
+            sections.push(Section {
+                heading: swimlane.name.clone(),
+                records,
+            });
+        }
 
-            let display_table: TableDisplay = match display_table
-                .table()
-                .title(vec![
-                    "Subtask ID".cell().bold(true),
-                    "Subtask Name".cell().bold(true),
-                    "Priority".cell().bold(true),
-                    "Deadline".cell().bold(true),
-                    "Parent Task".cell().bold(true),
-                ])
-                .display()
-            {
-                Ok(s) => s,
-                Err(e) => return Err(AppError::TableDisplayParseError(e.to_string())),
-            };
+        Ok(sections)
+    }
 
-            println!("{}", display_table);
+    /// Fetch all non-completed tasks and subtasks which are in swimlanes not marked as done
+    pub fn get_tasks_list(&self, storage: &dyn Storage) -> Result<Vec<String>, AppError> {
+        let mut tasks_list: Vec<String> = Vec::new();
+        for swimlane in self.open_swimlanes() {
+            for item_id in &swimlane.items {
+                if WorkItem::check_if_exists(storage, item_id)? {
+                    tasks_list.push(item_id.clone());
+                }
+            }
         }
+        Ok(tasks_list)
+    }
 
+    /// Add a given Task or SubTask ID to swimlane with given slug in the Kanban Board
+    pub fn add_to_board(
+        &mut self,
+        storage: &dyn Storage,
+        task_id: String,
+        swimlane: &str,
+    ) -> Result<(), AppError> {
+        self.get_swimlane_mut(swimlane)?.items.push(task_id);
+        self.save(storage)?;
         Ok(())
     }
 
-    /// Filter and display the tasks and subtasks based on deadline keyword provided.
-    ///
-    /// - `no-deadline`: Shows all tasks and subtasks which don't have a deadline defined.
-    /// - `past-deadline`: Shows all tasks and subtasks which are past their set deadline.
-    /// - `today`: Shows all tasks and subtasks which have today's date as deadline.
-    /// - `tomorrow`: Shows all tasks and subtasks which have tomorrow's date as  deadline.
-    /// - `after-tomorrow`: Shows all tasks and subtasks whose deadlines are upcoming after tomorrow.
-    pub fn filter_deadline(&self, keyword: &str) -> Result<(), AppError> {
-        for swimlane in vec![
-            TaskStatus::ToDo,
-            TaskStatus::InProgress,
-            TaskStatus::Blocked,
-            TaskStatus::InReview,
-        ] {
-            let tasks_link: TaskToSubtaskMap = TaskToSubtaskMap::load_from_file()?;
-            let tasks: &Vec<String> = match self.boards.get(&swimlane) {
-                Some(s) => s,
-                None => {
-                    return Err(AppError::SwimlaneNotFoundError(format!(
-                        "Swimlane {} not found.",
-                        swimlane
-                    )))
-                }
-            };
-            println!("====================");
-            println!("{:#?}", swimlane.to_string());
-            println!("====================");
-
-            let mut display_table: Vec<Vec<String>> = Vec::new();
-            for task_id in tasks {
-                if TaskItem::check_if_file_exists(task_id)? == true {
-                    let task_item: TaskItem = TaskItem::get_task(task_id)?;
-                    let num_subtasks: String = tasks_link.get_num_subtasks(task_id).to_string();
-
-                    if keyword == "no-deadline" {
-                        if task_item.task_deadline == None {
-                            display_table.push(vec![
-                                task_id.clone(),
-                                task_item.task_name,
-                                task_item.task_priority.to_string(),
-                                "None".to_string(),
-                                num_subtasks,
-                            ])
-                        }
-                    } else {
-                        if task_item.task_deadline != None {
-                            let task_deadline: NaiveDate =
-                                task_item.task_deadline.unwrap().to_naivedate()?;
-                            match keyword {
-                                "past-deadline" => {
-                                    if task_deadline < TimeStamp::new().to_naivedate()? {
-                                        display_table.push(
-                                            vec![task_id.clone(), task_item.task_name, task_item.task_priority.to_string(), task_deadline.to_string(), num_subtasks]
-                                        )
-                                    }
-                                },
-                                "today" => {
-                                    if task_deadline == TimeStamp::new().to_naivedate()? {
-                                        display_table.push(
-                                            vec![task_id.clone(), task_item.task_name, task_item.task_priority.to_string(), task_deadline.to_string(), num_subtasks]
-                                        )
-                                    }
-                                },
-                                "tomorrow" => {
-                                    if task_deadline == TimeStamp::new().to_naivedate()?.checked_add_days(Days::new(1)).unwrap() {
-                                        display_table.push(
-                                            vec![task_id.clone(), task_item.task_name, task_item.task_priority.to_string(), task_deadline.to_string(), num_subtasks]
-                                        )
-                                    }
-                                },
-                                "after-tomorrow" => {
-                                    if task_deadline > TimeStamp::new().to_naivedate()?.checked_add_days(Days::new(1)).unwrap() {
-                                        display_table.push(
-                                            vec![task_id.clone(), task_item.task_name, task_item.task_priority.to_string(), task_deadline.to_string(), num_subtasks]
-                                        )
-                                    }
-                                },
-                                _ => return Err(AppError::InvalidDeadlineKeyword (
-                                    format!("{} \nPlease select from following options: \n1) past-deadline 2) today 3) tomorrow 4) after-tomorrow 5) no-deadline\n", keyword)))
-                            }
-                        }
-                    }
-                }
+    /// Move a given Task or SubTask ID to different swimlanes in the Kanban Board
+    pub fn update_board(
+        &mut self,
+        storage: &dyn Storage,
+        task_id: String,
+        current_swimlane: &str,
+        new_swimlane: &str,
+    ) -> Result<(), AppError> {
+        self.get_swimlane(new_swimlane)?;
+
+        let tasks_list: &mut Vec<String> = &mut self.get_swimlane_mut(current_swimlane)?.items;
+        if let Some(s) = tasks_list.iter().position(|x| *x == task_id) {
+            tasks_list.remove(s);
+        };
+
+        self.get_swimlane_mut(new_swimlane)?.items.push(task_id);
+        self.save(storage)?;
+        Ok(())
+    }
+
+    /// Delete a given Task or SubTask ID in a specified swimlane in the Kanban Board
+    pub fn delete_task(
+        &mut self,
+        storage: &dyn Storage,
+        task_id: String,
+        swimlane: &str,
+    ) -> Result<(), AppError> {
+        let tasks_list: &mut Vec<String> = &mut self.get_swimlane_mut(swimlane)?.items;
+        if let Some(s) = tasks_list.iter().position(|x| *x == task_id) {
+            tasks_list.remove(s);
+        };
+        self.save(storage)?;
+        Ok(())
+    }
+
+    /// Fetch all Task and SubTask IDs across all swimlanes in the Kanban Board
+    pub fn get_all_items(&self) -> Vec<String> {
+        self.swimlanes
+            .iter()
+            .flat_map(|x| x.items.iter())
+            .filter(|x| !x.is_empty())
+            .cloned()
+            .collect()
+    }
+
+    /// Fetch all Task and SubTask IDs stored for the board, including those in the trash and the
+    /// archive
+    pub fn get_stored_items(&self) -> Vec<String> {
+        let mut items: Vec<String> = self.get_all_items();
+        items.extend(
+            self.trash
+                .iter()
+                .chain(self.archive.iter())
+                .map(|x| x.item_id.clone()),
+        );
+        items
+    }
 
-                if SubTaskItem::check_if_file_exists(task_id)? == true {
-                    let subtask_item: SubTaskItem = SubTaskItem::get_task(task_id)?;
-                    let parent_task_id: String = match tasks_link.get_task_id(task_id) {
-                        Some(s) => s,
-                        None => {
-                            return Err(AppError::TaskNotFound(format!(
-                                "Parent task not found for subtask {}",
-                                task_id
-                            )))
-                        }
-                    };
-
-                    if keyword == "no-deadline" {
-                        if subtask_item.subtask_deadline == None {
-                            display_table.push(vec![
-                                task_id.clone(),
-                                subtask_item.subtask_name,
-                                subtask_item.subtask_priority.to_string(),
-                                "None".to_string(),
-                                parent_task_id,
-                            ])
-                        }
-                    } else {
-                        if subtask_item.subtask_deadline != None {
-                            let subtask_deadline: NaiveDate =
-                                subtask_item.subtask_deadline.unwrap().to_naivedate()?;
-                            match keyword {
-                                "past-deadline" => {
-                                    if subtask_deadline < TimeStamp::new().to_naivedate()? {
-                                        display_table.push(
-                                            vec![task_id.clone(), subtask_item.subtask_name, subtask_item.subtask_priority.to_string(), subtask_deadline.to_string(), parent_task_id]
-                                        )
-                                    }
-                                },
-                                "today" => {
-                                    if subtask_deadline == TimeStamp::new().to_naivedate()? {
-                                        display_table.push(
-                                            vec![task_id.clone(), subtask_item.subtask_name, subtask_item.subtask_priority.to_string(), subtask_deadline.to_string(), parent_task_id]
-                                        )
-                                    }
-                                },
-                                "tomorrow" => {
-                                    if subtask_deadline == TimeStamp::new().to_naivedate()?.checked_add_days(Days::new(1)).unwrap() {
-                                        display_table.push(
-                                            vec![task_id.clone(), subtask_item.subtask_name, subtask_item.subtask_priority.to_string(), subtask_deadline.to_string(), parent_task_id]
-                                        )
-                                    }
-                                },
-                                "after-tomorrow" => {
-                                    if subtask_deadline > TimeStamp::new().to_naivedate()?.checked_add_days(Days::new(1)).unwrap() {
-                                        display_table.push(
-                                            vec![task_id.clone(), subtask_item.subtask_name, subtask_item.subtask_priority.to_string(), subtask_deadline.to_string(), parent_task_id]
-                                        )
-                                    }
-                                },
-                                _ => return Err(AppError::InvalidDeadlineKeyword (
-                                    format!("{} \nPlease select from following options: \n1) past-deadline 2) today 3) tomorrow 4) after-tomorrow 5) no-deadline\n", keyword)))
-                            }
-                        }
-                    }
+    /// Fetch all tasks which are in swimlanes marked as done
+    pub fn get_done_tasks(&self, storage: &dyn Storage) -> Result<Vec<String>, AppError> {
+        let mut tasks_list: Vec<String> = Vec::new();
+        for swimlane in self.swimlanes.iter().filter(|x| x.stage == LaneStage::Done) {
+            for task_id in &swimlane.items {
+                if WorkItem::check_if_exists(storage, task_id)?
+                    && WorkItem::get(storage, task_id)?.parent.is_none()
+                {
+                    tasks_list.push(task_id.clone());
                 }
             }
+        }
+        Ok(tasks_list)
+    }
 
-            let display_table: TableDisplay = match display_table
-                .table()
-                .title(vec![
-                    "Task ID".cell().bold(true),
-                    "Task Name".cell().bold(true),
-                    "Priority".cell().bold(true),
-                    "Deadline".cell().bold(true),
-                    "Subtasks/Parent Task".cell().bold(true),
-                ])
-                .display()
-            {
-                Ok(s) => s,
-                Err(e) => return Err(AppError::TableDisplayParseError(e.to_string())),
-            };
+    /// Find the trash or archive entry of given Task or SubTask ID (None if it is on the board)
+    pub fn find_hidden(&self, item_id: &str) -> Option<(Shelf, &HiddenItem)> {
+        for shelf in [Shelf::Trash, Shelf::Archive] {
+            if let Some(s) = self.shelf(shelf).iter().find(|x| x.item_id == item_id) {
+                return Some((shelf, s));
+            }
+        }
+        None
+    }
 
-            println!("{}", display_table);
+    /// Check if given Task or SubTask ID is done (in a done swimlane or in the archive)
+    pub fn is_completed(&self, item_id: &str) -> bool {
+        match self.item_swimlane(item_id) {
+            Some(s) => s.stage == LaneStage::Done,
+            None => matches!(self.find_hidden(item_id), Some((Shelf::Archive, _))),
         }
+    }
 
-        Ok(())
+    /// Check that given Task or SubTask ID is not in the trash or the archive
+    pub fn check_on_board(&self, item_id: &str) -> Result<(), AppError> {
+        match self.find_hidden(item_id) {
+            Some((shelf, _)) => Err(AppError::InvalidArgument(format!(
+                "{} is in the {}. Please restore it first.",
+                item_id, shelf
+            ))),
+            None => Ok(()),
+        }
     }
 
-    /// Filter and display the tasks and subtasks based on priority keyword provided.
-    ///
-    /// - `high`: Shows all high priority tasks and subtasks.
-    /// - `medium`: Shows all medium priority tasks and subtasks.
-    /// - `low`: Shows all low priority tasks and subtasks.
-    pub fn filter_priority(&self, keyword: &str) -> Result<(), AppError> {
-        for swimlane in vec![
-            TaskStatus::ToDo,
-            TaskStatus::InProgress,
-            TaskStatus::Blocked,
-            TaskStatus::InReview,
-        ] {
-            let tasks_link: TaskToSubtaskMap = TaskToSubtaskMap::load_from_file()?;
-            let tasks: &Vec<String> = match self.boards.get(&swimlane) {
-                Some(s) => s,
-                None => {
-                    return Err(AppError::SwimlaneNotFoundError(format!(
-                        "Swimlane {} not found.",
-                        swimlane
-                    )))
-                }
-            };
-            println!("====================");
-            println!("{:#?}", swimlane.to_string());
-            println!("====================");
-
-            let mut display_table: Vec<Vec<String>> = Vec::new();
-            for task_id in tasks {
-                if TaskItem::check_if_file_exists(task_id)? == true {
-                    let task_item: TaskItem = TaskItem::get_task(task_id)?;
-                    let task_deadline: String = match task_item.task_deadline {
-                        Some(s) => s.to_naivedate()?.format("%b %e, %Y").to_string(),
-                        None => "None".to_string(),
-                    };
-                    let num_subtasks: String = tasks_link.get_num_subtasks(task_id).to_string();
-
-                    match keyword {
-                        "high" => {
-                            if task_item.task_priority == TaskPriority::High {
-                                display_table.push(
-                                    vec![task_id.clone(), task_item.task_name, task_item.task_priority.to_string(), task_deadline.to_string(), num_subtasks]
-                                )
-                            }
-                        },
-                        "medium" => {
-                            if task_item.task_priority == TaskPriority::Medium {
-                                display_table.push(
-                                    vec![task_id.clone(), task_item.task_name, task_item.task_priority.to_string(), task_deadline.to_string(), num_subtasks]
-                                )
-                            }
-                        },
-                        "low" => {
-                            if task_item.task_priority == TaskPriority::Low {
-                                display_table.push(
-                                    vec![task_id.clone(), task_item.task_name, task_item.task_priority.to_string(), task_deadline.to_string(), num_subtasks]
-                                )
-                            }
-                        },
-                        _ => return Err(AppError::InvalidPriorityKeyword (
-                            format!("{} \nPlease select from following options: \n1) high 2) medium 3) low\n", keyword)))
-                    }
-                }
+    /// Take given Task or SubTask ID off the swimlane with given slug into the trash or the
+    /// archive. `hidden_with` is the Task or SubTask ID a subtask is taken off along with.
+    pub fn hide_item(
+        &mut self,
+        storage: &dyn Storage,
+        item_id: &str,
+        swimlane: &str,
+        shelf: Shelf,
+        hidden_with: Option<&str>,
+    ) -> Result<(), AppError> {
+        self.check_on_board(item_id)?;
+        let tasks_list: &mut Vec<String> = &mut self.get_swimlane_mut(swimlane)?.items;
+        if let Some(s) = tasks_list.iter().position(|x| x == item_id) {
+            tasks_list.remove(s);
+        };
+        self.shelf_mut(shelf).push(HiddenItem {
+            item_id: item_id.to_string(),
+            swimlane: swimlane.to_string(),
+            hidden_on: TimeStamp::new(),
+            hidden_with: hidden_with.map(|x| x.to_string()),
+        });
+        self.save(storage)
+    }
 
-                if SubTaskItem::check_if_file_exists(task_id)? == true {
-                    let subtask_item: SubTaskItem = SubTaskItem::get_task(task_id)?;
-                    let subtask_deadline: String = match subtask_item.subtask_deadline {
-                        Some(s) => s.to_naivedate()?.format("%b %e, %Y").to_string(),
-                        None => "None".to_string(),
-                    };
-                    let parent_task_id: String = match tasks_link.get_task_id(task_id) {
-                        Some(s) => s,
-                        None => {
-                            return Err(AppError::TaskNotFound(format!(
-                                "Parent task not found for subtask {}",
-                                task_id
-                            )))
-                        }
-                    };
-
-                    match keyword {
-                        "high" => {
-                            if subtask_item.subtask_priority == TaskPriority::High {
-                                display_table.push(
-                                    vec![task_id.clone(), subtask_item.subtask_name, subtask_item.subtask_priority.to_string(), subtask_deadline.to_string(), parent_task_id]
-                                )
-                            }
-                        },
-                        "medium" => {
-                            if subtask_item.subtask_priority == TaskPriority::Medium {
-                                display_table.push(
-                                    vec![task_id.clone(), subtask_item.subtask_name, subtask_item.subtask_priority.to_string(), subtask_deadline.to_string(), parent_task_id]
-                                )
-                            }
-                        },
-                        "low" => {
-                            if subtask_item.subtask_priority == TaskPriority::Low {
-                                display_table.push(
-                                    vec![task_id.clone(), subtask_item.subtask_name, subtask_item.subtask_priority.to_string(), subtask_deadline.to_string(), parent_task_id]
-                                )
-                            }
-                        },
-                        _ => return Err(AppError::InvalidPriorityKeyword (
-                            format!("{} \nPlease select from following options: \n1) high 2) medium 3) low\n", keyword)))
-                    }
-                }
-            }
+    /// Put given Task or SubTask ID back from the trash or the archive, along with the subtasks
+    /// taken off with it. Items go back into their swimlane, or into the first swimlane if it was
+    /// removed since. Returns each restored ID with the slug of the swimlane it was put into.
+    pub fn restore_item(
+        &mut self,
+        storage: &dyn Storage,
+        item_id: &str,
+        shelf: Shelf,
+    ) -> Result<Vec<(String, String)>, AppError> {
+        if !self.shelf(shelf).iter().any(|x| x.item_id == item_id) {
+            return Err(AppError::TaskNotFound(format!(
+                "{} is not in the {}.",
+                item_id, shelf
+            )));
+        }
+
+        let first_swimlane: String = self.first_swimlane()?.slug.clone();
+        let (restored, kept): (Vec<HiddenItem>, Vec<HiddenItem>) = self
+            .shelf_mut(shelf)
+            .drain(..)
+            .partition(|x| x.item_id == item_id || x.hidden_with.as_deref() == Some(item_id));
+        *self.shelf_mut(shelf) = kept;
 
-            let display_table: TableDisplay = match display_table
-                .table()
-                .title(vec![
-                    "Task ID".cell().bold(true),
-                    "Task Name".cell().bold(true),
-                    "Priority".cell().bold(true),
-                    "Deadline".cell().bold(true),
-                    "Subtasks/Parent Task".cell().bold(true),
-                ])
-                .display()
+        let mut restored_items: Vec<(String, String)> = Vec::new();
+        for hidden_item in restored {
+            let slug: String = match self
+                .swimlanes
+                .iter()
+                .any(|x| x.slug == hidden_item.swimlane)
             {
-                Ok(s) => s,
-                Err(e) => return Err(AppError::TableDisplayParseError(e.to_string())),
+                true => hidden_item.swimlane,
+                false => first_swimlane.clone(),
             };
-
-            println!("{}", display_table);
+            self.get_swimlane_mut(&slug)?
+                .items
+                .push(hidden_item.item_id.clone());
+            restored_items.push((hidden_item.item_id, slug));
         }
+        self.save(storage)?;
+        Ok(restored_items)
+    }
 
-        Ok(())
+    /// Remove all entries from the trash or the archive, returning them
+    pub fn clear_shelf(
+        &mut self,
+        storage: &dyn Storage,
+        shelf: Shelf,
+    ) -> Result<Vec<HiddenItem>, AppError> {
+        let hidden_items: Vec<HiddenItem> = std::mem::take(self.shelf_mut(shelf));
+        self.save(storage)?;
+        Ok(hidden_items)
     }
 
-    /// Fetch all non-completed tasks and subtasks which are in "to-do", "in-progress", "blocked", and "in-review" swimlanes
-    pub fn get_tasks_list(&self) -> Result<Vec<String>, AppError> {
-        let mut tasks_list: Vec<String> = Vec::new();
-        for swimlane in vec![
-            TaskStatus::ToDo,
-            TaskStatus::InProgress,
-            TaskStatus::Blocked,
-            TaskStatus::InReview,
-        ] {
-            let tasks: Vec<String> = match self.boards.get(&swimlane) {
-                Some(s) => s.to_vec(),
-                None => {
-                    return Err(AppError::SwimlaneNotFoundError(format!(
-                        "Swimlane {} not found.",
-                        swimlane
-                    )))
-                }
+    /// Fetch the records of all tasks and subtasks in the trash or the archive, in the order they
+    /// were hidden
+    pub fn hidden_records(
+        &self,
+        storage: &dyn Storage,
+        shelf: Shelf,
+    ) -> Result<Vec<HiddenRecord>, AppError> {
+        let mut hidden_records: Vec<HiddenRecord> = Vec::new();
+        for hidden_item in self.shelf(shelf) {
+            let item_id: &str = &hidden_item.item_id;
+            let name: Option<String> = match WorkItem::check_if_exists(storage, item_id)? {
+                true => Some(WorkItem::get(storage, item_id)?.name),
+                false => None,
             };
-            for task_id in tasks {
-                if TaskItem::check_if_file_exists(&task_id)? == true {
-                    tasks_list.push(task_id);
-                }
-            }
+            hidden_records.push(HiddenRecord {
+                id: item_id.to_string(),
+                name,
+                swimlane: hidden_item.swimlane.clone(),
+                hidden_on: hidden_item.hidden_on.to_naivedate()?,
+                hidden_with: hidden_item.hidden_with.clone(),
+            });
         }
-        Ok(tasks_list)
+        Ok(hidden_records)
     }
 
-    /// Add a given Task or SubTask ID to specific swimlane in the Kanban Board
-    pub fn add_to_board(&mut self, task_id: String, swimlane: TaskStatus) -> Result<(), AppError> {
-        let mut tasks_list: Vec<String> = match self.boards.get(&swimlane) {
-            Some(s) => s.to_vec(),
-            None => Vec::new(),
-        };
-        tasks_list.push(task_id);
-        self.boards.insert(swimlane, tasks_list);
-        self.write_to_file()?;
-        Ok(())
+    /// Fetch the entries of the trash or the archive
+    pub fn shelf(&self, shelf: Shelf) -> &[HiddenItem] {
+        match shelf {
+            Shelf::Trash => &self.trash,
+            Shelf::Archive => &self.archive,
+        }
     }
 
-    /// Move a given Task or SubTask ID to different swimlanes in the Kanban Board
-    pub fn update_board(
+    /// Fetch the entries of the trash or the archive for modification
+    pub(crate) fn shelf_mut(&mut self, shelf: Shelf) -> &mut Vec<HiddenItem> {
+        match shelf {
+            Shelf::Trash => &mut self.trash,
+            Shelf::Archive => &mut self.archive,
+        }
+    }
+
+    /// Fetch the colors given to tags used on the board
+    pub fn tag_colors(&self) -> &BTreeMap<String, TagColor> {
+        &self.tag_colors
+    }
+
+    /// Set the color of given tag (None to remove its color)
+    pub fn set_tag_color(
         &mut self,
-        task_id: String,
-        current_swimlane: TaskStatus,
-        new_swimlane: &str,
+        storage: &dyn Storage,
+        tag: &str,
+        color: Option<TagColor>,
     ) -> Result<(), AppError> {
-        let new_swimlane: TaskStatus = match new_swimlane {
-            "to-do" => TaskStatus::ToDo,
-            "in-progress" => TaskStatus::InProgress,
-            "blocked" => TaskStatus::Blocked,
-            "in-review" => TaskStatus::InReview,
-            "done" => TaskStatus::Done,
-            _ => return Err(AppError::InvalidSwimlanePassed(
-                format!("{} \nPlease select from following options: \n1) to-do 2) in-progress 3) blocked 4) in-review 5) done\n", new_swimlane.to_string())))
+        match color {
+            Some(s) => self.tag_colors.insert(tag.to_string(), s),
+            None => self.tag_colors.remove(tag),
         };
+        self.save(storage)
+    }
+
+    /// Hand the color of given tag over to the tag it is renamed or merged into (which keeps its
+    /// own color if it has one)
+    pub fn move_tag_color(
+        &mut self,
+        storage: &dyn Storage,
+        tag: &str,
+        new_tag: &str,
+    ) -> Result<(), AppError> {
+        if let Some(color) = self.tag_colors.remove(tag) {
+            self.tag_colors.entry(new_tag.to_string()).or_insert(color);
+        }
+        self.save(storage)
+    }
+
+    /// Fetch all people registered on the board
+    pub fn people(&self) -> &[Person] {
+        &self.people
+    }
 
-        let mut tasks_list: Vec<String> = match self.boards.get(&current_swimlane) {
-            Some(s) => s.to_vec(),
+    /// Fetch the person with given handle
+    pub fn get_person(&self, handle: &str) -> Result<&Person, AppError> {
+        let handle: String = validate_handle(handle)?;
+        match self.people.iter().find(|x| x.handle == handle) {
+            Some(s) => Ok(s),
+            None => Err(AppError::UserNotFound(format!(
+                "{} - Please add the person with user add first.",
+                handle
+            ))),
+        }
+    }
+
+    /// Register a new person on the board
+    pub fn add_person(&mut self, storage: &dyn Storage, person: Person) -> Result<(), AppError> {
+        if self.people.iter().any(|x| x.handle == person.handle) {
+            return Err(AppError::InvalidArgument(format!(
+                "{} - A person with this handle already exists.",
+                person.handle
+            )));
+        }
+        self.people.push(person);
+        self.save(storage)
+    }
+
+    /// Remove the person with given handle from the board
+    pub fn remove_person(&mut self, storage: &dyn Storage, handle: &str) -> Result<(), AppError> {
+        let handle: String = self.get_person(handle)?.handle.clone();
+        self.people.retain(|x| x.handle != handle);
+        self.save(storage)
+    }
+
+    /// Fetch the dependencies between the tasks and subtasks of the board
+    pub fn dependencies(&self) -> &Dependencies {
+        &self.dependencies
+    }
+
+    /// Record that given Task or SubTask ID is blocked by another one
+    pub fn add_dependency(
+        &mut self,
+        storage: &dyn Storage,
+        item_id: &str,
+        blocker_id: &str,
+    ) -> Result<(), AppError> {
+        self.dependencies.add(item_id, blocker_id)?;
+        self.save(storage)
+    }
+
+    /// Remove the dependency of given Task or SubTask ID on another one
+    pub fn remove_dependency(
+        &mut self,
+        storage: &dyn Storage,
+        item_id: &str,
+        blocker_id: &str,
+    ) -> Result<(), AppError> {
+        if !self.dependencies.remove(item_id, blocker_id) {
+            return Err(AppError::InvalidArgument(format!(
+                "{} does not depend on {}.",
+                item_id, blocker_id
+            )));
+        }
+        self.save(storage)
+    }
+
+    /// Remove all dependencies of and on given Task and SubTask IDs (when they are deleted for good)
+    pub fn forget_dependencies(
+        &mut self,
+        storage: &dyn Storage,
+        item_ids: &[String],
+    ) -> Result<(), AppError> {
+        for item_id in item_ids {
+            self.dependencies.remove_item(item_id);
+        }
+        self.save(storage)
+    }
+
+    /// Fetch the timers running on the board
+    pub fn timers(&self) -> &[RunningTimer] {
+        &self.timers
+    }
+
+    /// Fetch the timer running for given person (None when no current user is configured)
+    pub fn get_timer(&self, user: &Option<String>) -> Option<&RunningTimer> {
+        self.timers.iter().find(|x| &x.user == user)
+    }
+
+    /// Start a timer on given Task or SubTask ID for given person
+    pub fn start_timer(
+        &mut self,
+        storage: &dyn Storage,
+        item_id: &str,
+        user: Option<String>,
+    ) -> Result<(), AppError> {
+        if let Some(timer) = self.get_timer(&user) {
+            return Err(AppError::InvalidArgument(format!(
+                "A timer is already running on {} - Please stop it with timer stop first.",
+                timer.item_id
+            )));
+        }
+        self.timers.push(RunningTimer::new(item_id, user));
+        self.save(storage)
+    }
+
+    /// Stop the timer running for given person, returning it
+    pub fn stop_timer(
+        &mut self,
+        storage: &dyn Storage,
+        user: &Option<String>,
+    ) -> Result<RunningTimer, AppError> {
+        let timer: RunningTimer = match self.timers.iter().position(|x| &x.user == user) {
+            Some(s) => self.timers.remove(s),
             None => {
-                return Err(AppError::SwimlaneNotFoundError(format!(
-                    "Swimlane {} not found.",
-                    current_swimlane
-                )))
+                return Err(AppError::InvalidArgument(
+                    "No timer is running - Please start one with timer start first.".to_string(),
+                ))
             }
         };
-        match tasks_list.iter().position(|x| *x == task_id) {
-            Some(s) => {
-                tasks_list.remove(s);
-                self.boards.insert(current_swimlane, tasks_list);
-            }
-            None => {}
+        self.save(storage)?;
+        Ok(timer)
+    }
+
+    /// Remove the timers running on given Task and SubTask IDs (when they are deleted for good)
+    pub fn forget_timers(
+        &mut self,
+        storage: &dyn Storage,
+        item_ids: &[String],
+    ) -> Result<(), AppError> {
+        self.timers.retain(|x| !item_ids.contains(&x.item_id));
+        self.save(storage)
+    }
+
+    /// Fetch the sprints of the board, in the order they were created
+    pub fn sprints(&self) -> &[Sprint] {
+        &self.sprints
+    }
+
+    /// Fetch the sprint with given number
+    pub fn get_sprint(&self, number: u32) -> Result<&Sprint, AppError> {
+        match self.sprints.iter().find(|x| x.number == number) {
+            Some(s) => Ok(s),
+            None => Err(AppError::InvalidArgument(format!(
+                "Sprint {} not found.",
+                number
+            ))),
+        }
+    }
+
+    /// Fetch the current sprint (the earliest sprint which is still open)
+    pub fn current_sprint(&self) -> Result<&Sprint, AppError> {
+        match self.sprints.iter().find(|x| x.is_open()) {
+            Some(s) => Ok(s),
+            None => Err(AppError::InvalidArgument(
+                "No sprint is open - Please create one with sprint create first.".to_string(),
+            )),
+        }
+    }
+
+    /// Fetch the open sprint given Task ID is planned into (None if it is in the backlog)
+    pub fn item_sprint(&self, item_id: &str) -> Option<&Sprint> {
+        self.sprints
+            .iter()
+            .find(|x| x.is_open() && x.contains(item_id))
+    }
+
+    /// Add a new sprint with given goal to the board. It starts on given day (by default the day
+    /// after the last sprint ends, or today) and ends on given day (by default two weeks later).
+    pub fn add_sprint(
+        &mut self,
+        storage: &dyn Storage,
+        goal: &str,
+        start_on: Option<NaiveDate>,
+        end_on: Option<NaiveDate>,
+    ) -> Result<Sprint, AppError> {
+        let today: NaiveDate = TimeStamp::new().to_naivedate()?;
+        let start_on: NaiveDate = match (start_on, self.sprints.last()) {
+            (Some(s), _) => s,
+            (None, Some(s)) => (s.end_on.to_naivedate()? + Days::new(1)).max(today),
+            (None, None) => today,
         };
+        let end_on: NaiveDate = end_on.unwrap_or(start_on + Days::new(SPRINT_LENGTH_DAYS - 1));
+        let number: u32 = self.sprints.last().map(|x| x.number).unwrap_or(0) + 1;
+
+        let sprint: Sprint = Sprint::new(number, goal, start_on, end_on)?;
+        self.sprints.push(sprint.clone());
+        self.save(storage)?;
+        Ok(sprint)
+    }
+
+    /// Plan given Task ID into the open sprint with given number
+    pub fn plan_sprint(
+        &mut self,
+        storage: &dyn Storage,
+        number: u32,
+        item_id: &str,
+    ) -> Result<(), AppError> {
+        if !self.get_sprint(number)?.is_open() {
+            return Err(AppError::InvalidArgument(format!(
+                "Sprint {} is closed - Please plan tasks into an open sprint.",
+                number
+            )));
+        }
+        if let Some(sprint) = self.item_sprint(item_id) {
+            return Err(AppError::InvalidArgument(format!(
+                "{} is already planned into sprint {}.",
+                item_id, sprint.number
+            )));
+        }
+        if let Some(sprint) = self.sprints.iter_mut().find(|x| x.number == number) {
+            sprint.items.push(item_id.to_string());
+        }
+        self.save(storage)
+    }
 
-        let mut tasks_list: Vec<String> = match self.boards.get(&new_swimlane) {
-            Some(s) => s.to_vec(),
+    /// Take given Task ID out of the open sprint it is planned into, putting it back in the
+    /// backlog. Returns the number of the sprint.
+    pub fn unplan_sprint(&mut self, storage: &dyn Storage, item_id: &str) -> Result<u32, AppError> {
+        let sprint: &mut Sprint = match self
+            .sprints
+            .iter_mut()
+            .find(|x| x.is_open() && x.contains(item_id))
+        {
+            Some(s) => s,
             None => {
-                return Err(AppError::SwimlaneNotFoundError(format!(
-                    "Swimlane {} not found.",
-                    new_swimlane
+                return Err(AppError::InvalidArgument(format!(
+                    "{} is not planned into an open sprint.",
+                    item_id
                 )))
             }
         };
-        tasks_list.push(task_id);
-        self.boards.insert(new_swimlane, tasks_list);
-        self.write_to_file()?;
-        Ok(())
+        sprint.items.retain(|x| x != item_id);
+        let number: u32 = sprint.number;
+        self.save(storage)?;
+        Ok(number)
     }
 
-    /// Delete a given Task or SubTask ID in a specified swimlane in the Kanban Board
-    pub fn delete_task(&mut self, task_id: String, swimlane: TaskStatus) -> Result<(), AppError> {
-        let mut tasks_list: Vec<String> = match self.boards.get(&swimlane) {
-            Some(s) => s.to_vec(),
-            None => {
-                return Err(AppError::SwimlaneNotFoundError(format!(
-                    "Swimlane {} not found.",
-                    swimlane
-                )))
+    /// Close the sprint with given number, recording which of its tasks were completed and which
+    /// were carried over. The carried-over tasks are planned into the sprint with given number, or
+    /// put back in the backlog if None.
+    pub fn close_sprint(
+        &mut self,
+        storage: &dyn Storage,
+        number: u32,
+        completed: Vec<String>,
+        carried_over: Vec<String>,
+        carried_into: Option<u32>,
+    ) -> Result<(), AppError> {
+        for sprint in self.sprints.iter_mut() {
+            if sprint.number == number {
+                sprint.closed_on = Some(TimeStamp::new());
+                sprint.completed = completed.clone();
+                sprint.carried_over = carried_over.clone();
+            } else if Some(sprint.number) == carried_into {
+                sprint.items.extend(carried_over.iter().cloned());
             }
-        };
-        match tasks_list.iter().position(|x| *x == task_id) {
-            Some(s) => {
-                tasks_list.remove(s);
-                self.boards.insert(swimlane, tasks_list);
+        }
+        self.save(storage)
+    }
+
+    /// Remove given Task IDs from all sprints (when they are deleted for good)
+    pub fn forget_sprint_items(
+        &mut self,
+        storage: &dyn Storage,
+        item_ids: &[String],
+    ) -> Result<(), AppError> {
+        for sprint in self.sprints.iter_mut() {
+            for list in [
+                &mut sprint.items,
+                &mut sprint.completed,
+                &mut sprint.carried_over,
+            ] {
+                list.retain(|x| !item_ids.contains(x));
             }
-            None => {}
-        };
-        self.write_to_file()?;
-        Ok(())
+        }
+        self.save(storage)
     }
 
-    /// Load the Kanban Board from stored file in disk
-    pub fn load_from_file() -> Result<Self, AppError> {
-        let app_dir: String = create_app_dirs()?;
-        let data: Vec<u8> = match std::fs::read(format!("{}\\{}", app_dir, KANBAN_BOARD_FILE)) {
-            Ok(s) => s,
-            Err(e) => {
-                return Err(AppError::FileReadError(format!(
-                    "{} - {}",
-                    KANBAN_BOARD_FILE,
-                    e.to_string()
-                )))
+    /// Fetch the blockers of given Task or SubTask ID which are on the board but not in a done
+    /// swimlane (blockers in the trash or the archive no longer block it)
+    pub fn unfinished_blockers(&self, item_id: &str) -> Vec<String> {
+        self.dependencies
+            .blockers(item_id)
+            .into_iter()
+            .filter(|x| {
+                self.item_swimlane(x)
+                    .is_some_and(|y| y.stage != LaneStage::Done)
+            })
+            .collect()
+    }
+
+    /// Fetch the dependents of given Task or SubTask ID waiting in a blocked swimlane which have
+    /// no unfinished blockers left
+    pub fn unblocked_dependents(&self, item_id: &str) -> Vec<String> {
+        self.dependencies
+            .dependents(item_id)
+            .into_iter()
+            .filter(|x| {
+                self.item_swimlane(x).is_some_and(|y| y.blocked)
+                    && self.unfinished_blockers(x).is_empty()
+            })
+            .collect()
+    }
+
+    /// Fetch the swimlane which unblocked tasks and subtasks are moved into (the first started
+    /// swimlane other than the blocked swimlanes)
+    pub fn unblocked_swimlane(&self) -> Option<&Swimlane> {
+        self.swimlanes
+            .iter()
+            .find(|x| x.stage == LaneStage::Started && !x.blocked)
+    }
+
+    /// Describe every chain of blockers leading to given Task or SubTask ID along with where each
+    /// blocker is (e.g. "TASK-2 (In Progress) <- TASK-1 (Done)")
+    pub fn dependency_chains(&self, item_id: &str) -> Vec<String> {
+        self.dependencies
+            .chains(item_id)
+            .iter()
+            .map(|chain| {
+                chain
+                    .iter()
+                    .map(|x| {
+                        let location: String = match (self.item_swimlane(x), self.find_hidden(x)) {
+                            (Some(s), _) => s.name.clone(),
+                            (None, Some((shelf, _))) => shelf.to_string(),
+                            (None, None) => "missing".to_string(),
+                        };
+                        format!("{} ({})", x, location)
+                    })
+                    .collect::<Vec<String>>()
+                    .join(" <- ")
+            })
+            .collect()
+    }
+
+    /// Fetch the swimlane listing given Task or SubTask ID (None if it is not on the board)
+    pub fn item_swimlane(&self, item_id: &str) -> Option<&Swimlane> {
+        self.swimlanes
+            .iter()
+            .find(|x| x.items.iter().any(|y| y == item_id))
+    }
+
+    /// Fetch all swimlanes on the Kanban Board in display order
+    pub fn swimlanes(&self) -> &[Swimlane] {
+        &self.swimlanes
+    }
+
+    /// Fetch the swimlane with given slug
+    pub fn get_swimlane(&self, slug: &str) -> Result<&Swimlane, AppError> {
+        match self.swimlanes.iter().find(|x| x.slug == slug) {
+            Some(s) => Ok(s),
+            None => Err(AppError::InvalidSwimlanePassed(format!(
+                "{} \nPlease select from following options: \n{}\n",
+                slug,
+                self.describe_swimlanes(false)
+            ))),
+        }
+    }
+
+    /// Fetch the swimlane with given slug for modification
+    fn get_swimlane_mut(&mut self, slug: &str) -> Result<&mut Swimlane, AppError> {
+        self.get_swimlane(slug)?;
+        match self.swimlanes.iter_mut().find(|x| x.slug == slug) {
+            Some(s) => Ok(s),
+            None => Err(AppError::SwimlaneNotFoundError(format!(
+                "Swimlane {} not found.",
+                slug
+            ))),
+        }
+    }
+
+    /// Get the display name of the swimlane with given slug (the slug itself if not found)
+    pub fn swimlane_name(&self, slug: &str) -> String {
+        match self.swimlanes.iter().find(|x| x.slug == slug) {
+            Some(s) => s.name.clone(),
+            None => slug.to_string(),
+        }
+    }
+
+    /// Fetch the first swimlane, which new tasks and subtasks are added to
+    pub fn first_swimlane(&self) -> Result<&Swimlane, AppError> {
+        match self.swimlanes.first() {
+            Some(s) => Ok(s),
+            None => Err(AppError::SwimlaneNotFoundError(
+                "No swimlanes found on the board.".to_string(),
+            )),
+        }
+    }
+
+    /// Fetch the swimlanes to display for given slug (or all swimlanes for "all")
+    fn select_swimlanes(&self, swimlanes: &str) -> Result<Vec<&Swimlane>, AppError> {
+        match swimlanes {
+            "all" => Ok(self.swimlanes.iter().collect()),
+            _ => match self.swimlanes.iter().find(|x| x.slug == swimlanes) {
+                Some(s) => Ok(vec![s]),
+                None => Err(AppError::InvalidSwimlanePassed(format!(
+                    "{} \nPlease select from following options: \n{}\n",
+                    swimlanes,
+                    self.describe_swimlanes(true)
+                ))),
+            },
+        }
+    }
+
+    /// Fetch the swimlanes which are not marked as done
+    fn open_swimlanes(&self) -> Vec<&Swimlane> {
+        self.swimlanes
+            .iter()
+            .filter(|x| x.stage != LaneStage::Done)
+            .collect()
+    }
+
+    /// List the swimlane slugs as numbered options for error messages
+    fn describe_swimlanes(&self, include_all: bool) -> String {
+        let mut slugs: Vec<&str> = Vec::new();
+        if include_all {
+            slugs.push("all");
+        }
+        slugs.extend(self.swimlanes.iter().map(|x| x.slug.as_str()));
+        slugs
+            .iter()
+            .enumerate()
+            .map(|(idx, slug)| format!("{}) {}", idx + 1, slug))
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+
+    /// Fetch the records of all swimlanes on the Kanban Board in order, along with the time logged
+    /// on their items
+    pub fn swimlane_records(&self, storage: &dyn Storage) -> Result<Vec<SwimlaneRecord>, AppError> {
+        let mut swimlane_records: Vec<SwimlaneRecord> = Vec::new();
+        for (idx, x) in self.swimlanes.iter().enumerate() {
+            let mut minutes: u32 = 0;
+            for item_id in &x.items {
+                if WorkItem::check_if_exists(storage, item_id)? {
+                    let work_item: WorkItem = WorkItem::get(storage, item_id)?;
+                    minutes = minutes.saturating_add(worklog::total_minutes(&work_item.worklog));
+                }
             }
-        };
-        let boards: KanbanBoard = match bincode::deserialize(&data) {
-            Ok(s) => s,
-            Err(e) => return Err(AppError::BinaryDeserializationError(e.to_string())),
-        };
-        Ok(boards)
+            swimlane_records.push(SwimlaneRecord {
+                position: idx + 1,
+                slug: x.slug.clone(),
+                name: x.name.clone(),
+                stage: x.stage,
+                blocked: x.blocked,
+                items: x.items.len(),
+                wip_limit: x.wip_limit,
+                minutes,
+            });
+        }
+        Ok(swimlane_records)
     }
 
-    /// Store the Kanban Board information to a file in disk
-    pub fn write_to_file(&self) -> Result<(), AppError> {
-        let app_dir: String = create_app_dirs()?;
-        let data: Vec<u8> = match bincode::serialize(&self) {
-            Ok(s) => s,
-            Err(e) => return Err(AppError::BinarySerializationError(e.to_string())),
-        };
-        match std::fs::write(format!("{}\\{}", app_dir, KANBAN_BOARD_FILE), data) {
-            Ok(_) => {}
-            Err(e) => {
-                return Err(AppError::FileWriteError(format!(
-                    "{} - {}",
-                    KANBAN_BOARD_FILE,
-                    e.to_string()
-                )))
+    /// Add a new swimlane at the end of the Kanban Board
+    pub fn add_swimlane(
+        &mut self,
+        storage: &dyn Storage,
+        swimlane: Swimlane,
+    ) -> Result<(), AppError> {
+        if self.swimlanes.iter().any(|x| x.slug == swimlane.slug) {
+            return Err(AppError::InvalidArgument(format!(
+                "Swimlane {} already exists.",
+                swimlane.slug
+            )));
+        }
+        self.swimlanes.push(swimlane);
+        self.save(storage)
+    }
+
+    /// Rename the swimlane with given slug (deriving the new slug from the new name).
+    /// Returns the renamed swimlane, whose tasks and subtasks still need their status updated.
+    pub fn rename_swimlane(
+        &mut self,
+        storage: &dyn Storage,
+        slug: &str,
+        new_name: &str,
+    ) -> Result<Swimlane, AppError> {
+        let new_slug: String = slugify(new_name)?;
+        if new_slug != slug && self.swimlanes.iter().any(|x| x.slug == new_slug) {
+            return Err(AppError::InvalidArgument(format!(
+                "Swimlane {} already exists.",
+                new_slug
+            )));
+        }
+
+        for hidden_item in self.trash.iter_mut().chain(self.archive.iter_mut()) {
+            if hidden_item.swimlane == slug {
+                hidden_item.swimlane = new_slug.clone();
             }
+        }
+        let swimlane: &mut Swimlane = self.get_swimlane_mut(slug)?;
+        swimlane.slug = new_slug;
+        swimlane.name = new_name.to_string();
+        let swimlane: Swimlane = swimlane.clone();
+        self.save(storage)?;
+        Ok(swimlane)
+    }
+
+    /// Move the swimlane with given slug to given position (starting at 1) on the Kanban Board
+    pub fn reorder_swimlane(
+        &mut self,
+        storage: &dyn Storage,
+        slug: &str,
+        position: usize,
+    ) -> Result<(), AppError> {
+        if position == 0 || position > self.swimlanes.len() {
+            return Err(AppError::InvalidArgument(format!(
+                "{} - Position must be between 1 and {}.",
+                position,
+                self.swimlanes.len()
+            )));
+        }
+        let current: usize = match self.swimlanes.iter().position(|x| x.slug == slug) {
+            Some(s) => s,
+            None => return Err(self.get_swimlane(slug).unwrap_err()),
         };
-        Ok(())
+        let swimlane: Swimlane = self.swimlanes.remove(current);
+        self.swimlanes.insert(position - 1, swimlane);
+        self.save(storage)
+    }
+
+    /// Change the stage of work which the swimlane with given slug represents
+    pub fn mark_swimlane(
+        &mut self,
+        storage: &dyn Storage,
+        slug: &str,
+        stage: LaneStage,
+    ) -> Result<(), AppError> {
+        self.get_swimlane_mut(slug)?.stage = stage;
+        self.save(storage)
+    }
+
+    /// Set whether the swimlane with given slug holds tasks and subtasks waiting on their blockers
+    pub fn block_swimlane(
+        &mut self,
+        storage: &dyn Storage,
+        slug: &str,
+        blocked: bool,
+    ) -> Result<(), AppError> {
+        self.get_swimlane_mut(slug)?.blocked = blocked;
+        self.save(storage)
+    }
+
+    /// Set (or clear with None) the WIP limit of the swimlane with given slug
+    pub fn set_wip_limit(
+        &mut self,
+        storage: &dyn Storage,
+        slug: &str,
+        wip_limit: Option<WipLimit>,
+    ) -> Result<(), AppError> {
+        self.get_swimlane_mut(slug)?.wip_limit = wip_limit;
+        self.save(storage)
+    }
+
+    /// Check whether moving given Task or SubTask ID into the swimlane with given slug stays within
+    /// its WIP limit. Exceeding a strict limit is refused unless `force` is set.
+    pub fn check_wip_limit(
+        &self,
+        task_id: &str,
+        slug: &str,
+        force: bool,
+    ) -> Result<WipCheck, AppError> {
+        let swimlane: &Swimlane = self.get_swimlane(slug)?;
+        let wip_limit: WipLimit = match swimlane.wip_limit {
+            Some(s) => s,
+            None => return Ok(WipCheck::Allowed),
+        };
+        if swimlane.items.iter().any(|x| x == task_id) || swimlane.items.len() < wip_limit.max_items
+        {
+            return Ok(WipCheck::Allowed);
+        }
+
+        let message: String = format!(
+            "Swimlane {} is at its WIP limit of {} ({} tasks and subtasks).",
+            slug,
+            wip_limit.max_items,
+            swimlane.items.len()
+        );
+        match (wip_limit.strict, force) {
+            (false, _) => Ok(WipCheck::Warning(message)),
+            (true, true) => Ok(WipCheck::Overridden(message)),
+            (true, false) => Err(AppError::WipLimitExceeded(format!(
+                "{} Pass --force to move it anyway.",
+                message
+            ))),
+        }
+    }
+
+    /// Check whether given Task or SubTask ID can be moved into the swimlane with given slug while
+    /// its blockers are not done. Moving it into a started or done swimlane (other than a blocked
+    /// swimlane) is refused unless `force` is set. Returns the warning when it was overridden.
+    pub fn check_blockers(
+        &self,
+        item_id: &str,
+        slug: &str,
+        force: bool,
+    ) -> Result<Option<String>, AppError> {
+        let swimlane: &Swimlane = self.get_swimlane(slug)?;
+        if swimlane.stage == LaneStage::Pending || swimlane.blocked {
+            return Ok(None);
+        }
+        let blockers: Vec<String> = self.unfinished_blockers(item_id);
+        if blockers.is_empty() {
+            return Ok(None);
+        }
+
+        let message: String = format!(
+            "{} is blocked by {} which is not done yet.",
+            item_id,
+            blockers.join(", ")
+        );
+        match force {
+            true => Ok(Some(message)),
+            false => Err(AppError::BlockedByDependency(format!(
+                "{} Pass --force to move it anyway.",
+                message
+            ))),
+        }
+    }
+
+    /// Remove the swimlane with given slug from the Kanban Board.
+    /// The swimlane must be empty, so tasks and subtasks need to be moved out of it first.
+    pub fn remove_swimlane(&mut self, storage: &dyn Storage, slug: &str) -> Result<(), AppError> {
+        let swimlane: &Swimlane = self.get_swimlane(slug)?;
+        if !swimlane.items.is_empty() {
+            return Err(AppError::InvalidArgument(format!(
+                "Swimlane {} still has {} tasks and subtasks.",
+                slug,
+                swimlane.items.len()
+            )));
+        }
+        if self.swimlanes.len() == 1 {
+            return Err(AppError::InvalidArgument(format!(
+                "Swimlane {} is the only swimlane on the board and cannot be removed.",
+                slug
+            )));
+        }
+        self.swimlanes.retain(|x| x.slug != slug);
+        self.save(storage)
+    }
+
+    /// Generate the next Task ID of the board (stored along with the board)
+    pub fn next_task_id(&mut self, storage: &dyn Storage) -> Result<String, AppError> {
+        self.id_sequence.next_task_id(storage)
+    }
+
+    /// Generate the next SubTask ID under given parent Task or SubTask ID (stored along with the
+    /// board)
+    pub fn next_subtask_id(
+        &mut self,
+        storage: &dyn Storage,
+        parent_id: &str,
+    ) -> Result<String, AppError> {
+        self.id_sequence.next_subtask_id(storage, parent_id)
+    }
+
+    /// Set the prefix of Task IDs generated from now on
+    pub fn set_id_prefix(&mut self, storage: &dyn Storage, prefix: &str) -> Result<(), AppError> {
+        self.id_sequence.prefix = prefix.to_string();
+        self.save(storage)
+    }
+
+    /// Load the Kanban Board from storage (None if it was never stored)
+    pub fn load(storage: &dyn Storage) -> Result<Option<Self>, AppError> {
+        storage.load_board()
     }
 
-    /// Check if the Kanban Board file is present in disk
-    pub fn check_if_file_exists() -> Result<bool, AppError> {
-        let app_dir: String = create_app_dirs()?;
-        let boards_file: String = format!("{}\\{}", app_dir, KANBAN_BOARD_FILE);
-        Ok(Path::new(&boards_file).exists())
+    /// Store the Kanban Board information to storage
+    pub fn save(&self, storage: &dyn Storage) -> Result<(), AppError> {
+        storage.save_board(self)
     }
 }
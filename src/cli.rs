@@ -0,0 +1,325 @@
+//! Defines the command line argument parsing for running the application in one-shot mode
+
+use chrono::prelude::{Local, NaiveDate};
+use rustic_boards::{
+    error::AppError, output::OutputFormat, swimlanes::LaneStage, tags::TagColor,
+    utils::TaskPriority,
+};
+
+/// Flags which can be passed along with a command in place of the interactive prompts
+#[derive(Debug, Default)]
+pub struct CommandOptions {
+    /// Value for `--name`
+    pub name: Option<String>,
+
+    /// Value for `--description`
+    pub description: Option<String>,
+
+    /// Value for `--priority` (high, medium, low)
+    pub priority: Option<TaskPriority>,
+
+    /// Value for `--deadline` (dd/mm/yyyy). `Some(None)` means `--deadline none` was passed.
+    pub deadline: Option<Option<NaiveDate>>,
+
+    /// Values for `--note` (can be passed multiple times)
+    pub notes: Vec<String>,
+
+    /// Value for `--parent` (Task ID to link a subtask to)
+    pub parent: Option<String>,
+
+    /// Value for `--stage` (pending, started, done)
+    pub stage: Option<LaneStage>,
+
+    /// Value for `--into` (swimlane to move tasks into when removing a swimlane)
+    pub into: Option<String>,
+
+    /// Value for `--prefix` (prefix of Task IDs on a new board)
+    pub prefix: Option<String>,
+
+    /// Value for `--data-dir` (directory holding all application data)
+    pub data_dir: Option<String>,
+
+    /// Value for `--email` (email address of a person registered on the board)
+    pub email: Option<String>,
+
+    /// Value for `--color` (color to show a tag in)
+    pub color: Option<TagColor>,
+
+    /// Value for `--goal` (goal of a new sprint)
+    pub goal: Option<String>,
+
+    /// Value for `--start` (dd/mm/yyyy, first day of a new sprint)
+    pub start: Option<NaiveDate>,
+
+    /// Value for `--end` (dd/mm/yyyy, last day of a new sprint)
+    pub end: Option<NaiveDate>,
+
+    /// Value for `--sprint` (number of the sprint to plan tasks into or show)
+    pub sprint: Option<u32>,
+
+    /// Value for `--format` (table, json, ndjson, csv)
+    pub format: OutputFormat,
+
+    /// Whether `--global` was passed (use the global board even inside a project with its own board)
+    pub global: bool,
+
+    /// Whether `--check` was passed (report what a command would change without changing it)
+    pub check: bool,
+
+    /// Whether `--force` was passed (move a task or subtask beyond the WIP limit of a swimlane, or
+    /// undo or redo over changes made outside the journal)
+    pub force: bool,
+
+    /// Whether `--repair` was passed (fix the problems found by fsck)
+    pub repair: bool,
+
+    /// Whether `--soft` was passed (only warn when the WIP limit of a swimlane is exceeded)
+    pub soft: bool,
+
+    /// Whether `--blocked` was passed (a new swimlane holds tasks and subtasks waiting on their
+    /// blockers)
+    pub blocked: bool,
+
+    /// Whether `--week` was passed (show the timesheet of the current week per task and subtask)
+    pub week: bool,
+
+    /// Whether `--backlog` was passed (put the unfinished tasks of a closed sprint back in the
+    /// backlog instead of carrying them over to the next sprint)
+    pub backlog: bool,
+
+    /// Whether missing values can be asked for with interactive prompts
+    pub interactive: bool,
+}
+
+impl CommandOptions {
+    /// Create blank options for use in the interactive REPL
+    pub fn interactive() -> Self {
+        CommandOptions {
+            interactive: true,
+            ..Default::default()
+        }
+    }
+
+    /// Whether flags choosing the application directory were passed (`--data-dir`, `--global`),
+    /// which only take effect when starting the application
+    pub fn selects_data_dir(&self) -> bool {
+        self.data_dir.is_some() || self.global
+    }
+}
+
+/// Split a line entered at the `boards>` prompt into arguments the way a POSIX shell does, so that
+/// commands are written the same way as in one-shot mode: whitespace separates arguments, single
+/// quotes keep everything between them as is, double quotes keep everything but `\"` and `\\`
+/// escapes, and a backslash outside quotes escapes the next character.
+pub fn split_words(line: &str) -> Result<Vec<String>, AppError> {
+    let mut words: Vec<String> = Vec::new();
+    let mut word: Option<String> = None;
+    let mut chars = line.chars();
+
+    while let Some(x) = chars.next() {
+        match x {
+            ' ' | '\t' | '\n' | '\r' => {
+                words.extend(word.take());
+                continue;
+            }
+            _ => {}
+        }
+        let current: &mut String = word.get_or_insert_with(String::new);
+        match x {
+            '\'' => loop {
+                match chars.next() {
+                    Some('\'') => break,
+                    Some(y) => current.push(y),
+                    None => return Err(unterminated_quote(line)),
+                }
+            },
+            '"' => loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some('\\') => match chars.next() {
+                        Some(y @ ('"' | '\\')) => current.push(y),
+                        Some(y) => {
+                            current.push('\\');
+                            current.push(y);
+                        }
+                        None => return Err(unterminated_quote(line)),
+                    },
+                    Some(y) => current.push(y),
+                    None => return Err(unterminated_quote(line)),
+                }
+            },
+            '\\' => current.extend(chars.next()),
+            _ => current.push(x),
+        }
+    }
+    words.extend(word);
+    Ok(words)
+}
+
+/// Error for a line entered at the `boards>` prompt with a quote which is never closed
+fn unterminated_quote(line: &str) -> AppError {
+    AppError::InvalidCommand(format!("{} - Please close all quotes.", line.trim()))
+}
+
+/// Split given command line arguments into the command words and the flags passed along with it.
+///
+/// Flags can be passed either as `--flag value` or `--flag=value`, while switches (e.g. `--check`)
+/// are passed on their own.
+pub fn parse_args(args: &[String]) -> Result<(Vec<String>, CommandOptions), AppError> {
+    let mut command: Vec<String> = Vec::new();
+    let mut options: CommandOptions = CommandOptions::default();
+    let mut args_iter = args.iter();
+
+    while let Some(arg) = args_iter.next() {
+        if !arg.starts_with("--") {
+            command.push(arg.clone());
+            continue;
+        }
+
+        let (flag, inline_value): (&str, Option<String>) = match arg.split_once('=') {
+            Some((flag, value)) => (flag, Some(value.to_string())),
+            None => (arg.as_str(), None),
+        };
+        let switch: Option<&mut bool> = match flag {
+            "--check" => Some(&mut options.check),
+            "--force" => Some(&mut options.force),
+            "--soft" => Some(&mut options.soft),
+            "--week" => Some(&mut options.week),
+            "--blocked" => Some(&mut options.blocked),
+            "--backlog" => Some(&mut options.backlog),
+            "--repair" => Some(&mut options.repair),
+            "--global" => Some(&mut options.global),
+            _ => None,
+        };
+        if let Some(switch) = switch {
+            if inline_value.is_some() {
+                return Err(AppError::InvalidArgument(format!(
+                    "{} does not take a value",
+                    flag
+                )));
+            }
+            *switch = true;
+            continue;
+        }
+
+        let value: String = match inline_value {
+            Some(s) => s,
+            None => match args_iter.next() {
+                Some(s) => s.clone(),
+                None => {
+                    return Err(AppError::MissingArgument(format!(
+                        "No value passed for {}",
+                        flag
+                    )))
+                }
+            },
+        };
+
+        match flag {
+            "--name" => options.name = Some(value),
+            "--description" => options.description = Some(value),
+            "--priority" => options.priority = Some(value.parse::<TaskPriority>()?),
+            "--deadline" => options.deadline = Some(parse_deadline(&value)?),
+            "--note" => options.notes.push(value),
+            "--parent" => options.parent = Some(value),
+            "--stage" => options.stage = Some(value.parse::<LaneStage>()?),
+            "--into" => options.into = Some(value),
+            "--prefix" => options.prefix = Some(value),
+            "--data-dir" => options.data_dir = Some(value),
+            "--email" => options.email = Some(value),
+            "--color" => options.color = Some(value.parse::<TagColor>()?),
+            "--goal" => options.goal = Some(value),
+            "--start" => options.start = Some(parse_date(&value)?),
+            "--end" => options.end = Some(parse_date(&value)?),
+            "--sprint" => options.sprint = Some(parse_sprint_number(&value)?),
+            "--format" => options.format = value.parse::<OutputFormat>()?,
+            _ => return Err(AppError::InvalidArgument(format!("Unknown flag {}", flag))),
+        }
+    }
+
+    Ok((command, options))
+}
+
+/// Parse the deadline passed with `--deadline` in dd/mm/yyyy format (or `none` to remove the deadline)
+pub fn parse_deadline(value: &str) -> Result<Option<NaiveDate>, AppError> {
+    if value == "none" {
+        return Ok(None);
+    }
+    let deadline: NaiveDate = match NaiveDate::parse_from_str(value, "%d/%m/%Y") {
+        Ok(s) => s,
+        Err(_) => {
+            return Err(AppError::InvalidArgument(format!(
+                "{} - Please enter a valid date in dd/mm/yyyy format (or none).",
+                value
+            )))
+        }
+    };
+    if deadline < Local::now().date_naive() {
+        return Err(AppError::InvalidArgument(format!(
+            "{} - Entered date cannot be prior to current date.",
+            value
+        )));
+    }
+    Ok(Some(deadline))
+}
+
+/// Parse a date passed with `--start` or `--end` in dd/mm/yyyy format
+pub fn parse_date(value: &str) -> Result<NaiveDate, AppError> {
+    match NaiveDate::parse_from_str(value, "%d/%m/%Y") {
+        Ok(s) => Ok(s),
+        Err(_) => Err(AppError::InvalidArgument(format!(
+            "{} - Please enter a valid date in dd/mm/yyyy format.",
+            value
+        ))),
+    }
+}
+
+/// Parse the number of a sprint passed with `--sprint` or along with a command
+pub fn parse_sprint_number(value: &str) -> Result<u32, AppError> {
+    match value.parse::<u32>() {
+        Ok(s) if s > 0 => Ok(s),
+        _ => Err(AppError::InvalidArgument(format!(
+            "{} - Please enter the number of a sprint (from sprint list).",
+            value
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn words_are_split_like_a_shell() {
+        let words: Vec<String> =
+            split_words("add task --name \"Fix login\" --note 'it''s \"done\"'  x\\ y").unwrap();
+        assert_eq!(
+            words,
+            [
+                "add",
+                "task",
+                "--name",
+                "Fix login",
+                "--note",
+                "its \"done\"",
+                "x y"
+            ]
+        );
+    }
+
+    #[test]
+    fn quotes_keep_empty_words_and_escapes() {
+        assert_eq!(split_words("a '' \"\"").unwrap(), ["a", "", ""]);
+        assert_eq!(
+            split_words(r#""a \"b\" \\ \n""#).unwrap(),
+            [r#"a "b" \ \n"#]
+        );
+        assert_eq!(split_words("   ").unwrap(), Vec::<String>::new());
+    }
+
+    #[test]
+    fn unterminated_quotes_are_rejected() {
+        assert!(split_words("add task --name \"Fix login").is_err());
+        assert!(split_words("add task --name 'Fix").is_err());
+    }
+}
@@ -0,0 +1,1278 @@
+//! Defines the command handlers shared by the interactive REPL and the one-shot CLI mode, which
+//! take the input for each command and show the outcome of running it through the library
+
+use crate::{
+    cli::{parse_sprint_number, CommandOptions},
+    prompt::{
+        confirm_prompt, date_input_prompt, select_prompt, stage_select_prompt,
+        swimlane_select_prompt, tasks_select_prompt, text_input_prompt,
+    },
+};
+use cli_table::{Cell, Style, Table};
+use rustic_boards::{
+    boards::{HiddenRecord, KanbanBoard, Shelf},
+    constants::DATA_DIR_ENV_VAR,
+    error::AppError,
+    estimates::{self, Estimate, EstimateUnit},
+    fsck::Problem,
+    items::WorkItem,
+    journal::{HistoryRecord, JournalEntry},
+    migrations,
+    notes::NoteRecord,
+    output::{self, OutputFormat, Record},
+    sprints::{Sprint, SprintReport},
+    storage::{migrate_storage, StorageBackend},
+    swimlanes::{LaneStage, WipCheck, WipLimit},
+    tags::TagColor,
+    utils::{create_project_dir, TaskPriority, TimeStamp},
+    worklog::{self, WorkLog},
+    workspace::{ItemChanges, MoveOutcome, NewItem},
+    Board, Workspace,
+};
+use std::path::PathBuf;
+
+/// Titles of the tables shown by `show task`
+const TASK_TITLES: [&str; 8] = [
+    "Task ID",
+    "Task Name",
+    "Priority",
+    "Deadline",
+    "Tags",
+    "Assignee",
+    "Estimate",
+    "Subtasks",
+];
+
+/// Titles of the tables shown by `show subtask`
+const SUBTASK_TITLES: [&str; 8] = [
+    "Subtask ID",
+    "Subtask Name",
+    "Priority",
+    "Deadline",
+    "Tags",
+    "Assignee",
+    "Estimate",
+    "Parent Task",
+];
+
+/// Titles of the tables shown by `filter due`, `filter priority`, `filter tag`, `filter assignee`
+/// and `mine`
+const FILTER_TITLES: [&str; 8] = [
+    "Task ID",
+    "Task Name",
+    "Priority",
+    "Deadline",
+    "Tags",
+    "Assignee",
+    "Estimate",
+    "Subtasks/Parent Task",
+];
+
+/// Titles of the details shown by `open task`
+pub const TASK_DETAILS_TITLES: [&str; 19] = [
+    "Task ID",
+    "Task Name",
+    "Task Description",
+    "Task Added On",
+    "Task Started On",
+    "Task Deadline",
+    "Task Completed On",
+    "Task Status",
+    "Task Priority",
+    "Task Tags",
+    "Task Assignee",
+    "Blocked By",
+    "Blocks",
+    "Parent Task",
+    "Progress",
+    "Subtasks",
+    "Estimate",
+    "Time Logged",
+    "Worklog",
+];
+
+/// Titles of the details shown by `open subtask`
+pub const SUBTASK_DETAILS_TITLES: [&str; 19] = [
+    "Subtask ID",
+    "Subtask Name",
+    "Subtask Description",
+    "Subtask Added On",
+    "Subtask Started On",
+    "Subtask Deadline",
+    "Subtask Completed On",
+    "Subtask Status",
+    "Subtask Priority",
+    "Subtask Tags",
+    "Subtask Assignee",
+    "Blocked By",
+    "Blocks",
+    "Parent Task",
+    "Progress",
+    "Subtasks",
+    "Estimate",
+    "Time Logged",
+    "Worklog",
+];
+
+/// Application state which is kept in memory while commands are being executed
+pub struct AppState {
+    /// Workspace holding all boards
+    pub workspace: Workspace,
+
+    /// Active board
+    pub board: Board,
+}
+
+impl AppState {
+    /// Load the application state for the active board from the configured storage (setting up a
+    /// blank Kanban Board on first use of the board)
+    pub fn load() -> Result<Self, AppError> {
+        let workspace: Workspace = Workspace::open()?;
+        let board: Board = workspace.open_active_board()?;
+        Ok(AppState { workspace, board })
+    }
+}
+
+/// Execute a single command (split into words) against the application state
+pub fn execute_command(
+    input_parts: &[&str],
+    options: &CommandOptions,
+    state: &mut AppState,
+) -> Result<(), AppError> {
+    let AppState { workspace, board } = state;
+
+    match input_parts {
+        ["add", "task"] => {
+            let task: NewItem = input_new_item(options, "task")?;
+            let subtasks: Vec<NewItem> = input_subtasks(options, "task")?;
+            let (task_id, subtasks_list): (String, Vec<String>) = board.add_task(task, subtasks)?;
+            println!("{} created successfully.", task_id);
+
+            if !subtasks_list.is_empty() {
+                println!("{:?} created successfully.", subtasks_list);
+            }
+        }
+        ["add", "subtask"] => {
+            let subtask: NewItem = input_new_item(options, "subtask")?;
+            let task_id: String = input_parent_task(options, board)?;
+            let subtask_id: String = board.add_subtask(&task_id, subtask)?;
+            println!(
+                "{} created successfully and linked to parent {}.",
+                subtask_id, task_id
+            );
+        }
+        ["edit", "task", task_id] => {
+            let task_item: WorkItem = board.task(task_id)?;
+            let task_description: String =
+                input_description(options, "Task Description:", Some(&task_item.description))?;
+            let task_priority: TaskPriority = match (&options.priority, options.interactive) {
+                (None, false) => task_item.priority,
+                _ => input_priority(options, "Task Priority:")?,
+            };
+            let task_deadline: Option<TimeStamp> =
+                input_deadline(options, "task", task_item.deadline)?;
+            let notes_list: Vec<String> =
+                input_notes(options, "Do you want to add notes for this task?")?;
+            let subtasks: Vec<NewItem> = input_subtasks(options, "task")?;
+
+            let changes: ItemChanges = ItemChanges {
+                description: task_description,
+                priority: task_priority,
+                deadline: task_deadline,
+                notes: notes_list,
+            };
+            let subtasks_list: Vec<String> = board.edit_task(task_id, changes, subtasks)?;
+            println!("{} updated successfully.", task_id);
+
+            if !subtasks_list.is_empty() {
+                println!("{:?} created successfully.", subtasks_list);
+            }
+        }
+        ["edit", "subtask", subtask_id] => {
+            let subtask_item: WorkItem = board.subtask(subtask_id)?;
+            let subtask_description: String = input_description(
+                options,
+                "Subtask Description:",
+                Some(&subtask_item.description),
+            )?;
+            let subtask_priority: TaskPriority = match (&options.priority, options.interactive) {
+                (None, false) => subtask_item.priority,
+                _ => input_priority(options, "Subtask Priority:")?,
+            };
+            let subtask_deadline: Option<TimeStamp> =
+                input_deadline(options, "subtask", subtask_item.deadline)?;
+
+            let link_check: bool = match (&options.parent, options.interactive) {
+                (Some(_), _) => true,
+                (None, true) => confirm_prompt(
+                    "Do you want to link this subtask to a different parent?",
+                    None,
+                )?,
+                (None, false) => false,
+            };
+            let new_task_id: Option<String> = match link_check {
+                true => Some(input_parent_task(options, board)?),
+                false => None,
+            };
+            let notes_list: Vec<String> =
+                input_notes(options, "Do you want to add notes for this subtask?")?;
+            let subtasks: Vec<NewItem> = input_subtasks(options, "subtask")?;
+
+            let changes: ItemChanges = ItemChanges {
+                description: subtask_description,
+                priority: subtask_priority,
+                deadline: subtask_deadline,
+                notes: notes_list,
+            };
+            let subtasks_list: Vec<String> =
+                board.edit_subtask(subtask_id, changes, subtasks, new_task_id.as_deref())?;
+            println!("{} updated successfully.", subtask_id);
+
+            if !subtasks_list.is_empty() {
+                println!("{:?} created successfully.", subtasks_list);
+            }
+        }
+        ["link", "subtask", subtask_id] => {
+            board.subtask(subtask_id)?;
+            let new_task_id: String = input_parent_task(options, board)?;
+            board.link_subtask(subtask_id, &new_task_id)?;
+            println!(
+                "{} successfully linked to parent {}.",
+                subtask_id, new_task_id
+            );
+        }
+        ["move", "task", task_id, swimlane] => {
+            let move_outcome: MoveOutcome = board.move_task(task_id, swimlane, options.force)?;
+            show_move_outcome(task_id, swimlane, &move_outcome);
+        }
+        ["move", "subtask", subtask_id, swimlane] => {
+            let move_outcome: MoveOutcome =
+                board.move_subtask(subtask_id, swimlane, options.force)?;
+            show_move_outcome(subtask_id, swimlane, &move_outcome);
+        }
+        ["open", "task", task_id] => {
+            let details = board.task_details(task_id)?;
+            show_details(
+                options.format,
+                &TASK_DETAILS_TITLES,
+                &details,
+                &details.notes,
+            )?
+        }
+        ["open", "subtask", subtask_id] => {
+            let details = board.subtask_details(subtask_id)?;
+            show_details(
+                options.format,
+                &SUBTASK_DETAILS_TITLES,
+                &details,
+                &details.notes,
+            )?
+        }
+        ["delete", "task", task_id] => {
+            board.delete_task(task_id)?;
+            println!("{} moved to the trash.", task_id);
+        }
+        ["delete", "subtask", subtask_id] => {
+            board.delete_subtask(subtask_id)?;
+            println!("{} moved to the trash.", subtask_id);
+        }
+        ["trash", "list"] => show_hidden(
+            options.format,
+            &board.list_hidden(Shelf::Trash)?,
+            Shelf::Trash,
+        )?,
+        ["restore", item_id] => {
+            for (restored_id, slug) in board.restore(item_id, Shelf::Trash)? {
+                println!("{} restored to {} swimlane.", restored_id, slug);
+            }
+        }
+        ["trash", "empty"] => {
+            let trashed_items: Vec<String> = board.empty_trash()?;
+            println!(
+                "Trash emptied ({} tasks and subtasks deleted).",
+                trashed_items.len()
+            );
+        }
+        ["archive", "task", task_id] => {
+            board.archive_task(task_id)?;
+            println!("{} archived successfully.", task_id);
+        }
+        ["archive", "done"] => {
+            let tasks_list: Vec<String> = board.archive_done()?;
+            match tasks_list.is_empty() {
+                true => println!("No completed tasks to archive."),
+                false => println!("{:?} archived successfully.", tasks_list),
+            }
+        }
+        ["archive", "list"] => show_hidden(
+            options.format,
+            &board.list_hidden(Shelf::Archive)?,
+            Shelf::Archive,
+        )?,
+        ["archive", "restore", task_id] => {
+            for (restored_id, slug) in board.restore(task_id, Shelf::Archive)? {
+                println!("{} restored to {} swimlane.", restored_id, slug);
+            }
+        }
+        ["show", "task", swimlane] => {
+            output::print_sections(options.format, &TASK_TITLES, &board.list_tasks(swimlane)?)?
+        }
+        ["show", "subtask", swimlane] => output::print_sections(
+            options.format,
+            &SUBTASK_TITLES,
+            &board.list_subtasks(swimlane)?,
+        )?,
+        ["filter", "due", keyword] => output::print_sections(
+            options.format,
+            &FILTER_TITLES,
+            &board.find_by_deadline(keyword)?,
+        )?,
+        ["filter", "priority", keyword] => output::print_sections(
+            options.format,
+            &FILTER_TITLES,
+            &board.find_by_priority(keyword)?,
+        )?,
+        ["filter", "tag", tag] => {
+            output::print_sections(options.format, &FILTER_TITLES, &board.find_by_tag(tag)?)?
+        }
+        ["filter", "assignee", handle] => output::print_sections(
+            options.format,
+            &FILTER_TITLES,
+            &board.find_by_assignee(match *handle {
+                "none" => None,
+                _ => Some(handle),
+            })?,
+        )?,
+        ["filter", "estimate", keyword] => output::print_sections(
+            options.format,
+            &FILTER_TITLES,
+            &board.find_by_estimate(match *keyword {
+                "none" => None,
+                _ => Some(keyword.parse::<EstimateUnit>()?),
+            })?,
+        )?,
+        ["mine"] => output::print_sections(options.format, &FILTER_TITLES, &board.mine()?)?,
+        ["user", "add", handle] => {
+            let name: String = input_name(options, "Name:")?;
+            let email: Option<String> = input_email(options)?;
+            let handle: String = board.add_person(handle, &name, email.as_deref())?;
+            println!("{} added to board {}.", handle, board.name());
+        }
+        ["user", "list"] => output::print_records(
+            options.format,
+            &["Handle", "Name", "Email", "Tasks", "Subtasks"],
+            &board.list_people()?,
+        )?,
+        ["user", "remove", handle] => {
+            board.remove_person(handle)?;
+            println!("{} removed from board {}.", handle, board.name());
+        }
+        ["assign", item_id, handle] => {
+            let handle: Option<&str> = match *handle {
+                "none" => None,
+                _ => Some(handle),
+            };
+            match board.assign(item_id, handle)? {
+                Some(s) => println!("{} assigned to {}.", item_id, s),
+                None => println!("{} unassigned.", item_id),
+            }
+        }
+        ["estimate", item_id, effort] => {
+            let effort: Option<&str> = match *effort {
+                "none" => None,
+                _ => Some(effort),
+            };
+            match board.set_estimate(item_id, effort)? {
+                Some(s) => println!("{} estimated at {}.", item_id, s),
+                None => println!("Estimate removed from {}.", item_id),
+            }
+        }
+        ["remaining", item_id, effort] => {
+            let estimate: Estimate = board.set_remaining(item_id, effort)?;
+            println!(
+                "{} left on {}.",
+                estimates::format_effort(estimate.unit, estimate.remaining),
+                item_id
+            );
+        }
+        ["depends", item_id, "on", blocker_id] => {
+            board.add_dependency(item_id, blocker_id)?;
+            println!("{} now depends on {}.", item_id, blocker_id);
+        }
+        ["undepend", item_id, "on", blocker_id] => {
+            board.remove_dependency(item_id, blocker_id)?;
+            println!("{} no longer depends on {}.", item_id, blocker_id);
+        }
+        ["timer", "start", item_id] => match board.start_timer(item_id)? {
+            Some(s) => println!("Timer started on {} for {}.", item_id, s),
+            None => println!("Timer started on {}.", item_id),
+        },
+        ["timer", "stop", message @ ..] => {
+            let (item_id, work_log): (String, WorkLog) = board.stop_timer(&message.join(" "))?;
+            println!(
+                "Timer stopped. {} logged on {}.",
+                worklog::format_duration(work_log.minutes),
+                item_id
+            );
+        }
+        ["timer", "list"] => output::print_records(
+            options.format,
+            &["User", "Item ID", "Started At", "Elapsed"],
+            &board.timers()?,
+        )?,
+        ["log", item_id, duration, message @ ..] => {
+            let minutes: u32 = worklog::parse_duration(duration)?;
+            board.log_work(item_id, minutes, &message.join(" "))?;
+            println!(
+                "{} logged on {}.",
+                worklog::format_duration(minutes),
+                item_id
+            );
+        }
+        ["timesheet"] if options.week => {
+            let (week, timesheet_records) = board.weekly_timesheet()?;
+            let mut titles: Vec<String> = vec!["ID".to_string(), "Name".to_string()];
+            titles.extend(week.iter().map(|x| x.format("%a %e").to_string()));
+            titles.push("Total".to_string());
+            output::print_records(
+                options.format,
+                &titles.iter().map(String::as_str).collect::<Vec<&str>>(),
+                &timesheet_records,
+            )?
+        }
+        ["timesheet"] => output::print_records(
+            options.format,
+            &["Date", "Time Logged", "Entries"],
+            &board.timesheet()?,
+        )?,
+        ["sprint", "create"] => {
+            let goal: String = input_goal(options)?;
+            let sprint: Sprint = board.create_sprint(&goal, options.start, options.end)?;
+            println!(
+                "Sprint {} created, running from {} to {}.",
+                sprint.number,
+                output::display_date(&Some(sprint.start_on.to_naivedate()?)),
+                output::display_date(&Some(sprint.end_on.to_naivedate()?))
+            );
+        }
+        ["sprint", "list"] => output::print_records(
+            options.format,
+            &[
+                "Sprint",
+                "Goal",
+                "Start",
+                "End",
+                "Status",
+                "Tasks",
+                "Completed",
+                "Estimate",
+            ],
+            &board.sprints()?,
+        )?,
+        ["sprint", "plan", task_ids @ ..] if !task_ids.is_empty() => {
+            let number: u32 = board.plan_sprint(task_ids, options.sprint)?;
+            println!("{} planned into sprint {}.", task_ids.join(", "), number);
+        }
+        ["sprint", "unplan", task_id] => {
+            let number: u32 = board.unplan_sprint(task_id)?;
+            println!(
+                "{} taken out of sprint {} and put back in the backlog.",
+                task_id, number
+            );
+        }
+        ["sprint", "show", number @ ..] if number.len() <= 1 => {
+            let number: Option<u32> = match number.first() {
+                Some(s) => Some(parse_sprint_number(s)?),
+                None => None,
+            };
+            let (sprint, sections) = board.sprint_tasks(number)?;
+            if options.format == OutputFormat::Table {
+                println!("{}", sprint.describe()?);
+            }
+            output::print_sections(options.format, &TASK_TITLES, &sections)?
+        }
+        ["sprint", "close"] => {
+            let sprint_report: SprintReport = board.close_sprint(options.backlog)?;
+            if options.format == OutputFormat::Table {
+                println!("{}", sprint_report.summary());
+            }
+            output::print_sections(options.format, &TASK_TITLES, &sprint_report.sections())?
+        }
+        ["tag", "add", item_id, tag] => {
+            let tag: String = board.add_tag(item_id, tag, options.color)?;
+            println!("Tag {} added to {}.", tag, item_id);
+        }
+        ["tag", "remove", item_id, tag] => {
+            board.remove_tag(item_id, tag)?;
+            println!("Tag {} removed from {}.", tag.to_lowercase(), item_id);
+        }
+        ["tag", "list"] => output::print_records(
+            options.format,
+            &["Tag", "Color", "Tasks", "Subtasks"],
+            &board.list_tags()?,
+        )?,
+        ["tag", "color", tag, color] => {
+            let color: Option<TagColor> = match *color {
+                "none" => None,
+                _ => Some(color.parse::<TagColor>()?),
+            };
+            let tag: String = board.set_tag_color(tag, color)?;
+            match color {
+                Some(s) => println!("Tag {} will be shown in {}.", tag, s),
+                None => println!("Color of tag {} removed.", tag),
+            }
+        }
+        ["tag", "rename", tag, new_tag] => {
+            let items_changed: usize = board.rename_tag(tag, new_tag)?;
+            println!(
+                "Tag {} renamed to {} ({} tasks and subtasks changed).",
+                tag.to_lowercase(),
+                new_tag.to_lowercase(),
+                items_changed
+            );
+        }
+        ["tag", "merge", tag, into] => {
+            let items_changed: usize = board.merge_tag(tag, into)?;
+            println!(
+                "Tag {} merged into {} ({} tasks and subtasks changed).",
+                tag.to_lowercase(),
+                into.to_lowercase(),
+                items_changed
+            );
+        }
+        ["add", "notes", task_id] => {
+            // Fetching the notes checks that the item exists before prompting for new ones
+            board.notes(task_id)?;
+
+            let mut notes_list: Vec<String> = options.notes.clone();
+            if options.interactive && notes_list.is_empty() {
+                notes_list.push(text_input_prompt("Add a note:", None)?);
+
+                while confirm_prompt("Do you want to add another note?", None)? {
+                    notes_list.push(text_input_prompt("Add a note:", None)?);
+                }
+            }
+
+            board.add_notes(task_id, notes_list)?;
+            println!("Notes added successfully to {}.", task_id);
+        }
+        ["show", "notes", task_id] => {
+            let note_records: Vec<NoteRecord> = board.list_notes(task_id)?;
+            if note_records.is_empty() && options.format == OutputFormat::Table {
+                println!("No notes found.");
+                return Ok(());
+            }
+            output::print_records(options.format, &["#", "Note"], &note_records)?
+        }
+        ["storage", "show"] => {
+            println!("Storage backend: {}", workspace.storage_backend());
+        }
+        ["storage", "use", backend] => {
+            let new_backend: StorageBackend = backend.parse::<StorageBackend>()?;
+            match workspace.use_storage(new_backend)? {
+                Some(items_copied) => {
+                    *board = workspace.open_active_board()?;
+                    println!(
+                        "Storage backend switched to {} ({} tasks and subtasks copied).",
+                        new_backend, items_copied
+                    );
+                }
+                None => println!("Storage backend is already {}.", new_backend),
+            }
+        }
+        ["lane", "list"] => output::print_records(
+            options.format,
+            &[
+                "Position",
+                "Slug",
+                "Name",
+                "Stage",
+                "Items",
+                "WIP Limit",
+                "Time Logged",
+            ],
+            &board.list_swimlanes()?,
+        )?,
+        ["lane", "add", name @ ..] if !name.is_empty() => {
+            let lane_stage: LaneStage = input_stage(options, "Swimlane Stage:")?;
+            let slug: String = board.add_swimlane(&name.join(" "), lane_stage, options.blocked)?;
+            println!("Swimlane {} added successfully.", slug);
+        }
+        ["lane", "rename", slug, name @ ..] if !name.is_empty() => {
+            let new_slug: String = board.rename_swimlane(slug, &name.join(" "))?;
+            println!("Swimlane {} renamed to {}.", slug, new_slug);
+        }
+        ["lane", "reorder", slug, position] => {
+            let position: usize = match position.parse::<usize>() {
+                Ok(s) => s,
+                Err(_) => {
+                    return Err(AppError::InvalidArgument(format!(
+                        "{} - Please enter the position as a number.",
+                        position
+                    )))
+                }
+            };
+            board.reorder_swimlane(slug, position)?;
+            println!("Swimlane {} moved to position {}.", slug, position);
+        }
+        ["lane", "mark", slug, lane_stage] => {
+            let lane_stage: LaneStage = lane_stage.parse::<LaneStage>()?;
+            board.mark_swimlane(slug, lane_stage)?;
+            println!("Swimlane {} marked as {}.", slug, lane_stage);
+        }
+        ["lane", "block", slug] => {
+            board.block_swimlane(slug, true)?;
+            println!("Swimlane {} now holds blocked tasks and subtasks.", slug);
+        }
+        ["lane", "unblock", slug] => {
+            board.block_swimlane(slug, false)?;
+            println!(
+                "Swimlane {} no longer holds blocked tasks and subtasks.",
+                slug
+            );
+        }
+        ["lane", "limit", slug, max_items] => {
+            let wip_limit: Option<WipLimit> = match *max_items {
+                "none" => None,
+                _ => match max_items.parse::<usize>() {
+                    Ok(s) if s > 0 => Some(WipLimit {
+                        max_items: s,
+                        strict: !options.soft,
+                    }),
+                    _ => {
+                        return Err(AppError::InvalidArgument(format!(
+                            "{} - Please enter the WIP limit as a positive number or 'none'.",
+                            max_items
+                        )))
+                    }
+                },
+            };
+            board.set_wip_limit(slug, wip_limit)?;
+            match wip_limit {
+                Some(s) => println!("WIP limit of swimlane {} set to {}.", slug, s),
+                None => println!("WIP limit of swimlane {} removed.", slug),
+            }
+        }
+        ["lane", "remove", slug] => {
+            let into: Option<String> = input_into_swimlane(options, board.kanban(), slug)?;
+            for wip_check in board.remove_swimlane(slug, into.as_deref(), options.force)? {
+                show_wip_check(&wip_check);
+            }
+
+            if let Some(into) = into {
+                println!("Tasks and subtasks in {} moved to {}.", slug, into);
+            }
+            println!("Swimlane {} removed successfully.", slug);
+        }
+        ["board", "create", board_name] => {
+            workspace.create_board(board_name, options.prefix.as_deref())?;
+            println!(
+                "Board {} created successfully. Switch to it with 'board use {}'.",
+                board_name, board_name
+            );
+        }
+        ["board", "prefix", prefix] => {
+            let prefix: String = board.set_id_prefix(prefix)?;
+            println!(
+                "New tasks on board {} will get {}-<N> IDs.",
+                board.name(),
+                prefix
+            );
+        }
+        ["board", "rekey"] => {
+            let changes: Vec<(String, String)> = board.rekey(options.check)?;
+            if changes.is_empty() {
+                println!("All tasks and subtasks already have sequential IDs.");
+                return Ok(());
+            }
+
+            match options.check {
+                true => println!("Following IDs would be changed:"),
+                false => println!("Following IDs were changed:"),
+            }
+            for (current_id, new_id) in changes {
+                println!("- {} -> {}", current_id, new_id);
+            }
+        }
+        ["board", "list"] => output::print_records(
+            options.format,
+            &["Board", "Active"],
+            &workspace.list_boards(),
+        )?,
+        ["board", "use", board_name] => {
+            workspace.use_board(board_name)?;
+            *board = workspace.open_active_board()?;
+            println!("Switched to board {}.", board_name);
+        }
+        ["board", "delete", board_name] => {
+            workspace.delete_board(board_name)?;
+            println!("Board {} deleted successfully.", board_name);
+        }
+        ["board", "rename", board_name, new_name] => {
+            workspace.rename_board(board_name, new_name)?;
+            *board = workspace.open_active_board()?;
+            println!("Board {} renamed to {}.", board_name, new_name);
+        }
+        ["undo"] => {
+            let entry: Option<JournalEntry> = board.undo(options.force)?;
+            match entry {
+                Some(s) => println!("Undone: {} ({})", s.command, s.recorded_on),
+                None => println!("Nothing to undo."),
+            }
+        }
+        ["redo"] => {
+            let entry: Option<JournalEntry> = board.redo(options.force)?;
+            match entry {
+                Some(s) => println!("Redone: {} ({})", s.command, s.recorded_on),
+                None => println!("Nothing to redo."),
+            }
+        }
+        ["history"] => {
+            let history_records: Vec<HistoryRecord> = board.history();
+            if history_records.is_empty() && options.format == OutputFormat::Table {
+                println!("No commands recorded yet.");
+                return Ok(());
+            }
+            output::print_records(
+                options.format,
+                &["#", "Recorded On", "Command", "Tasks and Subtasks", "State"],
+                &history_records,
+            )?
+        }
+        ["fsck"] => {
+            let problems: Vec<Problem> = board.check()?;
+            if problems.is_empty() && options.format == OutputFormat::Table {
+                println!("No problems found on board {}.", board.name());
+                return Ok(());
+            }
+
+            output::print_records(
+                options.format,
+                &["Problem", "ID", "Details", "Repair"],
+                &problems,
+            )?;
+            if !options.repair || problems.is_empty() {
+                if options.format == OutputFormat::Table {
+                    println!(
+                        "{} problems found. Run fsck --repair to apply the repairs above.",
+                        problems.len()
+                    );
+                }
+                return Ok(());
+            }
+            if options.interactive
+                && !confirm_prompt("Do you want to apply the repairs above?", None)?
+            {
+                return Ok(());
+            }
+
+            board.repair(&problems)?;
+            println!("{} problems repaired.", problems.len());
+        }
+        ["init"] => init_project()?,
+        ["migrate"] => migrate_data(options)?,
+        ["help"] => show_help()?,
+        _ => return Err(AppError::InvalidCommand(input_parts.join(" "))),
+    }
+
+    Ok(())
+}
+
+/// Create a project board in the current directory, which is used instead of the global board
+/// when running the application anywhere inside the directory
+pub fn init_project() -> Result<(), AppError> {
+    let project_dir: PathBuf = create_project_dir()?;
+    println!("Project board created in {}.", project_dir.display());
+    if std::env::var_os(DATA_DIR_ENV_VAR).is_some_and(|x| !x.is_empty()) {
+        println!(
+            "Note: {} is set, so it takes precedence over the project board.",
+            DATA_DIR_ENV_VAR
+        );
+    }
+    Ok(())
+}
+
+/// Upgrade all stored data to the current schema version, or only report what would change when
+/// `--check` is passed
+pub fn migrate_data(options: &CommandOptions) -> Result<(), AppError> {
+    let changes: Vec<String> = migrate_storage(options.check)?;
+    if changes.is_empty() {
+        println!(
+            "All data is up to date (schema version {}).",
+            migrations::SCHEMA_VERSION
+        );
+        return Ok(());
+    }
+
+    match options.check {
+        true => println!("Following files would be migrated:"),
+        false => println!("Following files were migrated:"),
+    }
+    for change in &changes {
+        println!("- {}", change);
+    }
+    Ok(())
+}
+
+/// Display all commands and flags for the application
+fn show_help() -> Result<(), AppError> {
+    let display_vec: Vec<Vec<&str>> = vec![
+        vec!["add task", "To add a new task into board (along with subtasks - optional)"],
+        vec!["add subtask", "To add a new subtask into board under a parent task or subtask"],
+        vec!["edit task <Task ID>", "To modify details for a task or to create new subtasks under a task \n(only task description, priority, deadline can be modified)"],
+        vec!["edit subtask <SubTask ID>", "To modify details for a subtask or to create new subtasks under it \n(only subtask description, priority, deadline and parent can be modified)"],
+        vec!["open task <Task ID>", "To view all details for a task (including its worklog)"],
+        vec!["open subtask <SubTask ID>", "To view all details for a subtask (including its worklog)"],
+        vec!["delete task <Task ID>", "To move a task to the trash (along with its subtasks)"],
+        vec!["delete subtask <SubTask ID>", "To move a subtask to the trash along with its own subtasks (This won't have any impact on the parent)"],
+        vec!["trash list", "To view the tasks and subtasks in the trash"],
+        vec!["restore <ID>", "To restore a task (along with the subtasks deleted with it) or a subtask from the trash"],
+        vec!["trash empty", "To delete the tasks and subtasks in the trash for good"],
+        vec!["archive task <Task ID>", "To archive a task in a done swimlane (along with its subtasks)"],
+        vec!["archive done", "To archive all tasks in done swimlanes"],
+        vec!["archive list", "To view the archived tasks and subtasks"],
+        vec!["archive restore <Task ID>", "To put an archived task back on the board (along with its subtasks)"],
+        vec!["move task <Task ID> <Swimlane>", "To move a task across different swimlanes on board \n(--force to move beyond a strict WIP limit or unfinished blockers)"],
+        vec!["move subtask <SubTask ID> <Swimlane>", "To move a subtask across different swimlanes on board \n(--force to move beyond a strict WIP limit or unfinished blockers)"],
+        vec!["link subtask <SubTask ID>", "To move a subtask under a different parent task or subtask"],
+        vec!["show task <Swimlane>", "To view all tasks in given swimlane along with their story points \n(slug of a swimlane from lane list, or all)"],
+        vec!["show subtask <Swimlane>", "To view all subtasks in given swimlane \n(slug of a swimlane from lane list, or all)"],
+        vec!["add notes <Task or SubTask ID>", "To add notes to an existing task or subtask"],
+        vec!["show notes <Task or SubTask ID>", "To view notes for an existing task or subtask"],
+        vec!["filter due <Keyword>", "To filter all tasks and subtasks based on deadline \n(past-deadline, today, tomorrow, after-tomorrow, no-deadline)"],
+        vec!["filter priority <Keyword>", "To filter all tasks and subtasks based on priority \n(high, medium, low)"],
+        vec!["filter tag <Tag>", "To filter all tasks and subtasks with given tag"],
+        vec!["filter assignee <Handle|none>", "To filter all tasks and subtasks assigned to given person \n(none for unassigned ones)"],
+        vec!["filter estimate <Unit|none>", "To filter all tasks and subtasks estimated in given unit, counting their subtasks \n(points, time, none for unestimated ones)"],
+        vec!["mine", "To view all tasks and subtasks assigned to the current user \n(handle set in RUSTIC_BOARDS_USER)"],
+        vec!["user add <Handle>", "To register a person on the board who tasks can be assigned to \n(--name for their display name, --email for their email address)"],
+        vec!["user list", "To view all people on the board along with the number of tasks and subtasks assigned to them"],
+        vec!["user remove <Handle>", "To remove a person from the board (once nothing is assigned to them)"],
+        vec!["assign <Task or SubTask ID> <Handle|none>", "To assign a task or subtask to a person on the board \n(none to unassign it)"],
+        vec!["estimate <Task or SubTask ID> <Estimate|none>", "To estimate a task or subtask in story points or time, resetting its remaining effort \n(e.g. 5, 5pt, 1h30m, none to remove the estimate)"],
+        vec!["remaining <Task or SubTask ID> <Estimate>", "To set the effort remaining on an estimated task or subtask, in the unit of its estimate \n(time logged and moves into a done swimlane also update it)"],
+        vec!["depends <Task or SubTask ID> on <Task or SubTask ID>", "To record that a task or subtask is blocked by another one until it is done"],
+        vec!["undepend <Task or SubTask ID> on <Task or SubTask ID>", "To remove the dependency of a task or subtask on another one"],
+        vec!["timer start <Task or SubTask ID>", "To start tracking time on a task or subtask for the current user \n(one timer per person, handle set in RUSTIC_BOARDS_USER)"],
+        vec!["timer stop [Message]", "To stop the timer of the current user and log the time elapsed on its task or subtask"],
+        vec!["timer list", "To view the timers running on the board"],
+        vec!["log <Task or SubTask ID> <Duration> [Message]", "To log time spent today on a task or subtask \n(e.g. 1h30m, 2h or 45m)"],
+        vec!["timesheet", "To view the time logged on each day across the board \n(--week for the current week per task and subtask)"],
+        vec!["sprint create", "To add a new sprint with a goal, two weeks long by default \n(--goal for its goal, --start and --end for its first and last day)"],
+        vec!["sprint list", "To view all sprints along with their status, tasks completed and estimates"],
+        vec!["sprint plan <Task ID>...", "To pull tasks from the backlog into the current sprint \n(--sprint to plan them into another open sprint)"],
+        vec!["sprint unplan <Task ID>", "To take a task out of its sprint and put it back in the backlog"],
+        vec!["sprint show [Sprint]", "To view the tasks of the current sprint (or given sprint) across the swimlanes along with their story points"],
+        vec!["sprint close", "To close the current sprint, reporting completed and carried-over work \n(unfinished tasks move to the next open sprint, --backlog to put them back in the backlog)"],
+        vec!["tag add <Task or SubTask ID> <Tag>", "To put a tag on a task or subtask (letters, digits, '-' and '_') \n(--color to show the tag in a color)"],
+        vec!["tag remove <Task or SubTask ID> <Tag>", "To take a tag off a task or subtask"],
+        vec!["tag list", "To view all tags on the board along with their colors and number of tasks and subtasks"],
+        vec!["tag color <Tag> <Color|none>", "To set the color of a tag \n(red, green, yellow, blue, magenta, cyan, none to remove the color)"],
+        vec!["tag rename <Tag> <New Tag>", "To rename a tag on all tasks and subtasks of the board"],
+        vec!["tag merge <Tag> <Into Tag>", "To merge a tag into another tag on all tasks and subtasks of the board"],
+        vec!["storage show", "To view the storage backend holding the board data"],
+        vec!["storage use <Backend>", "To copy the board data to another storage backend and switch to it \n(bincode, sqlite, text)"],
+        vec!["lane list", "To view all swimlanes on the board along with their slugs, stages and time logged"],
+        vec!["lane add <Name>", "To add a new swimlane at the end of the board (slug is derived from the name) \n(stage: pending, started, done, --blocked to hold blocked tasks)"],
+        vec!["lane rename <Swimlane> <New Name>", "To rename a swimlane (tasks and subtasks in it are moved along)"],
+        vec!["lane reorder <Swimlane> <Position>", "To move a swimlane to another position on the board (starting at 1)"],
+        vec!["lane mark <Swimlane> <Stage>", "To change which stage of work a swimlane represents \n(pending, started, done)"],
+        vec!["lane block <Swimlane>", "To have a swimlane hold the tasks and subtasks waiting on their blockers"],
+        vec!["lane unblock <Swimlane>", "To stop a swimlane from holding the tasks and subtasks waiting on their blockers"],
+        vec!["lane limit <Swimlane> <N|none>", "To set the WIP limit of a swimlane, refusing moves beyond it \n(--soft to only warn, none to remove the limit)"],
+        vec!["lane remove <Swimlane>", "To remove a swimlane (tasks and subtasks in it are moved to the swimlane passed with --into)"],
+        vec!["board create <Board>", "To create a new named board \n(--prefix to set the prefix of its Task IDs, TASK by default)"],
+        vec!["board list", "To view all boards (the active board is marked with *)"],
+        vec!["board use <Board>", "To switch to another board"],
+        vec!["board delete <Board>", "To delete a board along with all its tasks, subtasks and notes"],
+        vec!["board rename <Board> <New Name>", "To rename a board"],
+        vec!["board prefix <Prefix>", "To set the prefix of new Task IDs on the active board \n(e.g. WEB for WEB-42)"],
+        vec!["board rekey", "To re-key all tasks and subtasks on the active board to sequential IDs \n(--check to only report what would change)"],
+        vec!["init", "To create a project board in the current directory \n(used instead of the global board anywhere inside it)"],
+        vec!["undo", "To undo the most recent change to the active board (also from an earlier session) \n(--force to overwrite changes made since outside the journal)"],
+        vec!["redo", "To redo the most recently undone change to the active board \n(--force to overwrite changes made since outside the journal)"],
+        vec!["history", "To view the recent changes to the active board which can be undone or redone"],
+        vec!["fsck", "To check the active board for orphans, duplicates, status mismatches, missing parents, parent cycles, stray notes, unknown assignees and broken dependencies \n(--repair to fix them after a preview)"],
+        vec!["migrate", "To upgrade all stored data to the current schema version \n(--check to only report what would change)"],
+        vec!["tui", "To open the board in a full-screen terminal UI \n(move cards with Shift+arrow keys, Enter for details, e to edit)"],
+        vec!["help", "To view all commands for the application"],
+        vec!["exit", "To exit the application"],
+    ];
+
+    match display_vec
+        .table()
+        .title(vec![
+            "Command".cell().bold(true),
+            "Description".cell().bold(true),
+        ])
+        .display()
+    {
+        Ok(s) => println!("{}", s),
+        Err(e) => return Err(AppError::TableDisplayParseError(e.to_string())),
+    };
+
+    let flags_vec: Vec<Vec<&str>> = vec![
+        vec![
+            "--name <Name>",
+            "Name for a new task or subtask, or display name for a new person",
+        ],
+        vec!["--email <Email>", "Email address for a new person"],
+        vec!["--description <Text>", "Description for a task or subtask"],
+        vec![
+            "--priority <Keyword>",
+            "Priority for a task or subtask \n(high, medium, low)",
+        ],
+        vec![
+            "--deadline <dd/mm/yyyy>",
+            "Deadline for a task or subtask \n(none to remove the deadline)",
+        ],
+        vec![
+            "--note <Text>",
+            "Note to add to a task or subtask (can be repeated)",
+        ],
+        vec!["--parent <Task or SubTask ID>", "Parent to nest a subtask under"],
+        vec![
+            "--stage <Stage>",
+            "Stage of work for a new swimlane \n(pending, started, done)",
+        ],
+        vec![
+            "--into <Swimlane>",
+            "Swimlane to move tasks into when removing a swimlane",
+        ],
+        vec![
+            "--prefix <Prefix>",
+            "Prefix of the Task IDs on a new board \n(letters and digits)",
+        ],
+        vec![
+            "--color <Color>",
+            "Color to show a tag in \n(red, green, yellow, blue, magenta, cyan)",
+        ],
+        vec!["--goal <Text>", "Goal for a new sprint"],
+        vec![
+            "--start <dd/mm/yyyy>",
+            "First day of a new sprint \n(the day after the last sprint ends, or today, by default)",
+        ],
+        vec![
+            "--end <dd/mm/yyyy>",
+            "Last day of a new sprint \n(two weeks after its start by default)",
+        ],
+        vec![
+            "--sprint <Sprint>",
+            "Number of the open sprint to plan tasks into (the current sprint by default)",
+        ],
+        vec![
+            "--format <Format>",
+            "Output format for show, open, filter and list commands \n(table, json, ndjson, csv)",
+        ],
+        vec![
+            "--check",
+            "Only report what migrate or board rekey would change without changing it",
+        ],
+        vec![
+            "--force",
+            "Move a task or subtask beyond a strict WIP limit or unfinished blockers \n(recorded as a note on it), or undo or redo over changes made outside the journal",
+        ],
+        vec![
+            "--repair",
+            "Apply the repairs previewed by fsck (after confirming in the boards> session)",
+        ],
+        vec![
+            "--soft",
+            "Only warn instead of refusing moves beyond the WIP limit",
+        ],
+        vec![
+            "--blocked",
+            "Have a new swimlane hold the tasks and subtasks waiting on their blockers",
+        ],
+        vec![
+            "--backlog",
+            "Put the unfinished tasks of a closed sprint back in the backlog instead of the next sprint",
+        ],
+        vec![
+            "--week",
+            "Show the timesheet of the current week per task and subtask",
+        ],
+        vec![
+            "--data-dir <Dir>",
+            "Directory holding all application data \n(pass when starting the application, overrides RUSTIC_BOARDS_HOME)",
+        ],
+        vec![
+            "--global",
+            "Use the global board even inside a project with its own board \n(pass when starting the application)",
+        ],
+    ];
+
+    match flags_vec
+        .table()
+        .title(vec![
+            "Flag (replaces the prompts)".cell().bold(true),
+            "Description".cell().bold(true),
+        ])
+        .display()
+    {
+        Ok(s) => println!("{}", s),
+        Err(e) => return Err(AppError::TableDisplayParseError(e.to_string())),
+    };
+
+    Ok(())
+}
+
+/// Display all details of a task or subtask in given output format. The table format is followed
+/// by the notes of the item.
+fn show_details<T: Record>(
+    format: OutputFormat,
+    titles: &[&str],
+    details: &T,
+    notes_list: &[String],
+) -> Result<(), AppError> {
+    output::print_details(format, titles, details)?;
+    if format != OutputFormat::Table {
+        return Ok(());
+    }
+
+    if notes_list.is_empty() {
+        println!("No notes found.");
+    } else {
+        println!("Additional Notes:");
+        for (idx, note) in notes_list.iter().enumerate() {
+            println!("{}) {}", idx + 1, note);
+        }
+    }
+    Ok(())
+}
+
+/// Display the tasks and subtasks in the trash or the archive in given output format
+fn show_hidden(
+    format: OutputFormat,
+    hidden_records: &[HiddenRecord],
+    shelf: Shelf,
+) -> Result<(), AppError> {
+    if hidden_records.is_empty() && format == OutputFormat::Table {
+        println!("The {} is empty.", shelf);
+        return Ok(());
+    }
+
+    let hidden_on_title: &str = match shelf {
+        Shelf::Trash => "Deleted On",
+        Shelf::Archive => "Archived On",
+    };
+    output::print_records(
+        format,
+        &["ID", "Name", "Swimlane", hidden_on_title, "Along With"],
+        hidden_records,
+    )
+}
+
+/// Print the warning for a WIP limit which was exceeded when moving a task or subtask
+fn show_wip_check(wip_check: &WipCheck) {
+    match wip_check {
+        WipCheck::Allowed => {}
+        WipCheck::Warning(message) => println!("Warning: {}", message),
+        WipCheck::Overridden(message) => {
+            println!("Warning: {} Moving anyway as --force was passed.", message)
+        }
+    }
+}
+
+/// Print the outcome of moving a task or subtask, along with the warnings raised by the move
+fn show_move_outcome(item_id: &str, swimlane: &str, move_outcome: &MoveOutcome) {
+    if let Some(message) = &move_outcome.blocked {
+        println!("Warning: {} Moving anyway as --force was passed.", message)
+    }
+    show_wip_check(&move_outcome.wip_check);
+    println!("{} moved to {} swimlane.", item_id, swimlane);
+    for (dependent, slug) in &move_outcome.unblocked {
+        println!("{} unblocked and moved to {} swimlane.", dependent, slug);
+    }
+}
+
+/// Take the details for a new task or subtask from the flags, falling back to prompts in
+/// interactive mode
+fn input_new_item(options: &CommandOptions, item_kind: &str) -> Result<NewItem, AppError> {
+    let label: &str = match item_kind {
+        "task" => "Task",
+        _ => "Subtask",
+    };
+    Ok(NewItem {
+        name: input_name(options, &format!("{} Name:", label))?,
+        description: input_description(options, &format!("{} Description:", label), None)?,
+        priority: input_priority(options, &format!("{} Priority:", label))?,
+        deadline: input_deadline(options, item_kind, None)?,
+        notes: input_notes(
+            options,
+            &format!("Do you want to add notes for this {}?", item_kind),
+        )?,
+    })
+}
+
+/// Take the name from `--name`, falling back to a prompt in interactive mode
+fn input_name(options: &CommandOptions, message: &str) -> Result<String, AppError> {
+    match (&options.name, options.interactive) {
+        (Some(s), _) => Ok(s.clone()),
+        (None, true) => text_input_prompt(message, None),
+        (None, false) => Err(AppError::MissingArgument(
+            "Please pass the name with --name".to_string(),
+        )),
+    }
+}
+
+/// Take the goal of a new sprint from `--goal`, falling back to a prompt in interactive mode
+fn input_goal(options: &CommandOptions) -> Result<String, AppError> {
+    match (&options.goal, options.interactive) {
+        (Some(s), _) => Ok(s.clone()),
+        (None, true) => text_input_prompt("Goal:", None),
+        (None, false) => Err(AppError::MissingArgument(
+            "Please pass the goal with --goal".to_string(),
+        )),
+    }
+}
+
+/// Take the email address from `--email`, falling back to a prompt in interactive mode (left
+/// blank for none)
+fn input_email(options: &CommandOptions) -> Result<Option<String>, AppError> {
+    match (&options.email, options.interactive) {
+        (Some(s), _) => Ok(Some(s.clone())),
+        (None, true) => {
+            let email: String = text_input_prompt("Email (leave blank for none):", None)?;
+            match email.trim().is_empty() {
+                true => Ok(None),
+                false => Ok(Some(email.trim().to_string())),
+            }
+        }
+        (None, false) => Ok(None),
+    }
+}
+
+/// Take the description from `--description`, falling back to a prompt in interactive mode.
+/// The current description (or a blank one) is kept if nothing was passed in one-shot mode.
+fn input_description(
+    options: &CommandOptions,
+    message: &str,
+    current: Option<&str>,
+) -> Result<String, AppError> {
+    match (&options.description, options.interactive) {
+        (Some(s), _) => Ok(s.clone()),
+        (None, true) => text_input_prompt(message, current),
+        (None, false) => Ok(current.unwrap_or_default().to_string()),
+    }
+}
+
+/// Take the priority from `--priority`, falling back to a prompt in interactive mode.
+/// Priority defaults to medium in one-shot mode.
+fn input_priority(options: &CommandOptions, message: &str) -> Result<TaskPriority, AppError> {
+    match (&options.priority, options.interactive) {
+        (Some(s), _) => Ok(*s),
+        (None, true) => select_prompt(message),
+        (None, false) => Ok(TaskPriority::Medium),
+    }
+}
+
+/// Take the swimlane stage from `--stage`, falling back to a prompt in interactive mode
+fn input_stage(options: &CommandOptions, message: &str) -> Result<LaneStage, AppError> {
+    match (&options.stage, options.interactive) {
+        (Some(s), _) => Ok(*s),
+        (None, true) => stage_select_prompt(message),
+        (None, false) => Err(AppError::MissingArgument(
+            "Please pass the swimlane stage with --stage (pending, started, done)".to_string(),
+        )),
+    }
+}
+
+/// Take the deadline from `--deadline`, falling back to prompts in interactive mode.
+/// The current deadline is kept if nothing was passed in one-shot mode.
+fn input_deadline(
+    options: &CommandOptions,
+    item_kind: &str,
+    current: Option<TimeStamp>,
+) -> Result<Option<TimeStamp>, AppError> {
+    if let Some(deadline) = options.deadline {
+        return Ok(deadline.map(TimeStamp::convert));
+    }
+    if !options.interactive {
+        return Ok(current);
+    }
+
+    let deadline_check: bool = match current {
+        Some(_) => confirm_prompt(
+            &format!("Do you want to change the deadline for this {}?", item_kind),
+            Some("It's recommended to set a deadline to track for completion."),
+        )?,
+        None => confirm_prompt(
+            &format!("Is there a deadline for this {}?", item_kind),
+            Some("It's recommended to set a deadline to track for completion."),
+        )?,
+    };
+
+    match (deadline_check, current) {
+        (true, _) => {
+            let message: String = match item_kind {
+                "task" => "Task Deadline:".to_string(),
+                _ => "Subtask Deadline:".to_string(),
+            };
+            Ok(Some(TimeStamp::convert(date_input_prompt(&message)?)))
+        }
+        (false, Some(s)) => Ok(Some(s)),
+        (false, None) => Ok(None),
+    }
+}
+
+/// Take the notes from `--note` flags, followed by prompts for more notes in interactive mode
+fn input_notes(options: &CommandOptions, message: &str) -> Result<Vec<String>, AppError> {
+    let mut notes_list: Vec<String> = options.notes.clone();
+    if options.interactive {
+        while confirm_prompt(message, None)? {
+            notes_list.push(text_input_prompt("Add a note:", None)?);
+        }
+    }
+    Ok(notes_list)
+}
+
+/// Take the swimlane to move tasks into from `--into`, falling back to a prompt in interactive mode
+/// when the swimlane being removed is not empty
+fn input_into_swimlane(
+    options: &CommandOptions,
+    boards: &KanbanBoard,
+    slug: &str,
+) -> Result<Option<String>, AppError> {
+    match (&options.into, options.interactive) {
+        (Some(s), _) => Ok(Some(s.clone())),
+        (None, true) if !boards.get_swimlane(slug)?.items.is_empty() => {
+            Ok(Some(swimlane_select_prompt(
+                &format!("Swimlane {} is not empty. Move its tasks into:", slug),
+                boards,
+                slug,
+            )?))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Take the parent Task ID from `--parent`, falling back to a prompt in interactive mode
+fn input_parent_task(options: &CommandOptions, board: &Board) -> Result<String, AppError> {
+    match (&options.parent, options.interactive) {
+        (Some(s), _) => Ok(s.clone()),
+        (None, true) => tasks_select_prompt(
+            "Select parent id to link to:",
+            board.storage(),
+            board.kanban(),
+        ),
+        (None, false) => Err(AppError::MissingArgument(
+            "Please pass the parent Task or SubTask ID with --parent".to_string(),
+        )),
+    }
+}
+
+/// Prompt for new subtasks to create under a task or subtask (only in interactive mode)
+fn input_subtasks(options: &CommandOptions, kind: &str) -> Result<Vec<NewItem>, AppError> {
+    let mut subtasks: Vec<NewItem> = Vec::new();
+    if !options.interactive {
+        return Ok(subtasks);
+    }
+
+    let subtask_options: CommandOptions = CommandOptions::interactive();
+    let message: String = format!("Do you want to create subtasks for this {}?", kind);
+    while confirm_prompt(&message, None)? {
+        subtasks.push(input_new_item(&subtask_options, "subtask")?);
+    }
+    Ok(subtasks)
+}
@@ -0,0 +1,128 @@
+//! Defines the application configuration along with associated helper methods
+
+use crate::{
+    constants::{BOARDS_PATH, CONFIG_FILE, DEFAULT_BOARD, SQLITE_DB_FILE, TEXT_INDEX_FILE},
+    error::AppError,
+    migrations::{self, RecordKind},
+    storage::StorageBackend,
+    utils::{create_app_dirs, list_dir_names, write_file_atomic},
+};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Rust structure for the application configuration
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct AppConfig {
+    /// Storage backend used to persist the Kanban Board data
+    pub storage_backend: StorageBackend,
+
+    /// Name of the board which commands are executed against
+    pub active_board: String,
+
+    /// Names of all boards in the order they were created
+    pub boards: Vec<String>,
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        AppConfig {
+            storage_backend: StorageBackend::default(),
+            active_board: DEFAULT_BOARD.to_string(),
+            boards: vec![DEFAULT_BOARD.to_string()],
+        }
+    }
+}
+
+impl AppConfig {
+    /// Load the application configuration from stored file in disk (defaults if not present).
+    /// A file written by an older version is upgraded and written back in the current format.
+    pub fn load() -> Result<Self, AppError> {
+        let (app_config, migrated): (AppConfig, bool) = AppConfig::read_file()?;
+        if migrated {
+            app_config.write_to_file()?;
+        }
+        Ok(app_config)
+    }
+
+    /// Read the application configuration without upgrading the stored file
+    pub fn read() -> Result<Self, AppError> {
+        Ok(AppConfig::read_file()?.0)
+    }
+
+    /// Upgrade the stored file to the current schema version (only report what would change when
+    /// `check_only` is set). Returns a description of each pending migration.
+    pub fn migrate(check_only: bool) -> Result<Vec<String>, AppError> {
+        let data: Vec<u8> = match AppConfig::read_bytes()? {
+            Some(s) => s,
+            None => return Ok(Vec::new()),
+        };
+        let change: Option<String> =
+            migrations::describe_pending(CONFIG_FILE, migrations::read_version(&data)?);
+        if change.is_some() && !check_only {
+            AppConfig::load()?;
+        }
+        Ok(change.into_iter().collect())
+    }
+
+    /// Check if a board with given name exists
+    pub fn has_board(&self, board_name: &str) -> bool {
+        self.boards.iter().any(|x| x == board_name)
+    }
+
+    /// Store the application configuration to a file in disk
+    pub fn write_to_file(&self) -> Result<(), AppError> {
+        let config_file: PathBuf = create_app_dirs()?.join(CONFIG_FILE);
+        let data: Vec<u8> = migrations::serialize(&self)?;
+        write_file_atomic(&config_file, &data)
+    }
+
+    /// Read the raw contents of the stored file (None if not present)
+    fn read_bytes() -> Result<Option<Vec<u8>>, AppError> {
+        let config_file: PathBuf = create_app_dirs()?.join(CONFIG_FILE);
+        if !config_file.exists() {
+            return Ok(None);
+        }
+        match std::fs::read(&config_file) {
+            Ok(s) => Ok(Some(s)),
+            Err(e) => Err(AppError::FileReadError(format!("{} - {}", CONFIG_FILE, e))),
+        }
+    }
+
+    /// Read and deserialize the stored file (defaults if not present), along with whether it had
+    /// to be upgraded from an older schema version
+    fn read_file() -> Result<(Self, bool), AppError> {
+        match AppConfig::read_bytes()? {
+            Some(data) => migrations::deserialize(RecordKind::Config, &data),
+            None => Ok((AppConfig::discover()?, false)),
+        }
+    }
+
+    /// Rebuild the configuration from the board directories when no file is stored, e.g. in a
+    /// fresh clone of a project board (which keeps the file out of git). The storage backend is
+    /// recognized from the files of the active board.
+    fn discover() -> Result<Self, AppError> {
+        let boards_dir: PathBuf = create_app_dirs()?.join(BOARDS_PATH);
+        let boards: Vec<String> = list_dir_names(&boards_dir)?;
+        let active_board: String = match boards.iter().any(|x| x == DEFAULT_BOARD) {
+            true => DEFAULT_BOARD.to_string(),
+            false => match boards.first() {
+                Some(s) => s.clone(),
+                None => return Ok(AppConfig::default()),
+            },
+        };
+
+        let board_dir: PathBuf = boards_dir.join(&active_board);
+        let storage_backend: StorageBackend = if board_dir.join(SQLITE_DB_FILE).exists() {
+            StorageBackend::Sqlite
+        } else if board_dir.join(TEXT_INDEX_FILE).exists() {
+            StorageBackend::Text
+        } else {
+            StorageBackend::Bincode
+        };
+        Ok(AppConfig {
+            storage_backend,
+            active_board,
+            boards,
+        })
+    }
+}
@@ -1,22 +1,82 @@
 //! Defines the constant values for the application
 
-/// Number of digits to keep when generating Task or SubTask IDs
-pub const DIGITS_IN_TASK_ID: usize = 5;
+/// Prefix of the Task IDs on a new board (e.g. TASK-42)
+pub const DEFAULT_ID_PREFIX: &str = "TASK";
 
-/// Application directory path
+/// Application directory inside the user's home directory on Windows
 pub const APP_DIR_PATH: &str = ".rustic_boards";
 
-/// Directory to store all tasks information
+/// Application directory inside the platform data directory on other platforms
+/// (e.g. `~/.local/share/rustic_boards` on Linux)
+pub const DATA_DIR_NAME: &str = "rustic_boards";
+
+/// Directory holding a project board, looked up from the current directory upward
+pub const PROJECT_DIR_PATH: &str = ".rustic_boards";
+
+/// Ignore file written into a new project board, keeping the files which only make sense on one
+/// machine out of git
+pub const GITIGNORE_FILE: &str = ".gitignore";
+
+/// Environment variable overriding the application directory
+pub const DATA_DIR_ENV_VAR: &str = "RUSTIC_BOARDS_HOME";
+
+/// Environment variable holding the handle of the current user (for `mine`)
+pub const USER_ENV_VAR: &str = "RUSTIC_BOARDS_USER";
+
+/// Directory holding one sub-directory per named board
+pub const BOARDS_PATH: &str = "boards";
+
+/// Name of the board created on first run
+pub const DEFAULT_BOARD: &str = "default";
+
+/// Directory to store all tasks and subtasks information
+pub const ITEMS_PATH: &str = ".items";
+
+/// Directory which stored all tasks information (up to schema version 9)
 pub const ACTIVE_TASKS_PATH: &str = ".tasks";
 
-/// Directory to store all subtasks information
+/// Directory which stored all subtasks information (up to schema version 9)
 pub const ACTIVE_SUBTASKS_PATH: &str = ".subtasks";
 
 /// File to store all Kanban Board information
 pub const KANBAN_BOARD_FILE: &str = "boards.bin";
 
-/// File to store all task-to-subtask link information
+/// File which stored all task-to-subtask link information (up to schema version 9)
 pub const TAKS_LINK_FILE: &str = "tasks_link.bin";
 
 /// File to store all notes information for Tasks and SubTasks
 pub const NOTES_FILE: &str = "notes.bin";
+
+/// Index file of the plain-text storage backend, holding the swimlanes of the Kanban Board
+pub const TEXT_INDEX_FILE: &str = "board.toml";
+
+/// File to store the application configuration (e.g. selected storage backend)
+pub const CONFIG_FILE: &str = "config.bin";
+
+/// File to store the operation journal of a board (for undo and redo)
+pub const JOURNAL_FILE: &str = "journal.bin";
+
+/// Number of most recent commands kept in the operation journal of a board
+pub const JOURNAL_LIMIT: usize = 100;
+
+/// Size in bytes the commands kept in the operation journal of a board may take up (the most
+/// recent command is always kept), as each of them holds snapshots of what it wrote
+pub const JOURNAL_SIZE_LIMIT: u64 = 2 * 1024 * 1024;
+
+/// File locked while a command runs, so that only one process changes the data at a time
+pub const LOCK_FILE: &str = "rustic_boards.lock";
+
+/// Database file used by the SQLite storage backend
+pub const SQLITE_DB_FILE: &str = "boards.db";
+
+/// Exit code returned when a command completes successfully
+pub const EXIT_SUCCESS: i32 = 0;
+
+/// Exit code returned when a command fails while being executed
+pub const EXIT_FAILURE: i32 = 1;
+
+/// Exit code returned when the command or its flags could not be understood
+pub const EXIT_USAGE_ERROR: i32 = 2;
+
+/// Number of days in a sprint created without an end date (two weeks)
+pub const SPRINT_LENGTH_DAYS: u64 = 14;
@@ -0,0 +1,204 @@
+//! Defines the dependencies between tasks and subtasks (which items are blocked by which), along
+//! with the cycle detection run when adding one
+
+use crate::error::AppError;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Rust structure for the dependencies between the tasks and subtasks of a board
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Eq, PartialEq)]
+pub struct Dependencies {
+    /// Task and SubTask IDs blocking each Task or SubTask ID, in the order they were added
+    pub blocked_by: BTreeMap<String, Vec<String>>,
+}
+
+impl Dependencies {
+    /// Fetch the Task and SubTask IDs directly blocking given ID
+    pub fn blockers(&self, item_id: &str) -> Vec<String> {
+        self.blocked_by.get(item_id).cloned().unwrap_or_default()
+    }
+
+    /// Fetch the Task and SubTask IDs directly blocked by given ID
+    pub fn dependents(&self, item_id: &str) -> Vec<String> {
+        self.blocked_by
+            .iter()
+            .filter(|(_, blockers)| blockers.iter().any(|x| x == item_id))
+            .map(|(dependent, _)| dependent.clone())
+            .collect()
+    }
+
+    /// Record that given ID is blocked by another one. Refused if the other ID is already
+    /// (directly or through other items) blocked by given ID, as the two could never be finished.
+    pub fn add(&mut self, item_id: &str, blocker_id: &str) -> Result<(), AppError> {
+        if item_id == blocker_id {
+            return Err(AppError::InvalidArgument(format!(
+                "{} - A task or subtask cannot depend on itself.",
+                item_id
+            )));
+        }
+        if self.blockers(item_id).iter().any(|x| x == blocker_id) {
+            return Err(AppError::InvalidArgument(format!(
+                "{} already depends on {}.",
+                item_id, blocker_id
+            )));
+        }
+        if let Some(path) = self.find_path(blocker_id, item_id, &mut Vec::new()) {
+            return Err(AppError::DependencyCycle(format!(
+                "{} cannot depend on {}, as that would close the cycle {} -> {}.",
+                item_id,
+                blocker_id,
+                item_id,
+                path.join(" -> ")
+            )));
+        }
+
+        self.blocked_by
+            .entry(item_id.to_string())
+            .or_default()
+            .push(blocker_id.to_string());
+        Ok(())
+    }
+
+    /// Remove the dependency of given ID on another one. Returns whether it existed.
+    pub fn remove(&mut self, item_id: &str, blocker_id: &str) -> bool {
+        let blockers: &mut Vec<String> = match self.blocked_by.get_mut(item_id) {
+            Some(s) => s,
+            None => return false,
+        };
+        let num_blockers: usize = blockers.len();
+        blockers.retain(|x| x != blocker_id);
+        let removed: bool = blockers.len() != num_blockers;
+        if blockers.is_empty() {
+            self.blocked_by.remove(item_id);
+        }
+        removed
+    }
+
+    /// Remove all dependencies of and on given ID (when it is deleted for good)
+    pub fn remove_item(&mut self, item_id: &str) {
+        self.blocked_by.remove(item_id);
+        for dependent in self.dependents(item_id) {
+            self.remove(&dependent, item_id);
+        }
+    }
+
+    /// Change every ID with given function (when re-keying the board)
+    pub fn rekey(&mut self, rekey: impl Fn(&String) -> String) {
+        self.blocked_by = self
+            .blocked_by
+            .iter()
+            .map(|(item_id, blockers)| (rekey(item_id), blockers.iter().map(&rekey).collect()))
+            .collect();
+    }
+
+    /// Fetch every chain of blockers leading to given ID, starting with a direct blocker
+    /// (e.g. TASK-3 blocked by TASK-2, which is blocked by TASK-1, gives [TASK-2, TASK-1])
+    pub fn chains(&self, item_id: &str) -> Vec<Vec<String>> {
+        let mut chains: Vec<Vec<String>> = Vec::new();
+        for blocker_id in self.blockers(item_id) {
+            let mut path: Vec<String> = vec![item_id.to_string()];
+            self.collect_chains(&blocker_id, &mut path, &mut chains);
+        }
+        chains
+    }
+
+    /// Collect the chains of blockers from given ID onward into `chains`
+    fn collect_chains(&self, item_id: &str, path: &mut Vec<String>, chains: &mut Vec<Vec<String>>) {
+        path.push(item_id.to_string());
+        let blockers: Vec<String> = self
+            .blockers(item_id)
+            .into_iter()
+            .filter(|x| !path.contains(x))
+            .collect();
+        if blockers.is_empty() {
+            chains.push(path[1..].to_vec());
+        }
+        for blocker_id in blockers {
+            self.collect_chains(&blocker_id, path, chains);
+        }
+        path.pop();
+    }
+
+    /// Find a chain of blockers leading from one ID to another (both included), if there is one
+    fn find_path(&self, from: &str, to: &str, visited: &mut Vec<String>) -> Option<Vec<String>> {
+        if from == to {
+            return Some(vec![to.to_string()]);
+        }
+        if visited.iter().any(|x| x == from) {
+            return None;
+        }
+        visited.push(from.to_string());
+        for blocker_id in self.blockers(from) {
+            if let Some(mut path) = self.find_path(&blocker_id, to, visited) {
+                path.insert(0, from.to_string());
+                return Some(path);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cycles_are_refused_with_the_path_closing_them() {
+        let mut dependencies: Dependencies = Dependencies::default();
+        dependencies.add("TASK-3", "TASK-2").unwrap();
+        dependencies.add("TASK-2", "TASK-1").unwrap();
+
+        let err: AppError = dependencies.add("TASK-1", "TASK-3").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "[DependencyCycle] TASK-1 cannot depend on TASK-3, as that would close the cycle \
+             TASK-1 -> TASK-3 -> TASK-2 -> TASK-1."
+        );
+        assert!(matches!(
+            dependencies.add("TASK-2", "TASK-3"),
+            Err(AppError::DependencyCycle(_))
+        ));
+        assert!(dependencies.blockers("TASK-1").is_empty());
+    }
+
+    #[test]
+    fn self_and_repeated_dependencies_are_refused() {
+        let mut dependencies: Dependencies = Dependencies::default();
+        assert!(matches!(
+            dependencies.add("TASK-1", "TASK-1"),
+            Err(AppError::InvalidArgument(_))
+        ));
+        dependencies.add("TASK-1", "TASK-2").unwrap();
+        assert!(matches!(
+            dependencies.add("TASK-1", "TASK-2"),
+            Err(AppError::InvalidArgument(_))
+        ));
+        assert_eq!(dependencies.blockers("TASK-1"), ["TASK-2"]);
+    }
+
+    #[test]
+    fn diamonds_are_not_cycles() {
+        let mut dependencies: Dependencies = Dependencies::default();
+        dependencies.add("TASK-4", "TASK-2").unwrap();
+        dependencies.add("TASK-4", "TASK-3").unwrap();
+        dependencies.add("TASK-2", "TASK-1").unwrap();
+        dependencies.add("TASK-3", "TASK-1").unwrap();
+
+        assert_eq!(
+            dependencies.chains("TASK-4"),
+            [vec!["TASK-2", "TASK-1"], vec!["TASK-3", "TASK-1"]]
+        );
+        assert_eq!(dependencies.dependents("TASK-1"), ["TASK-2", "TASK-3"]);
+    }
+
+    #[test]
+    fn removing_an_item_drops_its_dependencies_both_ways() {
+        let mut dependencies: Dependencies = Dependencies::default();
+        dependencies.add("TASK-3", "TASK-2").unwrap();
+        dependencies.add("TASK-2", "TASK-1").unwrap();
+
+        dependencies.remove_item("TASK-2");
+        assert!(dependencies.blocked_by.is_empty());
+        dependencies.add("TASK-1", "TASK-3").unwrap();
+    }
+}
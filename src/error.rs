@@ -1,5 +1,7 @@
 //! Defines the custom error types for the application
 
+use crate::constants::{EXIT_FAILURE, EXIT_USAGE_ERROR};
+
 /// Custom error types for the application
 #[allow(dead_code)]
 #[derive(Debug)]
@@ -23,6 +25,36 @@ pub enum AppError {
     NaiveDateConversionError(String),
     TableDisplayParseError(String),
     SwimlaneNotFoundError(String),
+    MissingArgument(String),
+    InvalidArgument(String),
+    DatabaseError(String),
+    UnsupportedSchemaVersion(String),
+    BoardNotFound(String),
+    UserNotFound(String),
+    WipLimitExceeded(String),
+    DependencyCycle(String),
+    BlockedByDependency(String),
+    TextFormatError(String),
+    LockError(String),
+    TransactionError(String),
+    JournalConflict(String),
+    OutputError(String),
+    TerminalError(String),
+}
+
+impl AppError {
+    /// Process exit code to report when the error ends a one-shot command
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            AppError::InvalidCommand(_)
+            | AppError::InvalidSwimlanePassed(_)
+            | AppError::InvalidDeadlineKeyword(_)
+            | AppError::InvalidPriorityKeyword(_)
+            | AppError::MissingArgument(_)
+            | AppError::InvalidArgument(_) => EXIT_USAGE_ERROR,
+            _ => EXIT_FAILURE,
+        }
+    }
 }
 
 impl std::fmt::Display for AppError {
@@ -59,6 +91,23 @@ impl std::fmt::Display for AppError {
             }
             AppError::TableDisplayParseError(err) => write!(f, "[TableDisplayParseError] {}", err),
             AppError::SwimlaneNotFoundError(err) => write!(f, "[SwimlaneNotFoundError] {}", err),
+            AppError::MissingArgument(err) => write!(f, "[MissingArgument] {}", err),
+            AppError::InvalidArgument(err) => write!(f, "[InvalidArgument] {}", err),
+            AppError::DatabaseError(err) => write!(f, "[DatabaseError] {}", err),
+            AppError::UnsupportedSchemaVersion(err) => {
+                write!(f, "[UnsupportedSchemaVersion] {}", err)
+            }
+            AppError::BoardNotFound(err) => write!(f, "[BoardNotFound] {}", err),
+            AppError::UserNotFound(err) => write!(f, "[UserNotFound] {}", err),
+            AppError::WipLimitExceeded(err) => write!(f, "[WipLimitExceeded] {}", err),
+            AppError::DependencyCycle(err) => write!(f, "[DependencyCycle] {}", err),
+            AppError::BlockedByDependency(err) => write!(f, "[BlockedByDependency] {}", err),
+            AppError::TextFormatError(err) => write!(f, "[TextFormatError] {}", err),
+            AppError::LockError(err) => write!(f, "[LockError] {}", err),
+            AppError::TransactionError(err) => write!(f, "[TransactionError] {}", err),
+            AppError::JournalConflict(err) => write!(f, "[JournalConflict] {}", err),
+            AppError::OutputError(err) => write!(f, "[OutputError] {}", err),
+            AppError::TerminalError(err) => write!(f, "[TerminalError] {}", err),
         }
     }
 }
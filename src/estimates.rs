@@ -0,0 +1,208 @@
+//! Defines the estimates of tasks and subtasks (in story points or time) along with the effort
+//! remaining on them, and the totals rolled up from subtasks to their parents
+
+use crate::{error::AppError, worklog};
+use serde::{Deserialize, Serialize};
+
+/// Units an estimate can be given in
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum EstimateUnit {
+    /// Story points
+    Points,
+
+    /// Time, in minutes
+    Time,
+}
+
+impl std::fmt::Display for EstimateUnit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EstimateUnit::Points => write!(f, "points"),
+            EstimateUnit::Time => write!(f, "time"),
+        }
+    }
+}
+
+impl std::str::FromStr for EstimateUnit {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "points" => Ok(EstimateUnit::Points),
+            "time" => Ok(EstimateUnit::Time),
+            _ => Err(AppError::InvalidArgument(format!(
+                "{} - Please select from following options: \n1) points 2) time\n",
+                s
+            ))),
+        }
+    }
+}
+
+/// Rust structure for the estimate of a task or subtask along with the effort remaining on it
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq)]
+pub struct Estimate {
+    /// Unit of the estimate
+    pub unit: EstimateUnit,
+
+    /// Effort the item was estimated at (story points or minutes)
+    pub original: u32,
+
+    /// Effort still remaining on the item, in the same unit
+    pub remaining: u32,
+}
+
+impl Estimate {
+    /// Create a new estimate with all of the effort remaining
+    pub fn new(unit: EstimateUnit, original: u32) -> Self {
+        Estimate {
+            unit,
+            original,
+            remaining: original,
+        }
+    }
+
+    /// Take given time spent off the remaining effort (for estimates in time)
+    pub fn log_time(&mut self, minutes: u32) {
+        if self.unit == EstimateUnit::Time {
+            self.remaining = self.remaining.saturating_sub(minutes);
+        }
+    }
+}
+
+impl std::fmt::Display for Estimate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", format_effort(self.unit, self.original))?;
+        match (self.unit, self.remaining) {
+            (_, remaining) if remaining == self.original => Ok(()),
+            (EstimateUnit::Points, remaining) => write!(f, " ({} left)", remaining),
+            (EstimateUnit::Time, remaining) => {
+                write!(f, " ({} left)", worklog::format_duration(remaining))
+            }
+        }
+    }
+}
+
+/// Estimates of a task or subtask and the subtasks under it, added up per unit
+#[derive(Debug, Clone, Copy, Default, Serialize, Eq, PartialEq)]
+pub struct EstimateTotals {
+    /// Story points estimated
+    pub points: u32,
+
+    /// Story points remaining
+    pub points_remaining: u32,
+
+    /// Time estimated, in minutes
+    pub minutes: u32,
+
+    /// Time remaining, in minutes
+    pub minutes_remaining: u32,
+}
+
+impl EstimateTotals {
+    /// Add given estimate to the totals
+    pub fn add(&mut self, estimate: &Estimate) {
+        match estimate.unit {
+            EstimateUnit::Points => {
+                self.points += estimate.original;
+                self.points_remaining += estimate.remaining;
+            }
+            EstimateUnit::Time => {
+                self.minutes += estimate.original;
+                self.minutes_remaining += estimate.remaining;
+            }
+        }
+    }
+
+    /// Add other totals to the totals
+    pub fn add_totals(&mut self, totals: &EstimateTotals) {
+        self.points += totals.points;
+        self.points_remaining += totals.points_remaining;
+        self.minutes += totals.minutes;
+        self.minutes_remaining += totals.minutes_remaining;
+    }
+
+    /// Check if no estimates were added up
+    pub fn is_empty(&self) -> bool {
+        self.points == 0 && self.minutes == 0
+    }
+
+    /// Story points estimated and remaining, e.g. "8 pts (3 left)" (None without points)
+    pub fn display_points(&self) -> Option<String> {
+        match self.points {
+            0 => None,
+            _ => Some(
+                Estimate {
+                    unit: EstimateUnit::Points,
+                    original: self.points,
+                    remaining: self.points_remaining,
+                }
+                .to_string(),
+            ),
+        }
+    }
+}
+
+impl std::fmt::Display for EstimateTotals {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut parts: Vec<String> = Vec::new();
+        if let Some(points) = self.display_points() {
+            parts.push(points);
+        }
+        if self.minutes > 0 {
+            parts.push(
+                Estimate {
+                    unit: EstimateUnit::Time,
+                    original: self.minutes,
+                    remaining: self.minutes_remaining,
+                }
+                .to_string(),
+            );
+        }
+        match parts.is_empty() {
+            true => write!(f, "None"),
+            false => write!(f, "{}", parts.join(", ")),
+        }
+    }
+}
+
+/// Parse an effort given in story points (e.g. 5 or 5pt) or time (e.g. 1h30m, 2h or 45m).
+/// No effort (e.g. 0, 0pt or 0m) is only accepted for the remaining effort.
+pub fn parse_effort(value: &str) -> Result<(EstimateUnit, u32), AppError> {
+    let lowercase: String = value.to_lowercase();
+    let points: &str = lowercase
+        .strip_suffix("pts")
+        .or_else(|| lowercase.strip_suffix("pt"))
+        .unwrap_or(&lowercase);
+    if !points.is_empty() && points.chars().all(|x| x.is_ascii_digit()) {
+        return match points.parse::<u32>() {
+            Ok(s) => Ok((EstimateUnit::Points, s)),
+            Err(_) => Err(invalid_effort(value)),
+        };
+    }
+
+    let digits: &str = lowercase.trim_end_matches(['h', 'm']);
+    if digits.len() < lowercase.len() && !digits.is_empty() && digits.chars().all(|x| x == '0') {
+        return Ok((EstimateUnit::Time, 0));
+    }
+    match worklog::parse_duration(value) {
+        Ok(s) => Ok((EstimateUnit::Time, s)),
+        Err(_) => Err(invalid_effort(value)),
+    }
+}
+
+/// Format an effort in given unit, e.g. "1 pt", "5 pts" or "1h30m"
+pub fn format_effort(unit: EstimateUnit, value: u32) -> String {
+    match (unit, value) {
+        (EstimateUnit::Points, 1) => "1 pt".to_string(),
+        (EstimateUnit::Points, _) => format!("{} pts", value),
+        (EstimateUnit::Time, _) => worklog::format_duration(value),
+    }
+}
+
+fn invalid_effort(value: &str) -> AppError {
+    AppError::InvalidArgument(format!(
+        "{} - Please enter story points (e.g. 5 or 5pt) or time (e.g. 1h30m, 2h or 45m).",
+        value
+    ))
+}
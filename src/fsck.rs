@@ -0,0 +1,501 @@
+//! Defines the integrity checker, which finds entries of the Kanban Board, parent pointers and
+//! notes that don't match the stored tasks and subtasks, along with the repair for each
+
+use crate::{
+    boards::{HiddenItem, KanbanBoard, Shelf},
+    error::AppError,
+    hierarchy::Hierarchy,
+    items::WorkItem,
+    notes::TaskNotes,
+    output::{self, Record},
+    storage::Storage,
+};
+use serde::Serialize;
+use std::collections::HashSet;
+
+/// Kinds of problems found by the integrity checker
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ProblemKind {
+    /// The board lists an ID which is not stored
+    MissingItem,
+
+    /// The board lists an ID more than once
+    Duplicate,
+
+    /// The status of a task or subtask doesn't match the swimlane listing it
+    StatusMismatch,
+
+    /// A stored task or subtask is not listed on the board
+    OrphanItem,
+
+    /// A subtask is nested under a task or subtask which is not stored
+    MissingParent,
+
+    /// A subtask is nested under itself through its parents
+    ParentCycle,
+
+    /// Notes are kept for a task or subtask which is not stored
+    OrphanNotes,
+
+    /// A task or subtask is assigned to a person who is not registered on the board
+    UnknownAssignee,
+
+    /// A dependency involves a task or subtask which is not stored
+    BrokenDependency,
+}
+
+impl std::fmt::Display for ProblemKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProblemKind::MissingItem => write!(f, "missing item"),
+            ProblemKind::Duplicate => write!(f, "duplicate"),
+            ProblemKind::StatusMismatch => write!(f, "status mismatch"),
+            ProblemKind::OrphanItem => write!(f, "orphan item"),
+            ProblemKind::MissingParent => write!(f, "missing parent"),
+            ProblemKind::ParentCycle => write!(f, "parent cycle"),
+            ProblemKind::OrphanNotes => write!(f, "orphan notes"),
+            ProblemKind::UnknownAssignee => write!(f, "unknown assignee"),
+            ProblemKind::BrokenDependency => write!(f, "broken dependency"),
+        }
+    }
+}
+
+/// Place on the board where a Task or SubTask ID is listed
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Location {
+    /// Swimlane with given slug
+    Swimlane(String),
+
+    /// Trash or archive
+    Shelf(Shelf),
+}
+
+impl std::fmt::Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Location::Swimlane(slug) => write!(f, "swimlane {}", slug),
+            Location::Shelf(shelf) => write!(f, "the {}", shelf),
+        }
+    }
+}
+
+/// Repair for a problem found by the integrity checker
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Repair {
+    /// Remove one listing of the ID from given place on the board
+    RemoveEntry(Location),
+
+    /// Set the status of the task or subtask to the swimlane with given slug
+    SetStatus(String),
+
+    /// List the task or subtask in the swimlane with given slug (setting its status to it)
+    AddToSwimlane(String),
+
+    /// Nest the subtask under given parent Task or SubTask ID
+    LinkTo(String),
+
+    /// Make the subtask a task, by clearing its parent
+    Unlink,
+
+    /// Delete all notes of the task or subtask
+    DeleteNotes,
+
+    /// Leave the task or subtask unassigned
+    Unassign,
+
+    /// Remove the dependency of the task or subtask on given Task or SubTask ID
+    RemoveDependency(String),
+}
+
+impl std::fmt::Display for Repair {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Repair::RemoveEntry(location) => write!(f, "Remove it from {}", location),
+            Repair::SetStatus(slug) => write!(f, "Set its status to {}", slug),
+            Repair::AddToSwimlane(slug) => write!(f, "Put it in swimlane {}", slug),
+            Repair::LinkTo(parent_id) => write!(f, "Link it to {}", parent_id),
+            Repair::Unlink => write!(f, "Make it a top-level task"),
+            Repair::DeleteNotes => write!(f, "Delete its notes"),
+            Repair::Unassign => write!(f, "Unassign it"),
+            Repair::RemoveDependency(blocker_id) => {
+                write!(f, "Remove its dependency on {}", blocker_id)
+            }
+        }
+    }
+}
+
+/// Rust structure for a problem found by the integrity checker
+#[derive(Debug, Clone, Serialize)]
+pub struct Problem {
+    /// Kind of problem
+    #[serde(serialize_with = "output::serialize_lowercase")]
+    pub kind: ProblemKind,
+
+    /// Task or SubTask ID the problem was found for
+    pub item_id: String,
+
+    /// What is wrong
+    pub details: String,
+
+    /// How `fsck --repair` fixes it
+    #[serde(serialize_with = "output::serialize_display")]
+    pub repair: Repair,
+}
+
+impl Record for Problem {
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.kind.to_string(),
+            self.item_id.clone(),
+            self.details.clone(),
+            self.repair.to_string(),
+        ]
+    }
+}
+
+/// Check the Kanban Board, parent pointers and notes against the stored tasks and subtasks.
+/// Returns the problems found, in the order their repairs have to be applied.
+pub fn check_board(
+    storage: &dyn Storage,
+    boards: &KanbanBoard,
+    task_notes: &TaskNotes,
+) -> Result<Vec<Problem>, AppError> {
+    let items: HashSet<String> = storage.list_items()?.into_iter().collect();
+    let hierarchy: Hierarchy = Hierarchy::load(storage)?;
+    let mut problems: Vec<Problem> = Vec::new();
+
+    let mut entries: Vec<(String, Location)> = Vec::new();
+    for swimlane in &boards.swimlanes {
+        for item_id in &swimlane.items {
+            entries.push((item_id.clone(), Location::Swimlane(swimlane.slug.clone())));
+        }
+    }
+    for (shelf, hidden_items) in [
+        (Shelf::Trash, &boards.trash),
+        (Shelf::Archive, &boards.archive),
+    ] {
+        for hidden_item in hidden_items {
+            entries.push((hidden_item.item_id.clone(), Location::Shelf(shelf)));
+        }
+    }
+
+    let mut listed: HashSet<String> = HashSet::new();
+    for (item_id, location) in entries {
+        if !items.contains(&item_id) {
+            problems.push(Problem {
+                kind: ProblemKind::MissingItem,
+                details: format!("Listed in {} but not stored", location),
+                item_id,
+                repair: Repair::RemoveEntry(location),
+            });
+        } else if listed.contains(&item_id) {
+            problems.push(Problem {
+                kind: ProblemKind::Duplicate,
+                details: format!("Listed again in {}", location),
+                item_id,
+                repair: Repair::RemoveEntry(location),
+            });
+        } else {
+            if let Location::Swimlane(slug) = &location {
+                let status: String = item_status(storage, &item_id)?;
+                if status != *slug {
+                    problems.push(Problem {
+                        kind: ProblemKind::StatusMismatch,
+                        item_id: item_id.clone(),
+                        details: format!("Status is {} but listed in swimlane {}", status, slug),
+                        repair: Repair::SetStatus(slug.clone()),
+                    });
+                }
+            }
+            listed.insert(item_id);
+        }
+    }
+
+    let first_swimlane: String = boards.first_swimlane()?.slug.clone();
+    for item_id in sorted(items.iter()) {
+        if listed.contains(&item_id) {
+            continue;
+        }
+        let status: String = item_status(storage, &item_id)?;
+        let slug: String = match boards.get_swimlane(&status) {
+            Ok(_) => status,
+            Err(_) => first_swimlane.clone(),
+        };
+        problems.push(Problem {
+            kind: ProblemKind::OrphanItem,
+            item_id,
+            details: "Stored but not listed on the board".to_string(),
+            repair: Repair::AddToSwimlane(slug),
+        });
+    }
+
+    for (item_id, parent_id) in &hierarchy.parents {
+        let in_trash: bool = boards.trash.iter().any(|x| x.item_id == *item_id);
+        if items.contains(parent_id) || in_trash {
+            continue;
+        }
+        // Subtask IDs are generated under their parent ID (e.g. TASK-3.1 under TASK-3)
+        let repair: Repair = match item_id.rsplit_once('.') {
+            Some((derived_id, _)) if items.contains(derived_id) => {
+                Repair::LinkTo(derived_id.to_string())
+            }
+            _ => Repair::Unlink,
+        };
+        problems.push(Problem {
+            kind: ProblemKind::MissingParent,
+            item_id: item_id.clone(),
+            details: format!("Nested under {} which is not stored", parent_id),
+            repair,
+        });
+    }
+
+    for item_id in hierarchy.parents.keys() {
+        // Each cycle is reported once, for the smallest ID in it
+        let mut cycle: Vec<String> = vec![item_id.clone()];
+        while let Some(parent_id) = hierarchy.parent(cycle.last().unwrap()) {
+            if parent_id == *item_id {
+                if cycle.iter().min() == Some(item_id) {
+                    problems.push(Problem {
+                        kind: ProblemKind::ParentCycle,
+                        item_id: item_id.clone(),
+                        details: format!("Nested under itself through {}", cycle.join(" -> ")),
+                        repair: Repair::Unlink,
+                    });
+                }
+                break;
+            }
+            if cycle.contains(&parent_id) {
+                break;
+            }
+            cycle.push(parent_id);
+        }
+    }
+
+    for item_id in sorted(task_notes.notes.keys()) {
+        if !items.contains(&item_id) {
+            problems.push(Problem {
+                kind: ProblemKind::OrphanNotes,
+                item_id,
+                details: "Has notes but is not stored".to_string(),
+                repair: Repair::DeleteNotes,
+            });
+        }
+    }
+
+    for item_id in sorted(items.iter()) {
+        if let Some(handle) = item_assignee(storage, &item_id)? {
+            if !boards.people.iter().any(|x| x.handle == handle) {
+                problems.push(Problem {
+                    kind: ProblemKind::UnknownAssignee,
+                    item_id,
+                    details: format!("Assigned to {} who is not registered", handle),
+                    repair: Repair::Unassign,
+                });
+            }
+        }
+    }
+
+    for (item_id, blockers) in &boards.dependencies.blocked_by {
+        for blocker_id in blockers {
+            let details: String = match (item_id, blocker_id) {
+                (x, _) if !items.contains(x) => {
+                    format!("Depends on {} but is not stored", blocker_id)
+                }
+                (_, y) if !items.contains(y) => {
+                    format!("Depends on {} which is not stored", blocker_id)
+                }
+                _ => continue,
+            };
+            problems.push(Problem {
+                kind: ProblemKind::BrokenDependency,
+                item_id: item_id.clone(),
+                details,
+                repair: Repair::RemoveDependency(blocker_id.clone()),
+            });
+        }
+    }
+
+    Ok(problems)
+}
+
+/// Apply the repair of each given problem (as found by `check_board`)
+pub fn repair_board(
+    storage: &dyn Storage,
+    boards: &mut KanbanBoard,
+    task_notes: &mut TaskNotes,
+    problems: &[Problem],
+) -> Result<(), AppError> {
+    for problem in problems {
+        let item_id: &String = &problem.item_id;
+        match &problem.repair {
+            Repair::RemoveEntry(Location::Swimlane(slug)) => {
+                if let Some(swimlane) = boards.swimlanes.iter_mut().find(|x| x.slug == *slug) {
+                    remove_last(&mut swimlane.items, item_id);
+                }
+            }
+            Repair::RemoveEntry(Location::Shelf(shelf)) => {
+                let hidden_items: &mut Vec<HiddenItem> = boards.shelf_mut(*shelf);
+                if let Some(s) = hidden_items.iter().rposition(|x| x.item_id == *item_id) {
+                    hidden_items.remove(s);
+                }
+            }
+            Repair::SetStatus(slug) => set_status(storage, item_id, slug)?,
+            Repair::AddToSwimlane(slug) => {
+                if let Some(swimlane) = boards.swimlanes.iter_mut().find(|x| x.slug == *slug) {
+                    swimlane.items.push(item_id.clone());
+                }
+                if item_status(storage, item_id)? != *slug {
+                    set_status(storage, item_id, slug)?;
+                }
+            }
+            Repair::LinkTo(parent_id) => set_parent(storage, item_id, Some(parent_id))?,
+            Repair::Unlink => set_parent(storage, item_id, None)?,
+            Repair::DeleteNotes => {
+                task_notes.notes.remove(item_id);
+            }
+            Repair::Unassign => unassign(storage, item_id)?,
+            Repair::RemoveDependency(blocker_id) => {
+                boards.dependencies.remove(item_id, blocker_id);
+            }
+        }
+    }
+
+    boards.save(storage)?;
+    task_notes.save(storage)?;
+    Ok(())
+}
+
+/// Get the status (swimlane slug) of given stored Task or SubTask ID
+fn item_status(storage: &dyn Storage, item_id: &str) -> Result<String, AppError> {
+    Ok(WorkItem::get(storage, item_id)?.status)
+}
+
+/// Set the status of given stored Task or SubTask ID to the swimlane with given slug
+fn set_status(storage: &dyn Storage, item_id: &str, slug: &str) -> Result<(), AppError> {
+    let mut work_item: WorkItem = WorkItem::get(storage, item_id)?;
+    work_item.status = slug.to_string();
+    work_item.save(storage)
+}
+
+/// Set the parent of given stored Task or SubTask ID (None makes it a task)
+fn set_parent(
+    storage: &dyn Storage,
+    item_id: &str,
+    parent_id: Option<&String>,
+) -> Result<(), AppError> {
+    let mut work_item: WorkItem = WorkItem::get(storage, item_id)?;
+    work_item.parent = parent_id.cloned();
+    work_item.save(storage)
+}
+
+/// Get the assignee of given stored Task or SubTask ID
+fn item_assignee(storage: &dyn Storage, item_id: &str) -> Result<Option<String>, AppError> {
+    Ok(WorkItem::get(storage, item_id)?.assignee)
+}
+
+/// Leave given stored Task or SubTask ID unassigned
+fn unassign(storage: &dyn Storage, item_id: &str) -> Result<(), AppError> {
+    let mut work_item: WorkItem = WorkItem::get(storage, item_id)?;
+    work_item.assignee = None;
+    work_item.save(storage)
+}
+
+/// Remove the last occurrence of given ID from given list
+fn remove_last(item_ids: &mut Vec<String>, item_id: &str) {
+    if let Some(s) = item_ids.iter().rposition(|x| x == item_id) {
+        item_ids.remove(s);
+    }
+}
+
+/// Collect given IDs in sorted order (so that problems are reported in a stable order)
+fn sorted<'a>(item_ids: impl Iterator<Item = &'a String>) -> Vec<String> {
+    let mut item_ids: Vec<String> = item_ids.cloned().collect();
+    item_ids.sort();
+    item_ids
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        constants::ITEMS_PATH,
+        storage::bincode_store::BincodeStorage,
+        swimlanes::Swimlane,
+        utils::{create_dir, test_dir, TaskPriority},
+    };
+
+    /// Store a task with given ID in given swimlane
+    fn store_task(storage: &dyn Storage, item_id: &str, swimlane: &Swimlane) {
+        WorkItem::new(
+            item_id.to_string(),
+            item_id.to_string(),
+            String::new(),
+            None,
+            TaskPriority::Medium,
+            swimlane,
+            None,
+        )
+        .unwrap()
+        .save(storage)
+        .unwrap();
+    }
+
+    #[test]
+    fn problems_are_reported_and_repaired() {
+        let board_dir: std::path::PathBuf = test_dir("fsck_repair");
+        create_dir(&board_dir.join(ITEMS_PATH)).unwrap();
+        let storage: BincodeStorage = BincodeStorage::new(board_dir);
+        let mut boards: KanbanBoard = KanbanBoard::new();
+        store_task(&storage, "TASK-1", &boards.swimlanes[1]);
+        store_task(&storage, "TASK-2", &boards.swimlanes[0]);
+        boards.swimlanes[0].items = vec!["TASK-1".to_string(), "TASK-3".to_string()];
+        boards.dependencies.add("TASK-1", "TASK-9").unwrap();
+        let mut task_notes: TaskNotes = TaskNotes::default();
+        task_notes
+            .notes
+            .insert("TASK-4".to_string(), vec!["Stray".to_string()]);
+        boards.save(&storage).unwrap();
+
+        let problems: Vec<Problem> = check_board(&storage, &boards, &task_notes).unwrap();
+        let found: Vec<(ProblemKind, &str, Repair)> = problems
+            .iter()
+            .map(|x| (x.kind, x.item_id.as_str(), x.repair.clone()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (
+                    ProblemKind::StatusMismatch,
+                    "TASK-1",
+                    Repair::SetStatus("to-do".to_string())
+                ),
+                (
+                    ProblemKind::MissingItem,
+                    "TASK-3",
+                    Repair::RemoveEntry(Location::Swimlane("to-do".to_string()))
+                ),
+                (
+                    ProblemKind::OrphanItem,
+                    "TASK-2",
+                    Repair::AddToSwimlane("to-do".to_string())
+                ),
+                (ProblemKind::OrphanNotes, "TASK-4", Repair::DeleteNotes),
+                (
+                    ProblemKind::BrokenDependency,
+                    "TASK-1",
+                    Repair::RemoveDependency("TASK-9".to_string())
+                ),
+            ]
+        );
+
+        repair_board(&storage, &mut boards, &mut task_notes, &problems).unwrap();
+        assert!(check_board(&storage, &boards, &task_notes)
+            .unwrap()
+            .is_empty());
+        assert_eq!(boards.swimlanes[0].items, ["TASK-1", "TASK-2"]);
+        assert_eq!(WorkItem::get(&storage, "TASK-1").unwrap().status, "to-do");
+        assert!(boards.dependencies.blockers("TASK-1").is_empty());
+        assert!(task_notes.notes.is_empty());
+        assert_eq!(storage.load_board().unwrap(), Some(boards));
+    }
+}
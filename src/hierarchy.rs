@@ -0,0 +1,173 @@
+//! Defines the parent-to-child structure of the work items of a board along with associated helper
+//! methods
+
+use crate::{error::AppError, ids::id_sort_key, items::WorkItem, storage::Storage};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Rust structure for the nesting of work items. It is built from the parent pointer stored with
+/// each item and is never stored on its own.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Hierarchy {
+    /// Parent ID for each Task or SubTask ID which is nested under another item
+    pub(crate) parents: BTreeMap<String, String>,
+}
+
+impl Hierarchy {
+    /// Create new blank hierarchy (for first time setup)
+    pub fn new() -> Self {
+        Hierarchy {
+            parents: BTreeMap::new(),
+        }
+    }
+
+    /// Build the hierarchy from the parent pointers of all stored items
+    pub fn load(storage: &dyn Storage) -> Result<Self, AppError> {
+        let mut hierarchy: Hierarchy = Hierarchy::new();
+        for item_id in storage.list_items()? {
+            let work_item: WorkItem = WorkItem::get(storage, &item_id)?;
+            if let Some(parent_id) = work_item.parent {
+                hierarchy.parents.insert(item_id, parent_id);
+            }
+        }
+        Ok(hierarchy)
+    }
+
+    /// Get parent ID for a given Task or SubTask ID (None for tasks)
+    pub fn parent(&self, item_id: &str) -> Option<String> {
+        self.parents.get(item_id).cloned()
+    }
+
+    /// Get list of SubTask IDs directly under a given Task or SubTask ID, in ID order
+    pub fn children(&self, item_id: &str) -> Vec<String> {
+        let mut children: Vec<String> = self
+            .parents
+            .iter()
+            .filter(|(_, parent_id)| *parent_id == item_id)
+            .map(|(child_id, _)| child_id.clone())
+            .collect();
+        children.sort_by_key(|x| id_sort_key(x));
+        children
+    }
+
+    /// Get list of SubTask IDs under a given Task or SubTask ID at every depth (depth first, in
+    /// ID order), along with their depth below it (1 for direct children)
+    pub fn descendants(&self, item_id: &str) -> Vec<(String, usize)> {
+        let mut descendants: Vec<(String, usize)> = Vec::new();
+        let mut visited: BTreeSet<String> = BTreeSet::from([item_id.to_string()]);
+        let mut pending: Vec<(String, usize)> = self
+            .children(item_id)
+            .into_iter()
+            .rev()
+            .map(|x| (x, 1))
+            .collect();
+        while let Some((child_id, depth)) = pending.pop() {
+            if !visited.insert(child_id.clone()) {
+                continue;
+            }
+            pending.extend(
+                self.children(&child_id)
+                    .into_iter()
+                    .rev()
+                    .map(|x| (x, depth + 1)),
+            );
+            descendants.push((child_id, depth));
+        }
+        descendants
+    }
+
+    /// Get list of IDs above a given Task or SubTask ID, starting with its parent
+    pub fn ancestors(&self, item_id: &str) -> Vec<String> {
+        let mut ancestors: Vec<String> = Vec::new();
+        let mut current_id: String = item_id.to_string();
+        while let Some(parent_id) = self.parents.get(&current_id) {
+            if parent_id == item_id || ancestors.contains(parent_id) {
+                break;
+            }
+            ancestors.push(parent_id.clone());
+            current_id = parent_id.clone();
+        }
+        ancestors
+    }
+
+    /// Check if a given Task or SubTask ID is nested (at any depth) under another one
+    pub fn is_under(&self, item_id: &str, ancestor_id: &str) -> bool {
+        self.ancestors(item_id).iter().any(|x| x == ancestor_id)
+    }
+
+    /// Nest a given Task or SubTask ID under a parent ID (None makes it a task)
+    pub fn set_parent(&mut self, item_id: &str, parent_id: Option<String>) {
+        match parent_id {
+            Some(s) => self.parents.insert(item_id.to_string(), s),
+            None => self.parents.remove(item_id),
+        };
+    }
+
+    /// Remove given Task and SubTask IDs from the hierarchy
+    pub fn forget(&mut self, item_ids: &[String]) {
+        for item_id in item_ids {
+            self.parents.remove(item_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build a hierarchy from given (child, parent) pairs
+    fn test_hierarchy(links: &[(&str, &str)]) -> Hierarchy {
+        let mut hierarchy: Hierarchy = Hierarchy::new();
+        for (item_id, parent_id) in links {
+            hierarchy.set_parent(item_id, Some(parent_id.to_string()));
+        }
+        hierarchy
+    }
+
+    #[test]
+    fn descendants_and_ancestors_follow_every_depth() {
+        let hierarchy: Hierarchy = test_hierarchy(&[
+            ("TASK-1.1", "TASK-1"),
+            ("TASK-1.2", "TASK-1"),
+            ("TASK-1.1.1", "TASK-1.1"),
+            ("TASK-1.10", "TASK-1"),
+        ]);
+        assert_eq!(
+            hierarchy.children("TASK-1"),
+            ["TASK-1.1", "TASK-1.2", "TASK-1.10"]
+        );
+        assert_eq!(
+            hierarchy.descendants("TASK-1"),
+            [
+                ("TASK-1.1".to_string(), 1),
+                ("TASK-1.1.1".to_string(), 2),
+                ("TASK-1.2".to_string(), 1),
+                ("TASK-1.10".to_string(), 1)
+            ]
+        );
+        assert_eq!(hierarchy.ancestors("TASK-1.1.1"), ["TASK-1.1", "TASK-1"]);
+        assert!(hierarchy.is_under("TASK-1.1.1", "TASK-1"));
+        assert!(!hierarchy.is_under("TASK-1", "TASK-1.1.1"));
+        assert!(!hierarchy.is_under("TASK-1.2", "TASK-1.1"));
+    }
+
+    #[test]
+    fn parent_cycles_are_not_followed_forever() {
+        let hierarchy: Hierarchy =
+            test_hierarchy(&[("TASK-1.1", "TASK-1.2"), ("TASK-1.2", "TASK-1.1")]);
+        assert_eq!(hierarchy.ancestors("TASK-1.1"), ["TASK-1.2"]);
+        assert_eq!(
+            hierarchy.descendants("TASK-1.1"),
+            [("TASK-1.2".to_string(), 1)]
+        );
+    }
+
+    #[test]
+    fn forgotten_items_are_no_longer_nested() {
+        let mut hierarchy: Hierarchy =
+            test_hierarchy(&[("TASK-1.1", "TASK-1"), ("TASK-1.2", "TASK-1")]);
+        hierarchy.forget(&["TASK-1.1".to_string()]);
+        assert_eq!(hierarchy.children("TASK-1"), ["TASK-1.2"]);
+        hierarchy.set_parent("TASK-1.2", None);
+        assert!(hierarchy.children("TASK-1").is_empty());
+    }
+}
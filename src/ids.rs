@@ -0,0 +1,374 @@
+//! Defines the sequential Task and SubTask ID generation of a board along with the re-keying of
+//! existing IDs
+
+use crate::{
+    boards::KanbanBoard, constants::DEFAULT_ID_PREFIX, error::AppError, hierarchy::Hierarchy,
+    items::WorkItem, notes::TaskNotes, storage::Storage,
+};
+use chrono::prelude::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Rust structure for the ID sequence of a board. Tasks get `<Prefix>-<N>` IDs (e.g. WEB-42) and
+/// subtasks get `<Parent ID>.<N>` IDs (e.g. WEB-42.3, or WEB-42.3.1 one level deeper). Numbers are
+/// never handed out twice.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct IdSequence {
+    /// Prefix of the Task IDs
+    pub prefix: String,
+
+    /// Number of the most recently generated Task ID
+    pub last_task_number: u64,
+
+    /// Number of the most recently generated SubTask ID for each parent Task or SubTask ID
+    pub last_subtask_numbers: HashMap<String, u64>,
+}
+
+impl Default for IdSequence {
+    fn default() -> Self {
+        IdSequence::new(DEFAULT_ID_PREFIX)
+    }
+}
+
+impl IdSequence {
+    /// Create new ID sequence starting at 1 with given Task ID prefix
+    pub fn new(prefix: &str) -> Self {
+        IdSequence {
+            prefix: prefix.to_string(),
+            last_task_number: 0,
+            last_subtask_numbers: HashMap::new(),
+        }
+    }
+
+    /// Generate the next Task ID, skipping any ID which is already taken by a stored item
+    pub fn next_task_id(&mut self, storage: &dyn Storage) -> Result<String, AppError> {
+        loop {
+            self.last_task_number += 1;
+            let task_id: String = format!("{}-{}", self.prefix, self.last_task_number);
+            if !WorkItem::check_if_exists(storage, &task_id)? {
+                return Ok(task_id);
+            }
+        }
+    }
+
+    /// Generate the next SubTask ID under given parent Task or SubTask ID, skipping any ID which
+    /// is already taken by a stored item
+    pub fn next_subtask_id(
+        &mut self,
+        storage: &dyn Storage,
+        parent_id: &str,
+    ) -> Result<String, AppError> {
+        let last_number: &mut u64 = self
+            .last_subtask_numbers
+            .entry(parent_id.to_string())
+            .or_insert(0);
+        loop {
+            *last_number += 1;
+            let subtask_id: String = format!("{}.{}", parent_id, last_number);
+            if !WorkItem::check_if_exists(storage, &subtask_id)? {
+                return Ok(subtask_id);
+            }
+        }
+    }
+}
+
+/// Check that given Task ID prefix only has letters and digits, returning it in uppercase
+pub fn validate_id_prefix(prefix: &str) -> Result<String, AppError> {
+    if prefix.is_empty() || !prefix.chars().all(|x| x.is_ascii_alphanumeric()) {
+        return Err(AppError::InvalidArgument(format!(
+            "{} - ID prefixes can only have letters and digits.",
+            prefix
+        )));
+    }
+    Ok(prefix.to_ascii_uppercase())
+}
+
+/// Sort key for Task and SubTask IDs, comparing the numbers in them numerically
+/// (e.g. TASK-2 before TASK-10, TASK-10 before TASK-10.1)
+pub fn id_sort_key(item_id: &str) -> (String, Vec<u64>) {
+    match item_id.rsplit_once('-') {
+        Some((prefix, numbers)) => (
+            prefix.to_string(),
+            numbers
+                .split('.')
+                .map(|x| x.parse::<u64>().unwrap_or(u64::MAX))
+                .collect(),
+        ),
+        None => (item_id.to_string(), Vec::new()),
+    }
+}
+
+/// Re-key all tasks on the board (in order of creation) and the subtasks under them at every depth
+/// (in ID order) to sequential IDs, restarting the ID sequence of the board. Returns the current
+/// and new ID of each item whose ID changes. Nothing is changed when `check_only` is set.
+pub fn rekey_board(
+    storage: &dyn Storage,
+    boards: &mut KanbanBoard,
+    hierarchy: &Hierarchy,
+    task_notes: &mut TaskNotes,
+    check_only: bool,
+) -> Result<Vec<(String, String)>, AppError> {
+    let mut tasks: Vec<(NaiveDate, WorkItem)> = Vec::new();
+    for item_id in boards.get_stored_items() {
+        if WorkItem::check_if_exists(storage, &item_id)? {
+            let work_item: WorkItem = WorkItem::get(storage, &item_id)?;
+            if work_item.parent.is_none() {
+                tasks.push((work_item.added_on.to_naivedate()?, work_item));
+            }
+        }
+    }
+    tasks.sort_by_key(|x| x.0);
+
+    let mut id_sequence: IdSequence = IdSequence::new(&boards.id_sequence.prefix);
+    let mut work_items: Vec<(String, WorkItem)> = Vec::new();
+    let mut new_ids: HashMap<String, String> = HashMap::new();
+    for (_, mut task_item) in tasks {
+        id_sequence.last_task_number += 1;
+        let task_id: String = format!("{}-{}", id_sequence.prefix, id_sequence.last_task_number);
+        new_ids.insert(task_item.id.clone(), task_id.clone());
+        work_items.push((std::mem::replace(&mut task_item.id, task_id), task_item));
+    }
+
+    // Subtasks are numbered under the new ID of their parent, which is always numbered before them
+    let mut next_parent: usize = 0;
+    while next_parent < work_items.len() {
+        let (current_parent_id, parent_item): &(String, WorkItem) = &work_items[next_parent];
+        let parent_id: String = parent_item.id.clone();
+        let mut last_subtask_number: u64 = 0;
+        for subtask_id in hierarchy.children(current_parent_id) {
+            // Items already numbered are skipped, in case the parents form a cycle
+            if new_ids.contains_key(&subtask_id)
+                || !WorkItem::check_if_exists(storage, &subtask_id)?
+            {
+                continue;
+            }
+            last_subtask_number += 1;
+            let mut subtask_item: WorkItem = WorkItem::get(storage, &subtask_id)?;
+            subtask_item.id = format!("{}.{}", parent_id, last_subtask_number);
+            subtask_item.parent = Some(parent_id.clone());
+            new_ids.insert(subtask_id.clone(), subtask_item.id.clone());
+            work_items.push((subtask_id, subtask_item));
+        }
+        if last_subtask_number > 0 {
+            id_sequence
+                .last_subtask_numbers
+                .insert(parent_id, last_subtask_number);
+        }
+        next_parent += 1;
+    }
+
+    let mut changes: Vec<(String, String)> = new_ids
+        .iter()
+        .filter(|(current_id, new_id)| current_id != new_id)
+        .map(|(current_id, new_id)| (current_id.clone(), new_id.clone()))
+        .collect();
+    changes.sort_by_key(|x| id_sort_key(&x.1));
+    if check_only {
+        return Ok(changes);
+    }
+
+    // All items are read before any is written, as a new ID can be the current ID of another item
+    for (_, work_item) in &work_items {
+        work_item.save(storage)?;
+    }
+
+    let rekey = |item_id: &String| new_ids.get(item_id).unwrap_or(item_id).clone();
+    for swimlane in boards.swimlanes.iter_mut() {
+        swimlane.items = swimlane.items.iter().map(rekey).collect();
+    }
+    for hidden_item in boards.trash.iter_mut().chain(boards.archive.iter_mut()) {
+        hidden_item.item_id = rekey(&hidden_item.item_id);
+        hidden_item.hidden_with = hidden_item.hidden_with.as_ref().map(rekey);
+    }
+    boards.id_sequence = id_sequence;
+    boards.dependencies.rekey(rekey);
+    for timer in boards.timers.iter_mut() {
+        timer.item_id = rekey(&timer.item_id);
+    }
+    for sprint in boards.sprints.iter_mut() {
+        for list in [
+            &mut sprint.items,
+            &mut sprint.completed,
+            &mut sprint.carried_over,
+        ] {
+            *list = list.iter().map(rekey).collect();
+        }
+    }
+    boards.save(storage)?;
+
+    task_notes.notes = task_notes
+        .notes
+        .iter()
+        .map(|(item_id, notes_list)| (rekey(item_id), notes_list.clone()))
+        .collect();
+    task_notes.save(storage)?;
+
+    for (current_id, _) in &changes {
+        if work_items.iter().any(|x| x.1.id == *current_id) {
+            continue;
+        }
+        if WorkItem::check_if_exists(storage, current_id)? {
+            WorkItem::delete(storage, current_id)?;
+        }
+    }
+    Ok(changes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        constants::ITEMS_PATH,
+        storage::bincode_store::BincodeStorage,
+        utils::{create_dir, test_dir, TaskPriority, TimeStamp},
+    };
+
+    /// Open a bincode storage in a blank test directory with given name
+    fn test_storage(name: &str) -> BincodeStorage {
+        let board_dir: std::path::PathBuf = test_dir(name);
+        create_dir(&board_dir.join(ITEMS_PATH)).unwrap();
+        BincodeStorage::new(board_dir)
+    }
+
+    /// Store a new item with given ID, parent and creation date in the first swimlane of given
+    /// board
+    fn add_item(
+        storage: &dyn Storage,
+        boards: &mut KanbanBoard,
+        item_id: &str,
+        parent: Option<&str>,
+        added_on: &str,
+    ) {
+        let mut work_item: WorkItem = WorkItem::new(
+            item_id.to_string(),
+            item_id.to_string(),
+            String::new(),
+            None,
+            TaskPriority::Medium,
+            &boards.swimlanes[0],
+            parent.map(|x| x.to_string()),
+        )
+        .unwrap();
+        work_item.added_on =
+            TimeStamp::convert(NaiveDate::parse_from_str(added_on, "%Y-%m-%d").unwrap());
+        work_item.save(storage).unwrap();
+        boards.swimlanes[0].items.push(item_id.to_string());
+    }
+
+    #[test]
+    fn next_ids_skip_numbers_taken_by_stored_items() {
+        let storage: BincodeStorage = test_storage("ids_next");
+        let mut boards: KanbanBoard = KanbanBoard::new();
+        add_item(&storage, &mut boards, "TASK-2", None, "2026-01-01");
+        add_item(
+            &storage,
+            &mut boards,
+            "TASK-2.1",
+            Some("TASK-2"),
+            "2026-01-01",
+        );
+
+        let mut id_sequence: IdSequence = IdSequence::new("TASK");
+        assert_eq!(id_sequence.next_task_id(&storage).unwrap(), "TASK-1");
+        assert_eq!(id_sequence.next_task_id(&storage).unwrap(), "TASK-3");
+        assert_eq!(id_sequence.last_task_number, 3);
+        assert_eq!(
+            id_sequence.next_subtask_id(&storage, "TASK-2").unwrap(),
+            "TASK-2.2"
+        );
+        assert_eq!(
+            id_sequence.next_subtask_id(&storage, "TASK-2.2").unwrap(),
+            "TASK-2.2.1"
+        );
+        assert_eq!(
+            id_sequence.next_subtask_id(&storage, "TASK-1").unwrap(),
+            "TASK-1.1"
+        );
+    }
+
+    #[test]
+    fn id_prefixes_are_validated_and_uppercased() {
+        assert_eq!(validate_id_prefix("web2").unwrap(), "WEB2");
+        for prefix in ["", "WEB-1", "W B", "wéb"] {
+            assert!(
+                validate_id_prefix(prefix).is_err(),
+                "{} was accepted",
+                prefix
+            );
+        }
+    }
+
+    #[test]
+    fn ids_sort_numerically() {
+        let mut item_ids: Vec<&str> = vec!["TASK-10", "TASK-2.10", "TASK-2", "TASK-2.9", "TASK-1"];
+        item_ids.sort_by_key(|x| id_sort_key(x));
+        assert_eq!(
+            item_ids,
+            ["TASK-1", "TASK-2", "TASK-2.9", "TASK-2.10", "TASK-10"]
+        );
+    }
+
+    #[test]
+    fn rekey_numbers_tasks_by_creation_and_subtasks_under_their_parents() {
+        let storage: BincodeStorage = test_storage("ids_rekey");
+        let mut boards: KanbanBoard = KanbanBoard::new();
+        add_item(&storage, &mut boards, "TASK-2", None, "2026-02-01");
+        add_item(&storage, &mut boards, "TASK-5", None, "2026-01-01");
+        add_item(
+            &storage,
+            &mut boards,
+            "TASK-2.4",
+            Some("TASK-2"),
+            "2026-02-01",
+        );
+        add_item(
+            &storage,
+            &mut boards,
+            "TASK-2.4.7",
+            Some("TASK-2.4"),
+            "2026-02-01",
+        );
+        boards.dependencies.add("TASK-5", "TASK-2.4.7").unwrap();
+        boards.id_sequence.last_task_number = 5;
+        boards.save(&storage).unwrap();
+        let mut task_notes: TaskNotes = TaskNotes::new();
+        task_notes
+            .notes
+            .insert("TASK-2.4".to_string(), vec!["A note".to_string()]);
+        let hierarchy: Hierarchy = Hierarchy::load(&storage).unwrap();
+
+        let expected: Vec<(String, String)> = [
+            ("TASK-5", "TASK-1"),
+            ("TASK-2.4", "TASK-2.1"),
+            ("TASK-2.4.7", "TASK-2.1.1"),
+        ]
+        .iter()
+        .map(|x| (x.0.to_string(), x.1.to_string()))
+        .collect();
+        let checked: Vec<(String, String)> =
+            rekey_board(&storage, &mut boards, &hierarchy, &mut task_notes, true).unwrap();
+        assert_eq!(checked, expected);
+        assert!(WorkItem::check_if_exists(&storage, "TASK-5").unwrap());
+        assert_eq!(boards.id_sequence.last_task_number, 5);
+
+        let changes: Vec<(String, String)> =
+            rekey_board(&storage, &mut boards, &hierarchy, &mut task_notes, false).unwrap();
+        assert_eq!(changes, expected);
+        let mut stored: Vec<String> = storage.list_items().unwrap();
+        stored.sort_by_key(|x| id_sort_key(x));
+        assert_eq!(stored, ["TASK-1", "TASK-2", "TASK-2.1", "TASK-2.1.1"]);
+        assert_eq!(
+            WorkItem::get(&storage, "TASK-2.1.1").unwrap().parent,
+            Some("TASK-2.1".to_string())
+        );
+        assert_eq!(
+            boards.swimlanes[0].items,
+            ["TASK-2", "TASK-1", "TASK-2.1", "TASK-2.1.1"]
+        );
+        assert_eq!(boards.dependencies.blockers("TASK-1"), ["TASK-2.1.1"]);
+        assert_eq!(task_notes.notes["TASK-2.1"], ["A note"]);
+        assert_eq!(boards.id_sequence.last_task_number, 2);
+        assert_eq!(boards.id_sequence.last_subtask_numbers["TASK-2"], 1);
+        assert_eq!(boards.id_sequence.last_subtask_numbers["TASK-2.1"], 1);
+    }
+}
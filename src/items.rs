@@ -0,0 +1,452 @@
+//! Defines the structure for a work item (a task, or a subtask nested under another item at any
+//! depth) along with associated helper methods
+
+use crate::{
+    boards::{KanbanBoard, Shelf},
+    error::AppError,
+    estimates::{Estimate, EstimateTotals},
+    hierarchy::Hierarchy,
+    notes::TaskNotes,
+    output::{self, Record},
+    storage::Storage,
+    swimlanes::{LaneStage, Swimlane},
+    worklog::{self, WorkLog, WorkLogRecord},
+    TaskPriority, TimeStamp,
+};
+use chrono::prelude::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// Rust structure for a work item. Items without a parent are tasks, while items nested under
+/// another item (a task or a subtask) are subtasks.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WorkItem {
+    /// Task or SubTask ID
+    pub id: String,
+
+    /// Name of the item
+    pub name: String,
+
+    /// Description for the item
+    pub description: String,
+
+    /// Date when the item got created
+    pub added_on: TimeStamp,
+
+    /// Date when work started on the item
+    pub started_on: Option<TimeStamp>,
+
+    /// Date when the item is supposed to finish
+    pub deadline: Option<TimeStamp>,
+
+    /// Date when the item got completed
+    pub completed_on: Option<TimeStamp>,
+
+    /// Status of the item (slug of the swimlane it is in)
+    pub status: String,
+
+    /// Priority of the item
+    pub priority: TaskPriority,
+
+    /// Tags put on the item (lowercase labels, e.g. bug or tech-debt)
+    pub tags: Vec<String>,
+
+    /// Handle of the person the item is assigned to
+    pub assignee: Option<String>,
+
+    /// ID of the item this one is nested under (None for tasks)
+    pub parent: Option<String>,
+
+    /// Time logged against the item, in the order it was logged
+    pub worklog: Vec<WorkLog>,
+
+    /// Estimate of the item along with the effort remaining on it
+    pub estimate: Option<Estimate>,
+}
+
+/// Number of subtasks under an item (at every depth) along with how many of them are done.
+/// Archived subtasks count as done, while subtasks in the trash are left out.
+#[derive(Debug, Clone, Copy, Default, Serialize, Eq, PartialEq)]
+pub struct Progress {
+    /// Subtasks in a done swimlane or in the archive
+    pub done: usize,
+
+    /// All subtasks which are not in the trash
+    pub total: usize,
+}
+
+impl std::fmt::Display for Progress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{} children done", self.done, self.total)
+    }
+}
+
+/// All information for a task or subtask as shown by `open task` and `open subtask`
+#[derive(Debug, Clone, Serialize)]
+pub struct ItemDetails {
+    /// Task or SubTask ID
+    pub id: String,
+
+    /// Kind of item ("task" or "subtask")
+    pub kind: String,
+
+    /// Name of the item
+    pub name: String,
+
+    /// Description for the item
+    pub description: String,
+
+    /// Date when the item got created
+    #[serde(serialize_with = "output::serialize_date")]
+    pub added_on: NaiveDate,
+
+    /// Date when work started on the item
+    #[serde(serialize_with = "output::serialize_optional_date")]
+    pub started_on: Option<NaiveDate>,
+
+    /// Date when the item is supposed to finish
+    #[serde(serialize_with = "output::serialize_optional_date")]
+    pub deadline: Option<NaiveDate>,
+
+    /// Date when the item got completed
+    #[serde(serialize_with = "output::serialize_optional_date")]
+    pub completed_on: Option<NaiveDate>,
+
+    /// Status of the item (slug of the swimlane it is in)
+    pub status: String,
+
+    /// Name of the swimlane the item is in
+    #[serde(skip)]
+    pub status_name: String,
+
+    /// Priority of the item
+    #[serde(serialize_with = "output::serialize_lowercase")]
+    pub priority: TaskPriority,
+
+    /// Tags put on the item
+    pub tags: Vec<String>,
+
+    /// Handle of the person the item is assigned to
+    pub assignee: Option<String>,
+
+    /// Task and SubTask IDs the item depends on (is blocked by)
+    pub blocked_by: Vec<String>,
+
+    /// Task and SubTask IDs depending on (blocked by) the item
+    pub blocks: Vec<String>,
+
+    /// Chains of blockers leading to the item, along with where each blocker is
+    #[serde(skip)]
+    pub dependency_chain: Vec<String>,
+
+    /// ID of the item this one is nested under (None for tasks)
+    pub parent: Option<String>,
+
+    /// Subtasks directly under the item
+    pub subtasks: Vec<String>,
+
+    /// Progress of the subtasks under the item, rolled up from every depth
+    pub progress: Progress,
+
+    /// Subtasks under the item at every depth, indented by depth along with their status
+    #[serde(skip)]
+    pub subtree: Vec<String>,
+
+    /// Time logged against the item, in the order it was logged
+    pub worklog: Vec<WorkLogRecord>,
+
+    /// Time logged against the item, in minutes
+    pub minutes_logged: u32,
+
+    /// Time logged against the item and its subtasks (at every depth), in minutes
+    pub minutes_rolled_up: u32,
+
+    /// Estimate of the item along with the effort remaining on it
+    pub estimate: Option<Estimate>,
+
+    /// Estimates of the item and its subtasks (at every depth), added up per unit
+    pub estimate_rolled_up: EstimateTotals,
+
+    /// Notes added to the item
+    pub notes: Vec<String>,
+}
+
+impl Record for ItemDetails {
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.id.clone(),
+            self.name.clone(),
+            self.description.clone(),
+            output::display_date(&Some(self.added_on)),
+            output::display_date(&self.started_on),
+            output::display_date(&self.deadline),
+            output::display_date(&self.completed_on),
+            self.status_name.clone(),
+            self.priority.to_string(),
+            output::display_list(&self.tags),
+            self.assignee.clone().unwrap_or("None".to_string()),
+            match self.dependency_chain.is_empty() {
+                true => "None".to_string(),
+                false => self.dependency_chain.join("\n"),
+            },
+            output::display_list(&self.blocks),
+            self.parent.clone().unwrap_or("None".to_string()),
+            match self.progress.total {
+                0 => "None".to_string(),
+                _ => self.progress.to_string(),
+            },
+            match self.subtree.is_empty() {
+                true => "None".to_string(),
+                false => self.subtree.join("\n"),
+            },
+            match self.estimate.map(|x| x.to_string()) {
+                Some(s) if s == self.estimate_rolled_up.to_string() => s,
+                _ if self.estimate_rolled_up.is_empty() => "None".to_string(),
+                estimate => format!(
+                    "{}\n{} with subtasks",
+                    estimate.unwrap_or("None".to_string()),
+                    self.estimate_rolled_up
+                ),
+            },
+            match self.minutes_rolled_up == self.minutes_logged {
+                true => worklog::format_duration(self.minutes_logged),
+                false => format!(
+                    "{} ({} with subtasks)",
+                    worklog::format_duration(self.minutes_logged),
+                    worklog::format_duration(self.minutes_rolled_up)
+                ),
+            },
+            match self.worklog.is_empty() {
+                true => "None".to_string(),
+                false => self
+                    .worklog
+                    .iter()
+                    .map(|x| x.to_string())
+                    .collect::<Vec<String>>()
+                    .join("\n"),
+            },
+        ]
+    }
+}
+
+impl WorkItem {
+    /// Create new item with given ID (generated from the ID sequence of the board), nested under
+    /// given parent ID (None for a task)
+    pub fn new(
+        id: String,
+        name: String,
+        description: String,
+        deadline: Option<TimeStamp>,
+        priority: TaskPriority,
+        swimlane: &Swimlane,
+        parent: Option<String>,
+    ) -> Result<WorkItem, AppError> {
+        let work_item: WorkItem = WorkItem {
+            id,
+            name,
+            description,
+            added_on: TimeStamp::new(),
+            started_on: None,
+            deadline,
+            completed_on: None,
+            status: swimlane.slug.clone(),
+            priority,
+            tags: Vec::new(),
+            assignee: None,
+            parent,
+            worklog: Vec::new(),
+            estimate: None,
+        };
+        Ok(work_item)
+    }
+
+    /// Kind of item ("task" or "subtask")
+    pub fn kind(&self) -> &'static str {
+        match self.parent {
+            Some(_) => "subtask",
+            None => "task",
+        }
+    }
+
+    /// Fetch item information for given Task or SubTask ID
+    pub fn get(storage: &dyn Storage, item_id: &str) -> Result<Self, AppError> {
+        storage.get_item(item_id)
+    }
+
+    /// Fetch all information for given Task or SubTask ID, along with its subtasks and notes
+    pub fn get_details(
+        storage: &dyn Storage,
+        boards: &KanbanBoard,
+        hierarchy: &Hierarchy,
+        task_notes: &TaskNotes,
+        item_id: &str,
+    ) -> Result<ItemDetails, AppError> {
+        let work_item: WorkItem = WorkItem::get(storage, item_id)?;
+
+        let minutes_logged: u32 = worklog::total_minutes(&work_item.worklog);
+        let mut minutes_rolled_up: u32 = minutes_logged;
+        let mut estimate_rolled_up: EstimateTotals = EstimateTotals::default();
+        if let Some(estimate) = &work_item.estimate {
+            estimate_rolled_up.add(estimate);
+        }
+        let mut subtree: Vec<String> = Vec::new();
+        for (subtask_id, depth) in hierarchy.descendants(item_id) {
+            if let Some((Shelf::Trash, _)) = boards.find_hidden(&subtask_id) {
+                continue;
+            }
+            let subtask_item: WorkItem = WorkItem::get(storage, &subtask_id)?;
+            minutes_rolled_up =
+                minutes_rolled_up.saturating_add(worklog::total_minutes(&subtask_item.worklog));
+            if let Some(estimate) = &subtask_item.estimate {
+                estimate_rolled_up.add(estimate);
+            }
+            let mut line: String = format!(
+                "{}{} {} ({})",
+                "  ".repeat(depth - 1),
+                subtask_id,
+                subtask_item.name,
+                boards.swimlane_name(&subtask_item.status)
+            );
+            let progress: Progress = WorkItem::progress(storage, boards, hierarchy, &subtask_id)?;
+            if progress.total > 0 {
+                line.push_str(&format!(" - {}", progress));
+            }
+            subtree.push(line);
+        }
+
+        let mut worklog: Vec<WorkLogRecord> = Vec::new();
+        for work_log in &work_item.worklog {
+            worklog.push(WorkLogRecord::from_worklog(work_log)?);
+        }
+
+        Ok(ItemDetails {
+            kind: work_item.kind().to_string(),
+            status_name: boards.swimlane_name(&work_item.status),
+            added_on: work_item.added_on.to_naivedate()?,
+            started_on: TimeStamp::to_optional_naivedate(work_item.started_on)?,
+            deadline: TimeStamp::to_optional_naivedate(work_item.deadline)?,
+            completed_on: TimeStamp::to_optional_naivedate(work_item.completed_on)?,
+            id: work_item.id,
+            name: work_item.name,
+            description: work_item.description,
+            status: work_item.status,
+            priority: work_item.priority,
+            tags: work_item.tags,
+            assignee: work_item.assignee,
+            blocked_by: boards.dependencies().blockers(item_id),
+            blocks: boards.dependencies().dependents(item_id),
+            dependency_chain: boards.dependency_chains(item_id),
+            parent: work_item.parent,
+            subtasks: hierarchy.children(item_id),
+            progress: WorkItem::progress(storage, boards, hierarchy, item_id)?,
+            subtree,
+            worklog,
+            minutes_logged,
+            minutes_rolled_up,
+            estimate: work_item.estimate,
+            estimate_rolled_up,
+            notes: task_notes.get_notes(item_id.to_string()),
+        })
+    }
+
+    /// Count the subtasks under given Task or SubTask ID (at every depth) which are done
+    pub fn progress(
+        storage: &dyn Storage,
+        boards: &KanbanBoard,
+        hierarchy: &Hierarchy,
+        item_id: &str,
+    ) -> Result<Progress, AppError> {
+        let mut progress: Progress = Progress::default();
+        for (subtask_id, _) in hierarchy.descendants(item_id) {
+            let done: bool = match boards.find_hidden(&subtask_id) {
+                Some((Shelf::Trash, _)) => continue,
+                Some((Shelf::Archive, _)) => true,
+                None => {
+                    let status: String = WorkItem::get(storage, &subtask_id)?.status;
+                    matches!(boards.get_swimlane(&status), Ok(s) if s.stage == LaneStage::Done)
+                }
+            };
+            progress.total += 1;
+            if done {
+                progress.done += 1;
+            }
+        }
+        Ok(progress)
+    }
+
+    /// Add up the estimates of given item and its subtasks (at every depth) per unit, leaving out
+    /// subtasks in the trash
+    pub fn rolled_up_estimate(
+        storage: &dyn Storage,
+        boards: &KanbanBoard,
+        hierarchy: &Hierarchy,
+        work_item: &WorkItem,
+    ) -> Result<EstimateTotals, AppError> {
+        let mut totals: EstimateTotals = EstimateTotals::default();
+        if let Some(estimate) = &work_item.estimate {
+            totals.add(estimate);
+        }
+        for (subtask_id, _) in hierarchy.descendants(&work_item.id) {
+            if let Some((Shelf::Trash, _)) = boards.find_hidden(&subtask_id) {
+                continue;
+            }
+            if let Some(estimate) = &WorkItem::get(storage, &subtask_id)?.estimate {
+                totals.add(estimate);
+            }
+        }
+        Ok(totals)
+    }
+
+    /// Log time spent on given Task or SubTask ID, taking it off the remaining effort when the item
+    /// is estimated in time
+    pub fn log_work(
+        storage: &dyn Storage,
+        item_id: &str,
+        work_log: WorkLog,
+    ) -> Result<(), AppError> {
+        let mut work_item: WorkItem = WorkItem::get(storage, item_id)?;
+        if let Some(estimate) = work_item.estimate.as_mut() {
+            estimate.log_time(work_log.minutes);
+        }
+        work_item.worklog.push(work_log);
+        work_item.save(storage)
+    }
+
+    /// Move given Task or SubTask ID from one swimlane to another in Kanban Board
+    pub fn change_swimlane(
+        storage: &dyn Storage,
+        item_id: &str,
+        swimlane: &Swimlane,
+    ) -> Result<(), AppError> {
+        let mut work_item: WorkItem = WorkItem::get(storage, item_id)?;
+
+        if work_item.started_on.is_none() && (swimlane.stage != LaneStage::Pending) {
+            work_item.started_on = Some(TimeStamp::new());
+        }
+
+        if swimlane.stage == LaneStage::Done {
+            work_item.completed_on = Some(TimeStamp::new());
+            if let Some(estimate) = work_item.estimate.as_mut() {
+                estimate.remaining = 0;
+            }
+        }
+
+        work_item.status = swimlane.slug.clone();
+        work_item.save(storage)?;
+        Ok(())
+    }
+
+    /// Delete a given Task or SubTask ID
+    pub fn delete(storage: &dyn Storage, item_id: &str) -> Result<(), AppError> {
+        storage.delete_item(item_id)
+    }
+
+    /// Store the item information to storage
+    pub fn save(&self, storage: &dyn Storage) -> Result<(), AppError> {
+        storage.save_item(self)
+    }
+
+    /// Check if the item information is present in storage for given Task or SubTask ID
+    pub fn check_if_exists(storage: &dyn Storage, item_id: &str) -> Result<bool, AppError> {
+        storage.item_exists(item_id)
+    }
+}
@@ -0,0 +1,310 @@
+//! Defines the operation journal of a board, which records the changes made by every command so
+//! that they can be undone and redone (also across sessions)
+
+use crate::{
+    constants::{JOURNAL_FILE, JOURNAL_LIMIT, JOURNAL_SIZE_LIMIT},
+    error::AppError,
+    migrations::{self, RecordKind, SCHEMA_VERSION},
+    output::Record,
+    storage::{
+        unit_of_work::{changed_since, replay, Change, ChangeSet},
+        Storage,
+    },
+    utils::{get_board_dir, write_file_atomic},
+};
+use chrono::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Rust structure for a command recorded in the journal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    /// Command as it was entered
+    pub command: String,
+
+    /// Date and time the command was executed
+    pub recorded_on: String,
+
+    /// Changes made by the command along with the changes which undo them
+    pub change_set: ChangeSet,
+
+    /// Parts of the storage written by undoing the command, as read back once it was undone
+    /// (empty until it is undone)
+    pub undone: Vec<Change>,
+}
+
+/// Command recorded in the journal as listed by `history`
+#[derive(Debug, Clone, Serialize)]
+pub struct HistoryRecord {
+    /// Position of the command in the journal (starting at 1 for the oldest)
+    pub number: usize,
+
+    /// Date and time the command was executed
+    pub recorded_on: String,
+
+    /// Command as it was entered
+    pub command: String,
+
+    /// Task and SubTask IDs changed by the command
+    pub items: Vec<String>,
+
+    /// Whether the command is applied (false once undone)
+    pub applied: bool,
+}
+
+impl Record for HistoryRecord {
+    fn cells(&self) -> Vec<String> {
+        let state: &str = match self.applied {
+            true => "applied",
+            false => "undone",
+        };
+        vec![
+            self.number.to_string(),
+            self.recorded_on.clone(),
+            self.command.clone(),
+            self.items.join(", "),
+            state.to_string(),
+        ]
+    }
+}
+
+/// Rust structure for the operation journal of a board
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Journal {
+    /// Recorded commands, oldest first (at most JOURNAL_LIMIT)
+    entries: Vec<JournalEntry>,
+
+    /// Number of entries which are currently applied (the entries after it can be redone)
+    position: usize,
+
+    /// Board directory holding the journal file
+    #[serde(skip)]
+    board_dir: PathBuf,
+}
+
+impl Journal {
+    /// Load the journal of given board (blank if not present). A journal written with an older
+    /// schema version is discarded, as its snapshots can't be written back in the current format.
+    pub fn load(board_name: &str) -> Result<Self, AppError> {
+        Journal::load_from(get_board_dir(board_name)?)
+    }
+
+    /// Load the journal kept in given board directory (blank if not present)
+    pub fn load_from(board_dir: PathBuf) -> Result<Self, AppError> {
+        let journal_file: PathBuf = board_dir.join(JOURNAL_FILE);
+        let mut journal: Journal = Journal::default();
+        if journal_file.exists() {
+            let data: Vec<u8> = match std::fs::read(&journal_file) {
+                Ok(s) => s,
+                Err(e) => {
+                    return Err(AppError::FileReadError(format!(
+                        "{} - {}",
+                        journal_file.display(),
+                        e
+                    )))
+                }
+            };
+            if migrations::read_version(&data)? == SCHEMA_VERSION {
+                journal = migrations::deserialize(RecordKind::Journal, &data)?.0;
+            }
+        }
+        journal.board_dir = board_dir;
+        Ok(journal)
+    }
+
+    /// Read the journal again from the board directory it was loaded from
+    pub fn reload(&mut self) -> Result<(), AppError> {
+        *self = Journal::load_from(self.board_dir.clone())?;
+        Ok(())
+    }
+
+    /// Store the journal to a file in the board directory
+    pub fn save(&self) -> Result<(), AppError> {
+        let data: Vec<u8> = migrations::serialize(self)?;
+        write_file_atomic(&self.board_dir.join(JOURNAL_FILE), &data)
+    }
+
+    /// Store the journal, only warning when it can't be written: the changes it records are
+    /// already saved, so the command itself succeeded
+    fn save_or_warn(&self) {
+        if let Err(e) = self.save() {
+            eprintln!(
+                "Warning: The change was saved, but the journal could not be written - {}",
+                e
+            );
+        }
+    }
+
+    /// Record the changes made by given command. Any undone commands can no longer be redone.
+    /// The oldest commands are dropped beyond JOURNAL_LIMIT commands or JOURNAL_SIZE_LIMIT bytes.
+    pub fn record(&mut self, command: &str, change_set: ChangeSet) -> Result<(), AppError> {
+        if change_set.is_empty() {
+            return Ok(());
+        }
+
+        self.entries.truncate(self.position);
+        self.entries.push(JournalEntry {
+            command: command.to_string(),
+            recorded_on: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            change_set,
+            undone: Vec::new(),
+        });
+        if self.entries.len() > JOURNAL_LIMIT {
+            self.entries.drain(..self.entries.len() - JOURNAL_LIMIT);
+        }
+        let mut kept: usize = 0;
+        let mut size: u64 = 0;
+        for entry in self.entries.iter().rev() {
+            size = size.saturating_add(entry_size(entry)?);
+            if kept > 0 && size > JOURNAL_SIZE_LIMIT {
+                break;
+            }
+            kept += 1;
+        }
+        self.entries.drain(..self.entries.len() - kept);
+        self.position = self.entries.len();
+        self.save_or_warn();
+        Ok(())
+    }
+
+    /// Undo the most recent command which is applied. Returns the undone entry (None if there is
+    /// nothing to undo). Refuses when the data it wrote was changed since (e.g. by a git pull or
+    /// by hand), unless `force` is set.
+    pub fn undo(
+        &mut self,
+        storage: &dyn Storage,
+        force: bool,
+    ) -> Result<Option<JournalEntry>, AppError> {
+        if self.position == 0 {
+            return Ok(None);
+        }
+        let entry: &mut JournalEntry = &mut self.entries[self.position - 1];
+        if !force {
+            check_unchanged(storage, &entry.change_set.written, "undo", &entry.command)?;
+        }
+        entry.undone = replay(storage, &entry.change_set.inverses)?.written;
+        let entry: JournalEntry = entry.clone();
+        self.position -= 1;
+        self.save_or_warn();
+        Ok(Some(entry))
+    }
+
+    /// Redo the most recently undone command. Returns the redone entry (None if there is nothing
+    /// to redo). Refuses when the data written by undoing it was changed since, unless `force` is
+    /// set.
+    pub fn redo(
+        &mut self,
+        storage: &dyn Storage,
+        force: bool,
+    ) -> Result<Option<JournalEntry>, AppError> {
+        if self.position == self.entries.len() {
+            return Ok(None);
+        }
+        let entry: &mut JournalEntry = &mut self.entries[self.position];
+        if !force {
+            check_unchanged(storage, &entry.undone, "redo", &entry.command)?;
+        }
+        entry.change_set.written = replay(storage, &entry.change_set.changes)?.written;
+        let entry: JournalEntry = entry.clone();
+        self.position += 1;
+        self.save_or_warn();
+        Ok(Some(entry))
+    }
+
+    /// Fetch the records of all recorded commands, oldest first
+    pub fn history(&self) -> Vec<HistoryRecord> {
+        self.entries
+            .iter()
+            .enumerate()
+            .map(|(idx, entry)| HistoryRecord {
+                number: idx + 1,
+                recorded_on: entry.recorded_on.clone(),
+                command: entry.command.clone(),
+                items: entry.change_set.item_ids(),
+                applied: idx < self.position,
+            })
+            .collect()
+    }
+}
+
+/// Get the size in bytes given entry takes up in the journal file
+fn entry_size(entry: &JournalEntry) -> Result<u64, AppError> {
+    match bincode::serialized_size(entry) {
+        Ok(s) => Ok(s),
+        Err(e) => Err(AppError::BinarySerializationError(e.to_string())),
+    }
+}
+
+/// Check that the storage still holds what was written when given command was recorded, undone
+/// or redone, so that undo and redo never overwrite changes made outside the journal
+fn check_unchanged(
+    storage: &dyn Storage,
+    written: &[Change],
+    action: &str,
+    command: &str,
+) -> Result<(), AppError> {
+    let changed: Vec<String> = changed_since(storage, written)?;
+    match changed.is_empty() {
+        true => Ok(()),
+        false => Err(AppError::JournalConflict(format!(
+            "{} changed outside the journal. Pass --force to {} `{}` anyway.",
+            changed.join(", "),
+            action,
+            command
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{notes::TaskNotes, utils::test_dir};
+    use std::collections::HashMap;
+
+    /// Change set saving notes of about given size in bytes
+    fn notes_change_set(size: usize) -> ChangeSet {
+        let task_notes: TaskNotes = TaskNotes {
+            notes: HashMap::from([("TASK-1".to_string(), vec!["x".repeat(size)])]),
+        };
+        ChangeSet {
+            changes: vec![Change::SaveNotes(task_notes.clone())],
+            inverses: vec![Change::SaveNotes(TaskNotes::new())],
+            written: vec![Change::SaveNotes(task_notes)],
+        }
+    }
+
+    #[test]
+    fn oldest_commands_are_dropped_beyond_size_limit() {
+        let mut journal: Journal = Journal {
+            board_dir: test_dir("journal_size_limit"),
+            ..Journal::default()
+        };
+        let note_size: usize = (JOURNAL_SIZE_LIMIT / 5) as usize;
+        for idx in 0..5 {
+            journal
+                .record(&format!("note {}", idx), notes_change_set(note_size))
+                .unwrap();
+        }
+        let commands: Vec<String> = journal.history().into_iter().map(|x| x.command).collect();
+        assert_eq!(commands, ["note 3", "note 4"]);
+        assert_eq!(journal.position, 2);
+
+        // A single command beyond the limit is still kept, so that it can be undone
+        journal
+            .record("note big", notes_change_set(JOURNAL_SIZE_LIMIT as usize))
+            .unwrap();
+        let commands: Vec<String> = journal.history().into_iter().map(|x| x.command).collect();
+        assert_eq!(commands, ["note big"]);
+    }
+
+    #[test]
+    fn command_is_recorded_when_journal_file_cannot_be_written() {
+        let mut journal: Journal = Journal {
+            board_dir: test_dir("journal_write_failure").join("missing"),
+            ..Journal::default()
+        };
+        journal.record("note", notes_change_set(10)).unwrap();
+        assert!(!journal.board_dir.join(JOURNAL_FILE).exists());
+        assert_eq!(journal.history().len(), 1);
+    }
+}
@@ -0,0 +1,318 @@
+//! # Rustic Boards
+//!
+//! Rustic Boards is a sleek and powerful CLI-based Kanban board application built with Rust,
+//! combining performance and usability for seamless task management.
+//! It simplifies task tracking and collaboration with an intuitive command line interface and robust features.
+//!
+//! Rustic Boards emerges from the philosophy that performance should not be sacrificed for usability.
+//! Built on the robust foundations of Rust, known for its speed, safety, and memory efficiency,
+//! Rustic Boards offers a powerful CLI-based Kanban board experience like no other.
+//!
+//! ## Design
+//!
+//! 1. Clear and intuitive CLI command structure
+//! 2. Pluggable storage for the Kanban board data (simple binary files, plain-text files or an embedded SQLite database)
+//! 3. Organize tasks into user-defined swimlanes for each board ("To Do", "In Progress", "Blocked", "In Review", "Done" by default) to represent the workflow stages
+//! 4. Sequential IDs per board (e.g. WEB-42 for tasks, WEB-42.3 for its subtasks, WEB-42.3.1 for theirs) which never collide
+//! 5. Nest subtasks under tasks and other subtasks to any depth, with progress rolled up to every parent
+//! 6. Track time spent on tasks and subtasks with timers and worklogs, totalled per parent, swimlane, day and week
+//! 7. Estimate tasks and subtasks in story points or time, with the remaining effort rolled up to every parent
+//! 8. Plan work in time-boxed sprints with a goal, closing each with a report of completed and carried-over work
+//! 9. Robust input validation to handle unexpected user inputs gracefully
+//! 10. Provide clear and helpful error messages to guide users when mistakes or issues occur
+//!
+//! ## CLI Commands
+//!
+//! | Command | Description |
+//! | ------- | ----------- |
+//! | `add task` | To add a new task into board (along with subtasks - optional) |
+//! | `add subtask` | To add a new subtask into board under a parent task or subtask |
+//! | `edit task <Task ID>` | To modify details for a task or to create new subtasks under a task <br> (Note: only task description, priority, deadline can be modified) |
+//! | `edit subtask <SubTask ID>` | To modify details for a subtask or to create new subtasks under it <br> (Note: only subtask description, priority, deadline and parent can be modified) |
+//! | `open task <Task ID>` | To view all details for a task (including its worklog) |
+//! | `open subtask <SubTask ID>` | To view all details for a subtask (including its worklog) |
+//! | `delete task <Task ID>` | To move a task to the trash (along with its subtasks) |
+//! | `delete subtask <SubTask ID>` | To move a subtask to the trash along with its own subtasks (This won't have any impact on the parent) |
+//! | `trash list` | To view the tasks and subtasks in the trash |
+//! | `restore <ID>` | To restore a task (along with the subtasks deleted with it) or a subtask from the trash |
+//! | `trash empty` | To delete the tasks and subtasks in the trash for good |
+//! | `archive task <Task ID>` | To archive a task in a done swimlane (along with its subtasks) |
+//! | `archive done` | To archive all tasks in done swimlanes |
+//! | `archive list` | To view the archived tasks and subtasks |
+//! | `archive restore <Task ID>` | To put an archived task back on the board (along with its subtasks) |
+//! | `move task <Task ID> <Swimlane>` | To move a task across different swimlanes on board <br> (`--force` to move beyond a strict WIP limit or unfinished blockers) |
+//! | `move subtask <SubTask ID> <Swimlane>` | To move a subtask across different swimlanes on board <br> (`--force` to move beyond a strict WIP limit or unfinished blockers) |
+//! | `link subtask <SubTask ID>` | To move a subtask under a different parent task or subtask |
+//! | `show task <Swimlane>` | To view all tasks in given swimlane along with their story points <br> (slug of a swimlane from `lane list`, or all) |
+//! | `show subtask <Swimlane>` | To view all subtasks in given swimlane <br> (slug of a swimlane from `lane list`, or all) |
+//! | `add notes <Task or SubTask ID>` | To add notes to an existing task or subtask |
+//! | `show notes <Task or SubTask ID>` | To view notes for an existing task or subtask |
+//! | `filter due <Keyword>` | To filter all tasks and subtasks based on deadline <br> (past-deadline, today, tomorrow, after-tomorrow) |
+//! | `filter priority <Keyword>` | To filter all tasks and subtasks based on priority <br> (high, medium, low) |
+//! | `filter tag <Tag>` | To filter all tasks and subtasks with given tag |
+//! | `filter assignee <Handle\|none>` | To filter all tasks and subtasks assigned to given person <br> (none for unassigned ones) |
+//! | `filter estimate <Unit\|none>` | To filter all tasks and subtasks estimated in given unit, counting their subtasks <br> (points, time, none for unestimated ones) |
+//! | `mine` | To view all tasks and subtasks assigned to the current user <br> (handle set in `RUSTIC_BOARDS_USER`) |
+//! | `user add <Handle>` | To register a person on the board who tasks can be assigned to <br> (`--name` for their display name, `--email` for their email address) |
+//! | `user list` | To view all people on the board along with the number of tasks and subtasks assigned to them |
+//! | `user remove <Handle>` | To remove a person from the board (once nothing is assigned to them, including in the trash and the archive) |
+//! | `assign <Task or SubTask ID> <Handle\|none>` | To assign a task or subtask to a person on the board <br> (none to unassign it) |
+//! | `estimate <Task or SubTask ID> <Estimate\|none>` | To estimate a task or subtask in story points or time, resetting its remaining effort <br> (e.g. `5`, `5pt`, `1h30m`, none to remove the estimate) |
+//! | `remaining <Task or SubTask ID> <Estimate>` | To set the effort remaining on an estimated task or subtask, in the unit of its estimate <br> (time logged and moves into a done swimlane also update it) |
+//! | `depends <Task or SubTask ID> on <Task or SubTask ID>` | To record that a task or subtask is blocked by another one until it is done |
+//! | `undepend <Task or SubTask ID> on <Task or SubTask ID>` | To remove the dependency of a task or subtask on another one |
+//! | `timer start <Task or SubTask ID>` | To start tracking time on a task or subtask for the current user <br> (one timer per person, handle set in `RUSTIC_BOARDS_USER`) |
+//! | `timer stop [Message]` | To stop the timer of the current user and log the time elapsed on its task or subtask |
+//! | `timer list` | To view the timers running on the board |
+//! | `log <Task or SubTask ID> <Duration> [Message]` | To log time spent today on a task or subtask (e.g. `1h30m`, `2h` or `45m`) |
+//! | `timesheet` | To view the time logged on each day across the board <br> (`--week` for the current week per task and subtask) |
+//! | `sprint create` | To add a new sprint with a goal, two weeks long by default <br> (`--goal` for its goal, `--start` and `--end` for its first and last day) |
+//! | `sprint list` | To view all sprints along with their status, tasks completed and estimates |
+//! | `sprint plan <Task ID>...` | To pull tasks from the backlog into the current sprint <br> (`--sprint` to plan them into another open sprint) |
+//! | `sprint unplan <Task ID>` | To take a task out of its sprint and put it back in the backlog |
+//! | `sprint show [Sprint]` | To view the tasks of the current sprint (or given sprint) across the swimlanes along with their story points |
+//! | `sprint close` | To close the current sprint, reporting completed and carried-over work <br> (unfinished tasks move to the next open sprint, `--backlog` to put them back in the backlog) |
+//! | `tag add <Task or SubTask ID> <Tag>` | To put a tag on a task or subtask (letters, digits, `-` and `_`, e.g. bug or tech-debt) <br> (`--color` to show the tag in a color) |
+//! | `tag remove <Task or SubTask ID> <Tag>` | To take a tag off a task or subtask |
+//! | `tag list` | To view all tags on the board along with their colors and number of tasks and subtasks |
+//! | `tag color <Tag> <Color\|none>` | To set the color of a tag <br> (red, green, yellow, blue, magenta, cyan, none to remove the color) |
+//! | `tag rename <Tag> <New Tag>` | To rename a tag on all tasks and subtasks of the board (including the trash and the archive) |
+//! | `tag merge <Tag> <Into Tag>` | To merge a tag into another tag on all tasks and subtasks of the board (including the trash and the archive) |
+//! | `storage show` | To view the storage backend holding the board data |
+//! | `storage use <Backend>` | To copy the board data to another storage backend and switch to it <br> (bincode, sqlite, text) |
+//! | `lane list` | To view all swimlanes on the board along with their slugs, stages and time logged |
+//! | `lane add <Name>` | To add a new swimlane at the end of the board (slug is derived from the name) <br> (stage: pending, started, done, `--blocked` to hold blocked tasks) |
+//! | `lane rename <Swimlane> <New Name>` | To rename a swimlane (tasks and subtasks in it are moved along) |
+//! | `lane reorder <Swimlane> <Position>` | To move a swimlane to another position on the board (starting at 1) |
+//! | `lane mark <Swimlane> <Stage>` | To change which stage of work a swimlane represents <br> (pending, started, done) |
+//! | `lane block <Swimlane>` | To have a swimlane hold the tasks and subtasks waiting on their blockers |
+//! | `lane unblock <Swimlane>` | To stop a swimlane from holding the tasks and subtasks waiting on their blockers |
+//! | `lane limit <Swimlane> <N\|none>` | To set the WIP limit of a swimlane, refusing moves beyond it <br> (`--soft` to only warn, none to remove the limit) |
+//! | `lane remove <Swimlane>` | To remove a swimlane (tasks and subtasks in it are moved to the swimlane passed with `--into`) |
+//! | `board create <Board>` | To create a new named board <br> (`--prefix` to set the prefix of its Task IDs, TASK by default) |
+//! | `board list` | To view all boards (the active board is marked with *) |
+//! | `board use <Board>` | To switch to another board |
+//! | `board delete <Board>` | To delete a board along with all its tasks, subtasks and notes |
+//! | `board rename <Board> <New Name>` | To rename a board |
+//! | `board prefix <Prefix>` | To set the prefix of new Task IDs on the active board (e.g. WEB for WEB-42) |
+//! | `board rekey` | To re-key all tasks and subtasks on the active board to sequential IDs <br> (`--check` to only report what would change) |
+//! | `init` | To create a project board in the current directory, used instead of the global board anywhere inside it |
+//! | `undo` | To undo the most recent change to the active board (also from an earlier session) <br> (`--force` to overwrite changes made since outside the journal) |
+//! | `redo` | To redo the most recently undone change to the active board <br> (`--force` to overwrite changes made since outside the journal) |
+//! | `history` | To view the recent changes to the active board which can be undone or redone |
+//! | `fsck` | To check the active board for orphans, duplicates, status mismatches, missing parents, parent cycles, stray notes, unknown assignees and broken dependencies <br> (`--repair` to fix them after a preview) |
+//! | `migrate` | To upgrade all stored data to the current schema version <br> (`--check` to only report what would change) |
+//! | `tui` | To open the board in a full-screen terminal UI <br> (move cards with Shift+arrow keys, Enter for details, `e` to edit) |
+//! | `help` | To view all commands for the application |
+//! | `exit` | To exit the application |
+//!
+//! Dependencies cannot form a cycle. A task or subtask with unfinished blockers can only be moved
+//! into a pending swimlane or a blocked swimlane (`blocked` by default, set with `lane block`),
+//! unless `--force` is passed. Once its last blocker is moved into a done swimlane, an item waiting
+//! in a blocked swimlane is moved into the next started swimlane (`in-progress` by default).
+//! `open task` shows the chains of blockers along with where each of them is.
+//!
+//! Subtasks can be nested under tasks and other subtasks to any depth (e.g. epics, stories and
+//! their steps). `open task` and `open subtask` show the whole subtree below an item, along with
+//! how many of its subtasks at every depth are done (e.g. "3/5 children done"); archived subtasks
+//! count as done and those in the trash are left out. Deleting or archiving an item takes its
+//! subtasks at every depth along, and a subtask cannot be moved under itself or one of its own
+//! subtasks.
+//!
+//! ## One-shot Mode
+//!
+//! Running `rustic_boards` without arguments starts the `boards (<Board>)>` prompt for the active
+//! board. Any command can also be passed directly as arguments (e.g.
+//! `rustic_boards move task TASK-12 done`), in which case it is executed once without any
+//! interactive prompts and the process exits with a status code (`0` on success, `1` if the
+//! command failed, `2` if the command or flags were invalid). Arguments at the prompt are quoted
+//! the same way as in a shell, so a command is written the same way in both modes.
+//!
+//! The read commands print tables by default. Pass `--format json` (a single JSON document),
+//! `--format ndjson` (one JSON object per line) or `--format csv` to read the board from scripts
+//! instead (e.g. `rustic_boards show task all --format json | jq '.[].id'`). Dates are given in
+//! `yyyy-mm-dd` format, priorities as the keywords passed with `--priority` and swimlanes by their
+//! slugs.
+//!
+//! | Flag | Description |
+//! | ---- | ----------- |
+//! | `--name <Name>` | Name for a new task or subtask, or display name for a new person (required for `add task`, `add subtask` and `user add`) |
+//! | `--email <Email>` | Email address for a new person (optional for `user add`) |
+//! | `--description <Text>` | Description for a task or subtask |
+//! | `--priority <Keyword>` | Priority for a task or subtask (high, medium, low - defaults to medium) |
+//! | `--deadline <dd/mm/yyyy>` | Deadline for a task or subtask (none to remove the deadline) |
+//! | `--note <Text>` | Note to add to a task or subtask (can be repeated) |
+//! | `--parent <Task or SubTask ID>` | Parent to nest a subtask under (required for `add subtask` and `link subtask`) |
+//! | `--stage <Stage>` | Stage of work for a new swimlane (pending, started, done - required for `lane add`) |
+//! | `--into <Swimlane>` | Swimlane to move tasks and subtasks into when removing a swimlane |
+//! | `--prefix <Prefix>` | Prefix of the Task IDs on a new board (letters and digits) |
+//! | `--color <Color>` | Color to show a tag in with `tag add` (red, green, yellow, blue, magenta, cyan) |
+//! | `--goal <Text>` | Goal for a new sprint (required for `sprint create`) |
+//! | `--start <dd/mm/yyyy>` | First day of a new sprint (the day after the last sprint ends, or today, by default) |
+//! | `--end <dd/mm/yyyy>` | Last day of a new sprint (two weeks after its start by default) |
+//! | `--sprint <Sprint>` | Number of the open sprint to plan tasks into with `sprint plan` (the current sprint by default) |
+//! | `--format <Format>` | Output format for `show task`, `show subtask`, `show notes`, `open task`, `open subtask`, `filter due`, `filter priority`, `filter tag`, `filter assignee`, `filter estimate`, `mine`, `user list`, `tag list`, `timer list`, `timesheet`, `sprint list`, `sprint show`, `sprint close`, `trash list`, `archive list`, `lane list`, `board list`, `history` and `fsck` (table, json, ndjson, csv - defaults to table) |
+//! | `--check` | Only report what `migrate` or `board rekey` would change without changing it |
+//! | `--force` | Move a task or subtask beyond a strict WIP limit or unfinished blockers (recorded as a note on it), or undo or redo over changes made outside the journal |
+//! | `--repair` | Apply the repairs previewed by `fsck` (after confirming in the `boards>` session) |
+//! | `--soft` | Only warn instead of refusing moves beyond the WIP limit set with `lane limit` |
+//! | `--blocked` | Have the swimlane added with `lane add` hold the tasks and subtasks waiting on their blockers |
+//! | `--backlog` | Put the unfinished tasks of the sprint closed with `sprint close` back in the backlog instead of the next sprint |
+//! | `--week` | Show the timesheet of the current week per task and subtask with `timesheet` |
+//! | `--data-dir <Dir>` | Directory holding all application data (also for the `boards>` prompt, overrides `RUSTIC_BOARDS_HOME`) |
+//! | `--global` | Use the global board even inside a project with its own board (also for the `boards>` prompt) |
+//!
+//! ## Terminal UI
+//!
+//! `rustic_boards tui` (or `tui` at the `boards>` prompt) draws all swimlanes of the active board
+//! side by side as columns of cards, each showing the ID, name, priority, deadline, assignee and
+//! tags of a task.
+//!
+//! | Key | Action |
+//! | --- | ------ |
+//! | `←` `→` / `h` `l` | Select the previous or next swimlane |
+//! | `↑` `↓` / `k` `j` | Select the previous or next card |
+//! | `Shift+←` `Shift+→` / `H` `L` | Move the selected card into the previous or next swimlane |
+//! | `Enter` | Open the detail pane of the selected card (all its fields, subtasks and notes) |
+//! | `e` | Edit the description, priority and deadline of the selected card and add a note (`Enter` saves, `Esc` cancels) |
+//! | `Tab` | Switch between task and subtask cards |
+//! | `u` / `r` | Undo or redo the most recent change |
+//! | `q` / `Esc` | Leave the terminal UI |
+//!
+//! Changes are saved and recorded in the journal the same way as the commands above, and WIP
+//! limits are enforced as for `move task`.
+//!
+//! ## Data Directory
+//!
+//! All boards are kept in `%USERPROFILE%\.rustic_boards` on Windows and in the platform data
+//! directory elsewhere (`$XDG_DATA_HOME/rustic_boards`, by default `~/.local/share/rustic_boards`,
+//! on Linux). Boards kept by older versions in a `<home>\.rustic_boards` directory next to the home
+//! directory are moved there on the first run (unless the data directory already exists, in which
+//! case the command stops so that the two sets of boards can be merged by hand). Set the
+//! `RUSTIC_BOARDS_HOME` environment variable or pass `--data-dir <Dir>` to keep the data in another
+//! directory (e.g. for separate profiles or isolated test runs). Every file is written to a
+//! temporary file first and then renamed into place, so a crash never leaves a half-written file
+//! behind. Commands which change several items at once (e.g. deleting a task along with its
+//! subtasks) stage all their changes and write them together (in a single transaction with the
+//! SQLite backend), rolling back what was already written if any write fails. Every change to a
+//! board is also recorded in a `journal.bin` file in the board directory (the last 100 per board,
+//! as far as they fit in 2 MB), which `undo`, `redo` and `history` work from. A change is kept even
+//! if the journal can't be written, with a warning. `undo` and `redo` refuse to overwrite tasks,
+//! subtasks, notes or the board when they were changed since outside the journal (e.g. by a
+//! `git pull` or by hand), unless `--force` is passed. Creating, switching, renaming and deleting
+//! boards and changing the storage backend are not recorded. Deleted tasks and subtasks are moved
+//! to the trash with their parents and notes intact, so they can be restored until the trash is
+//! emptied. Archived tasks are kept the same way, but only hidden from the done swimlanes. Commands
+//! lock the data directory while they run (through a `rustic_boards.lock` file), so several
+//! terminals can work on the same boards: a `boards>` session picks up changes made by other
+//! processes before its next command, and a command waits while another process holds the lock.
+//! Commands which only read the data leave the lock file as it is.
+//!
+//! Running `rustic_boards init` creates a project board in a `.rustic_boards` directory in the
+//! current directory, along with a `.gitignore` keeping the lock file, the journals and the
+//! `config.bin` configuration (active board and storage backend) out of git; a fresh clone
+//! rebuilds the configuration from the board directories. Like git looks up `.git`, the
+//! application searches upward from the current directory for a `.rustic_boards` directory and uses
+//! that project board before falling back to the global one. Pass `--global` to use the global
+//! board from inside a project.
+//!
+//! Project boards are best kept with the `text` storage backend (`rustic_boards storage use text`),
+//! which keeps one Markdown file per task and subtask under `.items` with the fields as TOML
+//! front-matter and the description as the body, along with a `board.toml` index holding the
+//! swimlanes. Items in the index are sorted by ID, so two branches which each add tasks merge
+//! cleanly. The last used ID numbers are stored in the index and checked against the existing
+//! files, so a number is never handed out twice; conflicts on them between two merged branches are
+//! resolved by keeping the higher number. When two merged branches both added an item under the
+//! same ID, the item file is left with git conflict markers; the next command splits it, keeping
+//! the ID for the version of the current branch and giving the version of the merged branch the
+//! next free ID.
+//!
+//! Everyone working on a shared board registers with `user add <Handle>` (kept in the board index
+//! along with the swimlanes) and sets the `RUSTIC_BOARDS_USER` environment variable to their
+//! handle, so that `mine` lists the tasks and subtasks assigned to them with `assign`.
+//!
+//! Time spent on a task or subtask is logged with `log <ID> 1h30m "message"`, or tracked with
+//! `timer start <ID>` and `timer stop`, which logs the time elapsed on the day the timer was
+//! started. Each person (set in `RUSTIC_BOARDS_USER`) can have one timer running per board, and
+//! worklog entries record who logged them. `open task` shows the worklog of an item along with the
+//! time logged on it and its subtasks, `lane list` totals the time per swimlane and `timesheet`
+//! totals it per day (or per item and weekday with `--week`).
+//!
+//! Tasks and subtasks are estimated with `estimate <ID> 5` (story points, also `5pt`) or
+//! `estimate <ID> 4h` (time), and the effort left on them is set with `remaining <ID> <Estimate>`.
+//! Time logged against an item estimated in time is taken off its remaining effort, and moving an
+//! item into a done swimlane leaves no effort remaining. Estimates of subtasks are rolled up to
+//! their parents, `show task` shows the story points in each swimlane and `filter estimate none`
+//! lists the open items which still need an estimate.
+//!
+//! Work is planned in sprints: `sprint create --goal <Text>` adds a sprint running two weeks from
+//! the end of the previous one (or `--start` and `--end` for other days), and
+//! `sprint plan <Task ID>...` pulls tasks from the backlog into the current sprint. `sprint show`
+//! lists only the tasks of the sprint across the swimlanes, with the story points in each
+//! swimlane. `sprint close` reports the tasks completed and carried over, moving the unfinished
+//! ones into the next open sprint (or back to the backlog when there is none or `--backlog` is
+//! passed).
+//!
+//! ## Library
+//!
+//! The application is built on the `rustic_boards` library, which can also be embedded in other
+//! tools instead of running the binary. A [`Workspace`] holds all boards in the data directory
+//! (creating, switching, renaming and deleting them), and a [`Board`] runs every operation on a
+//! single board against an explicit storage handle, returning the data instead of printing it.
+//! Changes made through a [`Board`] are recorded in its journal, so they can be undone from the
+//! command line as well. Hold a [`lock::DataLock`] while working on the data when other processes
+//! may use it at the same time.
+//!
+//! ```no_run
+//! use rustic_boards::{Board, TaskPriority, Workspace};
+//! use rustic_boards::workspace::NewItem;
+//!
+//! let workspace: Workspace = Workspace::open()?;
+//! let mut board: Board = workspace.open_active_board()?;
+//! let (task_id, _) = board.add_task(
+//!     NewItem {
+//!         name: "Release notes".to_string(),
+//!         description: String::new(),
+//!         priority: TaskPriority::High,
+//!         deadline: None,
+//!         notes: Vec::new(),
+//!     },
+//!     Vec::new(),
+//! )?;
+//! board.move_task(&task_id, "in-progress", false)?;
+//! for section in board.list_tasks("all")? {
+//!     println!("{}: {} tasks", section.heading, section.records.len());
+//! }
+//! # Ok::<(), rustic_boards::error::AppError>(())
+//! ```
+//!
+//! ## Code Repository
+//!
+//! Visit <https://github.com/oss-rust-github-io/rustic_boards> for application source code.
+
+pub mod boards;
+pub mod config;
+pub mod constants;
+pub mod dependencies;
+pub mod error;
+pub mod estimates;
+pub mod fsck;
+pub mod hierarchy;
+pub mod ids;
+pub mod items;
+pub mod journal;
+pub mod lock;
+pub mod migrations;
+pub mod notes;
+pub mod output;
+pub mod people;
+pub mod sprints;
+pub mod storage;
+pub mod swimlanes;
+pub mod tags;
+pub mod utils;
+pub mod worklog;
+pub mod workspace;
+
+pub use utils::{TaskPriority, TimeStamp};
+pub use workspace::{Board, Workspace};
@@ -0,0 +1,92 @@
+//! Defines the advisory lock on the data directory, which keeps commands from several terminals
+//! from overwriting each other's changes
+
+use crate::{
+    constants::LOCK_FILE,
+    error::AppError,
+    utils::{create_app_dirs, write_count},
+};
+use std::{
+    fs::{File, OpenOptions, TryLockError},
+    io::{Read, Seek, SeekFrom, Write},
+    path::PathBuf,
+};
+
+/// Rust structure for the lock on the data directory. The lock file also holds a revision number
+/// which is raised after every command that changed the data, so that a long-running session can
+/// tell that another process changed it since it was last read. The lock is released when dropped.
+pub struct DataLock {
+    /// Open lock file (locked exclusively)
+    file: File,
+
+    /// Revision of the data when the lock was taken
+    revision: u64,
+
+    /// Number of changes this process had made to the data directory when the revision was last
+    /// read or raised
+    writes: u64,
+}
+
+impl DataLock {
+    /// Lock the data directory, waiting for any other process which holds the lock
+    pub fn acquire() -> Result<Self, AppError> {
+        let lock_path: PathBuf = create_app_dirs()?.join(LOCK_FILE);
+        let lock_error =
+            |e: std::io::Error| AppError::LockError(format!("{} - {}", lock_path.display(), e));
+
+        let mut file: File = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&lock_path)
+            .map_err(lock_error)?;
+        match file.try_lock() {
+            Ok(_) => {}
+            Err(TryLockError::WouldBlock) => {
+                eprintln!("Waiting for another rustic_boards process to finish...");
+                file.lock().map_err(lock_error)?;
+            }
+            Err(TryLockError::Error(e)) => return Err(lock_error(e)),
+        }
+
+        let mut contents: String = String::new();
+        file.read_to_string(&mut contents).map_err(lock_error)?;
+        let revision: u64 = contents.trim().parse::<u64>().unwrap_or(0);
+        Ok(DataLock {
+            file,
+            revision,
+            writes: write_count(),
+        })
+    }
+
+    /// Revision of the data when the lock was taken
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// Raise the revision if the data changed while the lock was held, so that other sessions
+    /// reload it before their next command. Commands which only read the data leave it as is.
+    pub fn bump_revision(&mut self) -> Result<(), AppError> {
+        if write_count() == self.writes {
+            return Ok(());
+        }
+        self.writes = write_count();
+        self.revision += 1;
+        let result: std::io::Result<()> = self
+            .file
+            .set_len(0)
+            .and_then(|_| self.file.seek(SeekFrom::Start(0)))
+            .and_then(|_| write!(self.file, "{}", self.revision));
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(AppError::LockError(format!("{} - {}", LOCK_FILE, e))),
+        }
+    }
+}
+
+impl Drop for DataLock {
+    fn drop(&mut self) {
+        let _ = self.file.unlock();
+    }
+}
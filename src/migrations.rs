@@ -0,0 +1,780 @@
+//! Defines the versioned on-disk format along with the migration chain which upgrades files
+//! written by older versions of the application
+
+use crate::{
+    boards::KanbanBoard,
+    constants::{
+        ACTIVE_SUBTASKS_PATH, ACTIVE_TASKS_PATH, DEFAULT_BOARD, KANBAN_BOARD_FILE, NOTES_FILE,
+        SQLITE_DB_FILE, TAKS_LINK_FILE,
+    },
+    error::AppError,
+    utils::{create_board_dirs, create_dir, record_write},
+    TimeStamp,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+/// Magic bytes at the start of every file written in the versioned format
+const FORMAT_MAGIC: &[u8; 4] = b"RBRD";
+
+/// Length of the format header (magic bytes followed by the schema version)
+const HEADER_LEN: usize = 8;
+
+/// Current schema version of all persisted files
+pub const SCHEMA_VERSION: u32 = 14;
+
+/// Schema version which started marking the swimlanes holding blocked tasks and subtasks
+pub const BLOCKED_SWIMLANES_VERSION: u32 = 14;
+
+/// Slug of the swimlane which held blocked tasks and subtasks before swimlanes were marked
+pub const LEGACY_BLOCKED_SWIMLANE: &str = "blocked";
+
+/// Kinds of records persisted by the application
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum RecordKind {
+    Config,
+    Board,
+    Links,
+    Notes,
+    Task,
+    SubTask,
+    Item,
+    Journal,
+}
+
+impl std::fmt::Display for RecordKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RecordKind::Config => write!(f, "config"),
+            RecordKind::Board => write!(f, "board"),
+            RecordKind::Links => write!(f, "links"),
+            RecordKind::Notes => write!(f, "notes"),
+            RecordKind::Task => write!(f, "task"),
+            RecordKind::SubTask => write!(f, "subtask"),
+            RecordKind::Item => write!(f, "item"),
+            RecordKind::Journal => write!(f, "journal"),
+        }
+    }
+}
+
+/// A single step in the migration chain, upgrading a record to `version`
+pub struct Migration {
+    /// Schema version produced by this migration
+    pub version: u32,
+
+    /// Description of what the migration changes
+    pub description: &'static str,
+
+    /// Upgrade the record payload from the previous schema version
+    pub apply: fn(RecordKind, Vec<u8>) -> Result<Vec<u8>, AppError>,
+}
+
+/// Migration chain in ascending order of schema version
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "Add format header with schema version",
+        apply: |_, payload| Ok(payload),
+    },
+    Migration {
+        version: 2,
+        description: "Scope board data per named board",
+        apply: scope_named_boards,
+    },
+    Migration {
+        version: 3,
+        description: "Replace hard-coded task status with user-defined swimlanes",
+        apply: define_swimlanes,
+    },
+    Migration {
+        version: 4,
+        description: "Add work-in-progress limits to swimlanes",
+        apply: add_wip_limits,
+    },
+    Migration {
+        version: 5,
+        description: "Add sequential Task and SubTask IDs per board",
+        apply: add_id_sequence,
+    },
+    Migration {
+        version: 6,
+        description: "Add trash and archive to boards",
+        apply: add_trash_and_archive,
+    },
+    Migration {
+        version: 7,
+        description: "Add tags to tasks and subtasks",
+        apply: add_tags,
+    },
+    Migration {
+        version: 8,
+        description: "Add people and assignees to boards",
+        apply: add_people,
+    },
+    Migration {
+        version: 9,
+        description: "Add dependencies between tasks and subtasks",
+        apply: add_dependencies,
+    },
+    Migration {
+        version: 10,
+        description: "Merge tasks and subtasks into work items with a parent pointer",
+        apply: add_parents,
+    },
+    Migration {
+        version: 11,
+        description: "Add worklogs to tasks and subtasks and timers to boards",
+        apply: add_worklogs,
+    },
+    Migration {
+        version: 12,
+        description: "Add estimates and remaining effort to tasks and subtasks",
+        apply: add_estimates,
+    },
+    Migration {
+        version: 13,
+        description: "Add sprints to boards",
+        apply: add_sprints,
+    },
+    Migration {
+        version: BLOCKED_SWIMLANES_VERSION,
+        description: "Mark the swimlanes holding blocked tasks and subtasks",
+        apply: mark_blocked_swimlanes,
+    },
+];
+
+/// Register the default board as the only (and active) board in the application configuration
+fn scope_named_boards(kind: RecordKind, mut payload: Vec<u8>) -> Result<Vec<u8>, AppError> {
+    if kind != RecordKind::Config {
+        return Ok(payload);
+    }
+    let boards_fields: (&str, Vec<&str>) = (DEFAULT_BOARD, vec![DEFAULT_BOARD]);
+    payload.extend(encode(&boards_fields)?);
+    Ok(payload)
+}
+
+/// Convert the hard-coded task status on boards, tasks and subtasks into the default swimlanes
+fn define_swimlanes(kind: RecordKind, payload: Vec<u8>) -> Result<Vec<u8>, AppError> {
+    match kind {
+        RecordKind::Board => {
+            let mut boards: HashMap<TaskStatusV2, Vec<String>> = decode(&payload)?;
+            let swimlanes: Vec<SwimlaneV3> = TaskStatusV2::ALL
+                .iter()
+                .map(|status| SwimlaneV3 {
+                    slug: status.slug().to_string(),
+                    name: status.name().to_string(),
+                    stage: status.stage(),
+                    items: boards
+                        .remove(status)
+                        .unwrap_or_default()
+                        .into_iter()
+                        .filter(|x| !x.is_empty())
+                        .collect(),
+                })
+                .collect();
+            encode(&swimlanes)
+        }
+        RecordKind::Task | RecordKind::SubTask => {
+            let item: ItemV2 = decode(&payload)?;
+            encode(&ItemV3 {
+                id: item.id,
+                name: item.name,
+                description: item.description,
+                added_on: item.added_on,
+                started_on: item.started_on,
+                deadline: item.deadline,
+                completed_on: item.completed_on,
+                status: item.status.slug().to_string(),
+                priority: item.priority,
+            })
+        }
+        _ => Ok(payload),
+    }
+}
+
+/// Add an unset WIP limit to every swimlane on the board
+fn add_wip_limits(kind: RecordKind, payload: Vec<u8>) -> Result<Vec<u8>, AppError> {
+    if kind != RecordKind::Board {
+        return Ok(payload);
+    }
+    let swimlanes: Vec<SwimlaneV3> = decode(&payload)?;
+    let swimlanes: Vec<SwimlaneV4> = swimlanes
+        .into_iter()
+        .map(|x| SwimlaneV4 {
+            slug: x.slug,
+            name: x.name,
+            stage: x.stage,
+            wip_limit: None,
+            items: x.items,
+        })
+        .collect();
+    encode(&swimlanes)
+}
+
+/// Start an ID sequence with the TASK prefix on the board (existing IDs are kept until re-keyed)
+fn add_id_sequence(kind: RecordKind, mut payload: Vec<u8>) -> Result<Vec<u8>, AppError> {
+    if kind != RecordKind::Board {
+        return Ok(payload);
+    }
+    let id_sequence: (&str, u64, HashMap<String, u64>) = ("TASK", 0, HashMap::new());
+    payload.extend(encode(&id_sequence)?);
+    Ok(payload)
+}
+
+/// Start the board with an empty trash and archive
+fn add_trash_and_archive(kind: RecordKind, mut payload: Vec<u8>) -> Result<Vec<u8>, AppError> {
+    if kind != RecordKind::Board {
+        return Ok(payload);
+    }
+    let hidden_items: (Vec<String>, Vec<String>) = (Vec::new(), Vec::new());
+    payload.extend(encode(&hidden_items)?);
+    Ok(payload)
+}
+
+/// Start every task and subtask without tags, and the board without tag colors
+fn add_tags(kind: RecordKind, mut payload: Vec<u8>) -> Result<Vec<u8>, AppError> {
+    match kind {
+        RecordKind::Task | RecordKind::SubTask => {
+            let tags: Vec<String> = Vec::new();
+            payload.extend(encode(&tags)?);
+        }
+        RecordKind::Board => {
+            let tag_colors: HashMap<String, String> = HashMap::new();
+            payload.extend(encode(&tag_colors)?);
+        }
+        _ => {}
+    }
+    Ok(payload)
+}
+
+/// Start every task and subtask unassigned, and the board without people
+fn add_people(kind: RecordKind, mut payload: Vec<u8>) -> Result<Vec<u8>, AppError> {
+    match kind {
+        RecordKind::Task | RecordKind::SubTask => {
+            let assignee: Option<String> = None;
+            payload.extend(encode(&assignee)?);
+        }
+        RecordKind::Board => {
+            let people: Vec<(String, String, Option<String>)> = Vec::new();
+            payload.extend(encode(&people)?);
+        }
+        _ => {}
+    }
+    Ok(payload)
+}
+
+/// Start every board without dependencies
+fn add_dependencies(kind: RecordKind, mut payload: Vec<u8>) -> Result<Vec<u8>, AppError> {
+    if kind == RecordKind::Board {
+        let dependencies: HashMap<String, Vec<String>> = HashMap::new();
+        payload.extend(encode(&dependencies)?);
+    }
+    Ok(payload)
+}
+
+/// Start every task and subtask without a parent (subtasks are nested under their parent task from
+/// the task-to-subtask links by the storage backend)
+fn add_parents(kind: RecordKind, mut payload: Vec<u8>) -> Result<Vec<u8>, AppError> {
+    if let RecordKind::Task | RecordKind::SubTask = kind {
+        let parent: Option<String> = None;
+        payload.extend(encode(&parent)?);
+    }
+    Ok(payload)
+}
+
+/// Start every task and subtask without logged time, and the board without running timers
+fn add_worklogs(kind: RecordKind, mut payload: Vec<u8>) -> Result<Vec<u8>, AppError> {
+    match kind {
+        RecordKind::Item | RecordKind::Task | RecordKind::SubTask => {
+            let worklog: Vec<(TimeStamp, u32, String, Option<String>)> = Vec::new();
+            payload.extend(encode(&worklog)?);
+        }
+        RecordKind::Board => {
+            let timers: Vec<(String, Option<String>, i64)> = Vec::new();
+            payload.extend(encode(&timers)?);
+        }
+        _ => {}
+    }
+    Ok(payload)
+}
+
+/// Start every task and subtask without an estimate
+fn add_estimates(kind: RecordKind, mut payload: Vec<u8>) -> Result<Vec<u8>, AppError> {
+    if let RecordKind::Item | RecordKind::Task | RecordKind::SubTask = kind {
+        let estimate: Option<(u32, u32, u32)> = None;
+        payload.extend(encode(&estimate)?);
+    }
+    Ok(payload)
+}
+
+/// Start every board without sprints, leaving all of its tasks in the backlog
+fn add_sprints(kind: RecordKind, mut payload: Vec<u8>) -> Result<Vec<u8>, AppError> {
+    if kind == RecordKind::Board {
+        let sprints: Vec<(u32, String, TimeStamp, TimeStamp)> = Vec::new();
+        payload.extend(encode(&sprints)?);
+    }
+    Ok(payload)
+}
+
+/// Mark the swimlane which used to be recognized as holding blocked tasks and subtasks by its slug
+fn mark_blocked_swimlanes(kind: RecordKind, payload: Vec<u8>) -> Result<Vec<u8>, AppError> {
+    if kind != RecordKind::Board {
+        return Ok(payload);
+    }
+    // The swimlanes lead the board, so the fields after them are carried over as they are
+    let swimlanes: Vec<SwimlaneV4> = decode(&payload)?;
+    let swimlanes_len: usize = match bincode::serialized_size(&swimlanes) {
+        Ok(s) => s as usize,
+        Err(e) => return Err(AppError::BinaryDeserializationError(e.to_string())),
+    };
+    let swimlanes: Vec<SwimlaneV14> = swimlanes
+        .into_iter()
+        .map(|x| SwimlaneV14 {
+            blocked: x.slug == LEGACY_BLOCKED_SWIMLANE,
+            slug: x.slug,
+            name: x.name,
+            stage: x.stage,
+            wip_limit: x.wip_limit,
+            items: x.items,
+        })
+        .collect();
+    let mut upgraded: Vec<u8> = encode(&swimlanes)?;
+    upgraded.extend_from_slice(&payload[swimlanes_len..]);
+    Ok(upgraded)
+}
+
+/// Task status enum up to schema version 2, when swimlanes were hard-coded
+#[derive(Debug, Deserialize, Eq, PartialEq, Hash)]
+enum TaskStatusV2 {
+    ToDo,
+    InProgress,
+    Blocked,
+    InReview,
+    Done,
+}
+
+impl TaskStatusV2 {
+    const ALL: [TaskStatusV2; 5] = [
+        TaskStatusV2::ToDo,
+        TaskStatusV2::InProgress,
+        TaskStatusV2::Blocked,
+        TaskStatusV2::InReview,
+        TaskStatusV2::Done,
+    ];
+
+    fn slug(&self) -> &'static str {
+        match self {
+            TaskStatusV2::ToDo => "to-do",
+            TaskStatusV2::InProgress => "in-progress",
+            TaskStatusV2::Blocked => "blocked",
+            TaskStatusV2::InReview => "in-review",
+            TaskStatusV2::Done => "done",
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            TaskStatusV2::ToDo => "To-Do",
+            TaskStatusV2::InProgress => "In Progress",
+            TaskStatusV2::Blocked => "Blocked",
+            TaskStatusV2::InReview => "In Review",
+            TaskStatusV2::Done => "Done",
+        }
+    }
+
+    fn stage(&self) -> LaneStageV3 {
+        match self {
+            TaskStatusV2::ToDo => LaneStageV3::Pending,
+            TaskStatusV2::Done => LaneStageV3::Done,
+            _ => LaneStageV3::Started,
+        }
+    }
+}
+
+/// Date as stored since the first version
+#[derive(Serialize, Deserialize)]
+struct DateV0 {
+    year: i32,
+    month: u32,
+    day: u32,
+}
+
+/// Priority enum as stored since the first version
+#[derive(Serialize, Deserialize)]
+enum PriorityV0 {
+    High,
+    Medium,
+    Low,
+}
+
+/// Task or subtask item up to schema version 2
+#[derive(Deserialize)]
+struct ItemV2 {
+    id: String,
+    name: String,
+    description: String,
+    added_on: DateV0,
+    started_on: Option<DateV0>,
+    deadline: Option<DateV0>,
+    completed_on: Option<DateV0>,
+    status: TaskStatusV2,
+    priority: PriorityV0,
+}
+
+/// Task or subtask item as of schema version 3
+#[derive(Serialize)]
+struct ItemV3 {
+    id: String,
+    name: String,
+    description: String,
+    added_on: DateV0,
+    started_on: Option<DateV0>,
+    deadline: Option<DateV0>,
+    completed_on: Option<DateV0>,
+    status: String,
+    priority: PriorityV0,
+}
+
+/// Swimlane stage enum as of schema version 3
+#[derive(Serialize, Deserialize)]
+enum LaneStageV3 {
+    Pending,
+    Started,
+    Done,
+}
+
+/// Swimlane as of schema version 3 (the board is stored as the list of swimlanes)
+#[derive(Serialize, Deserialize)]
+struct SwimlaneV3 {
+    slug: String,
+    name: String,
+    stage: LaneStageV3,
+    items: Vec<String>,
+}
+
+/// WIP limit of a swimlane as of schema version 4
+#[derive(Serialize, Deserialize)]
+struct WipLimitV4 {
+    max_items: usize,
+    strict: bool,
+}
+
+/// Swimlane as of schema version 4
+#[derive(Serialize, Deserialize)]
+struct SwimlaneV4 {
+    slug: String,
+    name: String,
+    stage: LaneStageV3,
+    wip_limit: Option<WipLimitV4>,
+    items: Vec<String>,
+}
+
+/// Swimlane as of schema version 14
+#[derive(Serialize)]
+struct SwimlaneV14 {
+    slug: String,
+    name: String,
+    stage: LaneStageV3,
+    blocked: bool,
+    wip_limit: Option<WipLimitV4>,
+    items: Vec<String>,
+}
+
+/// Deserialize a payload without format header inside a migration step
+fn decode<T: DeserializeOwned>(payload: &[u8]) -> Result<T, AppError> {
+    match bincode::deserialize(payload) {
+        Ok(s) => Ok(s),
+        Err(e) => Err(AppError::BinaryDeserializationError(e.to_string())),
+    }
+}
+
+/// Serialize a payload without format header inside a migration step
+fn encode<T: Serialize>(value: &T) -> Result<Vec<u8>, AppError> {
+    match bincode::serialize(value) {
+        Ok(s) => Ok(s),
+        Err(e) => Err(AppError::BinarySerializationError(e.to_string())),
+    }
+}
+
+/// Serialize given value with the format header for the current schema version
+pub fn serialize<T: Serialize>(value: &T) -> Result<Vec<u8>, AppError> {
+    let payload: Vec<u8> = match bincode::serialize(value) {
+        Ok(s) => s,
+        Err(e) => return Err(AppError::BinarySerializationError(e.to_string())),
+    };
+    let mut data: Vec<u8> = Vec::with_capacity(HEADER_LEN + payload.len());
+    data.extend_from_slice(FORMAT_MAGIC);
+    data.extend_from_slice(&SCHEMA_VERSION.to_le_bytes());
+    data.extend(payload);
+    Ok(data)
+}
+
+/// Deserialize given file contents, upgrading them through the migration chain if written by an
+/// older version. Returns the value along with whether any migration was applied.
+pub fn deserialize<T: DeserializeOwned>(
+    kind: RecordKind,
+    data: &[u8],
+) -> Result<(T, bool), AppError> {
+    let version: u32 = read_version(data)?;
+    let mut payload: Vec<u8> = match version {
+        0 => data.to_vec(),
+        _ => data[HEADER_LEN..].to_vec(),
+    };
+
+    let pending: Vec<&Migration> = pending_migrations(version);
+    for migration in &pending {
+        payload = (migration.apply)(kind, payload)?;
+    }
+
+    match bincode::deserialize(&payload) {
+        Ok(s) => Ok((s, !pending.is_empty())),
+        Err(e) => Err(AppError::BinaryDeserializationError(format!(
+            "{} (schema version {}) - {}",
+            kind, version, e
+        ))),
+    }
+}
+
+/// Read the schema version from the format header of given file contents.
+/// Files written before the header was introduced are reported as version 0.
+pub fn read_version(data: &[u8]) -> Result<u32, AppError> {
+    if data.len() < HEADER_LEN || &data[..4] != FORMAT_MAGIC {
+        return Ok(0);
+    }
+    let version: u32 = u32::from_le_bytes([data[4], data[5], data[6], data[7]]);
+    if version > SCHEMA_VERSION {
+        return Err(AppError::UnsupportedSchemaVersion(format!(
+            "File has schema version {} but this version of the application supports up to {}. Please upgrade rustic_boards.",
+            version, SCHEMA_VERSION
+        )));
+    }
+    Ok(version)
+}
+
+/// Get the migrations which still need to be applied to a file at given schema version
+pub fn pending_migrations(version: u32) -> Vec<&'static Migration> {
+    MIGRATIONS.iter().filter(|x| x.version > version).collect()
+}
+
+/// Describe the migrations pending for a file at given schema version (None if up to date)
+pub fn describe_pending(name: &str, version: u32) -> Option<String> {
+    let pending: Vec<&Migration> = pending_migrations(version);
+    if pending.is_empty() {
+        return None;
+    }
+    let steps: Vec<String> = pending
+        .iter()
+        .map(|x| format!("v{}: {}", x.version, x.description))
+        .collect();
+    Some(format!(
+        "{} (schema version {} -> {}): {}",
+        name,
+        version,
+        SCHEMA_VERSION,
+        steps.join("; ")
+    ))
+}
+
+/// Move the board data kept directly in the application directory (by versions before named boards
+/// were introduced) into the default board, unless `check_only` is set.
+/// Returns the description of the change (None if there is no data in the legacy layout).
+pub fn migrate_layout(app_dir: &Path, check_only: bool) -> Result<Option<String>, AppError> {
+    let legacy_files: Vec<&str> = [
+        KANBAN_BOARD_FILE,
+        TAKS_LINK_FILE,
+        NOTES_FILE,
+        SQLITE_DB_FILE,
+    ]
+    .into_iter()
+    .filter(|x| app_dir.join(x).exists())
+    .collect();
+    if legacy_files.is_empty() {
+        return Ok(None);
+    }
+
+    let change: String = format!(
+        "{} (legacy layout): Move board data into the {} board",
+        app_dir.display(),
+        DEFAULT_BOARD
+    );
+    if check_only {
+        return Ok(Some(change));
+    }
+
+    let board_dir: PathBuf = create_board_dirs(DEFAULT_BOARD)?;
+    let board_file: PathBuf = app_dir.join(KANBAN_BOARD_FILE);
+    if board_file.exists() {
+        let data: Vec<u8> = match std::fs::read(&board_file) {
+            Ok(s) => s,
+            Err(e) => {
+                return Err(AppError::FileReadError(format!(
+                    "{} - {}",
+                    board_file.display(),
+                    e
+                )))
+            }
+        };
+        let boards: KanbanBoard = deserialize::<KanbanBoard>(RecordKind::Board, &data)?.0;
+        for item_id in boards.get_all_items() {
+            for items_dir in [ACTIVE_TASKS_PATH, ACTIVE_SUBTASKS_PATH] {
+                let item_file: PathBuf = app_dir.join(items_dir).join(format!("{}.bin", item_id));
+                if item_file.exists() {
+                    create_dir(&board_dir.join(items_dir))?;
+                    move_file(
+                        &item_file,
+                        &board_dir.join(items_dir).join(format!("{}.bin", item_id)),
+                    )?;
+                }
+            }
+        }
+    }
+
+    for file_name in legacy_files {
+        move_file(&app_dir.join(file_name), &board_dir.join(file_name))?;
+    }
+    Ok(Some(change))
+}
+
+/// Move the application directory used before the platform data directory was introduced (see
+/// `utils::create_app_dirs`) to given application directory. Both directories holding data would
+/// mean two sets of boards, which are left for the user to reconcile.
+/// Returns whether the legacy directory was moved.
+pub fn migrate_app_dir(legacy_dir: &Path, app_dir: &Path) -> Result<bool, AppError> {
+    if !legacy_dir.is_dir() {
+        return Ok(false);
+    }
+    if app_dir.exists() {
+        return Err(AppError::FileWriteError(format!(
+            "{} - already exists, unable to move the boards kept by older versions in {} into it. \
+             Please move the boards you want to keep into one of them and remove the other.",
+            app_dir.display(),
+            legacy_dir.display()
+        )));
+    }
+
+    if let Some(parent_dir) = app_dir.parent() {
+        create_dir(parent_dir)?;
+    }
+    match std::fs::rename(legacy_dir, app_dir) {
+        Ok(_) => {
+            record_write();
+            Ok(true)
+        }
+        Err(e) => Err(AppError::FileWriteError(format!(
+            "{} - unable to move {} into it - {}",
+            app_dir.display(),
+            legacy_dir.display(),
+            e
+        ))),
+    }
+}
+
+/// Move given file to a new path, without overwriting any existing file
+pub(crate) fn move_file(from: &Path, to: &Path) -> Result<(), AppError> {
+    if to.exists() {
+        return Err(AppError::FileWriteError(format!(
+            "{} - already exists, unable to move {} into it",
+            to.display(),
+            from.display()
+        )));
+    }
+    match std::fs::rename(from, to) {
+        Ok(_) => {
+            record_write();
+            Ok(())
+        }
+        Err(e) => Err(AppError::FileWriteError(format!(
+            "{} - {}",
+            to.display(),
+            e
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        constants::{BOARDS_PATH, CONFIG_FILE},
+        utils::test_dir,
+    };
+
+    #[test]
+    fn blocked_swimlane_is_marked_by_its_slug() {
+        let boards: KanbanBoard = KanbanBoard::new();
+        let payload: Vec<u8> = encode(&boards).unwrap();
+        let swimlanes_len: usize = bincode::serialized_size(&boards.swimlanes).unwrap() as usize;
+        let legacy_swimlanes: Vec<SwimlaneV4> = boards
+            .swimlanes
+            .iter()
+            .map(|x| SwimlaneV4 {
+                slug: x.slug.clone(),
+                name: x.name.clone(),
+                stage: decode(&encode(&x.stage).unwrap()).unwrap(),
+                wip_limit: None,
+                items: x.items.clone(),
+            })
+            .collect();
+        let mut legacy_payload: Vec<u8> = encode(&legacy_swimlanes).unwrap();
+        legacy_payload.extend_from_slice(&payload[swimlanes_len..]);
+
+        let upgraded: Vec<u8> = mark_blocked_swimlanes(RecordKind::Board, legacy_payload).unwrap();
+        let upgraded: KanbanBoard = decode(&upgraded).unwrap();
+        assert_eq!(upgraded, boards);
+        assert_eq!(
+            upgraded
+                .swimlanes
+                .iter()
+                .filter(|x| x.blocked)
+                .map(|x| x.slug.as_str())
+                .collect::<Vec<&str>>(),
+            vec![LEGACY_BLOCKED_SWIMLANE]
+        );
+    }
+
+    #[test]
+    fn legacy_app_dir_is_moved_into_data_dir() {
+        let base_dir: PathBuf = test_dir("legacy_app_dir");
+        let legacy_dir: PathBuf = base_dir.join("home\\.rustic_boards");
+        let app_dir: PathBuf = base_dir.join("share").join("rustic_boards");
+        let board_dir: PathBuf = legacy_dir.join(BOARDS_PATH).join(DEFAULT_BOARD);
+        create_dir(&board_dir).unwrap();
+        std::fs::write(legacy_dir.join(CONFIG_FILE), b"config").unwrap();
+        std::fs::write(board_dir.join(KANBAN_BOARD_FILE), b"board").unwrap();
+
+        assert!(migrate_app_dir(&legacy_dir, &app_dir).unwrap());
+        assert!(!legacy_dir.exists());
+        assert_eq!(std::fs::read(app_dir.join(CONFIG_FILE)).unwrap(), b"config");
+        let board_file: PathBuf = app_dir
+            .join(BOARDS_PATH)
+            .join(DEFAULT_BOARD)
+            .join(KANBAN_BOARD_FILE);
+        assert_eq!(std::fs::read(board_file).unwrap(), b"board");
+        assert!(!migrate_app_dir(&legacy_dir, &app_dir).unwrap());
+    }
+
+    #[test]
+    fn legacy_app_dir_is_not_merged_into_existing_data_dir() {
+        let base_dir: PathBuf = test_dir("legacy_app_dir_conflict");
+        let legacy_dir: PathBuf = base_dir.join("home\\.rustic_boards");
+        let app_dir: PathBuf = base_dir.join("rustic_boards");
+        create_dir(&legacy_dir).unwrap();
+        create_dir(&app_dir).unwrap();
+        std::fs::write(legacy_dir.join(CONFIG_FILE), b"legacy").unwrap();
+        std::fs::write(app_dir.join(CONFIG_FILE), b"current").unwrap();
+
+        let err: AppError = migrate_app_dir(&legacy_dir, &app_dir).unwrap_err();
+        assert!(matches!(err, AppError::FileWriteError(_)), "{}", err);
+        assert_eq!(
+            std::fs::read(legacy_dir.join(CONFIG_FILE)).unwrap(),
+            b"legacy"
+        );
+        assert_eq!(
+            std::fs::read(app_dir.join(CONFIG_FILE)).unwrap(),
+            b"current"
+        );
+    }
+}
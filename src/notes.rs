@@ -1,18 +1,33 @@
 //! Defines the Notes structure along with associated helper methods
 
-use crate::{
-    constants::NOTES_FILE, 
-    error::AppError,
-    utils::create_app_dirs
-};
+use crate::{error::AppError, output::Record, storage::Storage};
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, path::Path};
+use std::collections::HashMap;
+
+/// Note of a task or subtask as listed by `show notes`
+#[derive(Debug, Clone, Serialize)]
+pub struct NoteRecord {
+    /// Task or SubTask ID the note was added to
+    pub item_id: String,
+
+    /// Position of the note among the notes of the item (starting at 1 for the oldest)
+    pub number: usize,
+
+    /// Text of the note
+    pub note: String,
+}
+
+impl Record for NoteRecord {
+    fn cells(&self) -> Vec<String> {
+        vec![self.number.to_string(), self.note.clone()]
+    }
+}
 
 /// Rust structure for storing notes for Tasks and SubTasks
-#[derive(Debug, Serialize, Deserialize, Eq, PartialEq)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Eq, PartialEq)]
 pub struct TaskNotes {
     /// Using HashMaps to store list of notes for different Tasks and SubTasks
-    notes: HashMap<String, Vec<String>>,
+    pub(crate) notes: HashMap<String, Vec<String>>,
 }
 
 impl TaskNotes {
@@ -26,6 +41,7 @@ impl TaskNotes {
     /// Add a new note for given Task or SubTask IDs
     pub fn add_new_note(
         &mut self,
+        storage: &dyn Storage,
         task_id: String,
         notes_list: Vec<String>,
     ) -> Result<(), AppError> {
@@ -35,7 +51,15 @@ impl TaskNotes {
         };
         current_notes_list.extend(notes_list.clone());
         self.notes.insert(task_id, current_notes_list);
-        self.write_to_file()?;
+        self.save(storage)?;
+        Ok(())
+    }
+
+    /// Delete all notes of given Task or SubTask ID
+    pub fn delete_notes(&mut self, storage: &dyn Storage, task_id: &str) -> Result<(), AppError> {
+        if self.notes.remove(task_id).is_some() {
+            self.save(storage)?;
+        }
         Ok(())
     }
 
@@ -47,50 +71,13 @@ impl TaskNotes {
         notes_list
     }
 
-    /// Load the Notes information from stored file in disk
-    pub fn load_from_file() -> Result<Self, AppError> {
-        let app_dir: String = create_app_dirs()?;
-        let data: Vec<u8> = match std::fs::read(format!("{}\\{}", app_dir, NOTES_FILE)) {
-            Ok(s) => s,
-            Err(e) => {
-                return Err(AppError::FileReadError(format!(
-                    "{} - {}",
-                    NOTES_FILE,
-                    e.to_string()
-                )))
-            }
-        };
-        let task_notes: TaskNotes = match bincode::deserialize(&data) {
-            Ok(s) => s,
-            Err(e) => return Err(AppError::BinaryDeserializationError(e.to_string())),
-        };
-        Ok(task_notes)
-    }
-
-    /// Store the Notes informationto a file in disk
-    pub fn write_to_file(&self) -> Result<(), AppError> {
-        let app_dir: String = create_app_dirs()?;
-        let data: Vec<u8> = match bincode::serialize(&self) {
-            Ok(s) => s,
-            Err(e) => return Err(AppError::BinarySerializationError(e.to_string())),
-        };
-        match std::fs::write(format!("{}\\{}", app_dir, NOTES_FILE), data) {
-            Ok(_) => {}
-            Err(e) => {
-                return Err(AppError::FileWriteError(format!(
-                    "{} - {}",
-                    NOTES_FILE,
-                    e.to_string()
-                )))
-            }
-        };
-        Ok(())
+    /// Load the Notes information from storage (None if it was never stored)
+    pub fn load(storage: &dyn Storage) -> Result<Option<Self>, AppError> {
+        storage.load_notes()
     }
 
-    /// Check if the Notes file is present in disk
-    pub fn check_if_file_exists() -> Result<bool, AppError> {
-        let app_dir: String = create_app_dirs()?;
-        let notes_file: String = format!("{}\\{}", app_dir, NOTES_FILE);
-        Ok(Path::new(&notes_file).exists())
+    /// Store the Notes information to storage
+    pub fn save(&self, storage: &dyn Storage) -> Result<(), AppError> {
+        storage.save_notes(self)
     }
 }
@@ -0,0 +1,362 @@
+//! Defines the output formats for the read commands and how their records are rendered in each
+
+use crate::error::AppError;
+use chrono::prelude::NaiveDate;
+use cli_table::{Cell, CellStruct, Style, Table, TableDisplay};
+use serde::{Serialize, Serializer};
+use serde_json::Value;
+
+/// Output formats which can be chosen with `--format`
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum OutputFormat {
+    /// Tables meant to be read in the terminal
+    #[default]
+    Table,
+
+    /// A single JSON document (an array of records, or an object for a single item)
+    Json,
+
+    /// One JSON object per line
+    Ndjson,
+
+    /// Comma-separated values with a header row
+    Csv,
+}
+
+impl std::fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OutputFormat::Table => write!(f, "table"),
+            OutputFormat::Json => write!(f, "json"),
+            OutputFormat::Ndjson => write!(f, "ndjson"),
+            OutputFormat::Csv => write!(f, "csv"),
+        }
+    }
+}
+
+impl std::str::FromStr for OutputFormat {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "table" => Ok(OutputFormat::Table),
+            "json" => Ok(OutputFormat::Json),
+            "ndjson" => Ok(OutputFormat::Ndjson),
+            "csv" => Ok(OutputFormat::Csv),
+            _ => Err(AppError::InvalidArgument(format!(
+                "{} \nPlease select from following output formats: \n1) table 2) json 3) ndjson 4) csv\n",
+                s
+            ))),
+        }
+    }
+}
+
+/// A record returned by a read command, which can be shown as a table row or serialized
+pub trait Record: Serialize {
+    /// Values shown in the table format, in the same order as the titles of the table
+    fn cells(&self) -> Vec<String>;
+}
+
+/// Records shown together under a heading (e.g. the swimlane they are in) in the table format
+#[derive(Debug)]
+pub struct Section<T> {
+    /// Heading printed above the table of records
+    pub heading: String,
+
+    /// Records in the section
+    pub records: Vec<T>,
+}
+
+/// Print the sections in given format.
+/// Only the table format keeps the sections apart; the other formats list all records together.
+pub fn print_sections<T: Record>(
+    format: OutputFormat,
+    titles: &[&str],
+    sections: &[Section<T>],
+) -> Result<(), AppError> {
+    if format != OutputFormat::Table {
+        let records: Vec<&T> = sections.iter().flat_map(|x| x.records.iter()).collect();
+        return print_values(format, to_values(&records)?, false);
+    }
+
+    for section in sections {
+        println!("====================");
+        println!("{}", section.heading);
+        println!("====================");
+        print_table(titles, &section.records)?;
+    }
+    Ok(())
+}
+
+/// Print the records in given format
+pub fn print_records<T: Record>(
+    format: OutputFormat,
+    titles: &[&str],
+    records: &[T],
+) -> Result<(), AppError> {
+    match format {
+        OutputFormat::Table => print_table(titles, records),
+        _ => {
+            let records: Vec<&T> = records.iter().collect();
+            print_values(format, to_values(&records)?, false)
+        }
+    }
+}
+
+/// Print all details of a single record in given format.
+/// The table format shows every title next to its value.
+pub fn print_details<T: Record>(
+    format: OutputFormat,
+    titles: &[&str],
+    record: &T,
+) -> Result<(), AppError> {
+    if format != OutputFormat::Table {
+        return print_values(format, to_values(&[record])?, true);
+    }
+
+    let display_vec: Vec<Vec<String>> = titles
+        .iter()
+        .zip(record.cells())
+        .map(|(title, value)| vec![title.to_string(), value])
+        .collect();
+    let display_table: TableDisplay = match display_vec.table().display() {
+        Ok(s) => s,
+        Err(e) => return Err(AppError::TableDisplayParseError(e.to_string())),
+    };
+    println!("{}", display_table);
+    Ok(())
+}
+
+/// Print the records as a table with given titles
+fn print_table<T: Record>(titles: &[&str], records: &[T]) -> Result<(), AppError> {
+    let display_vec: Vec<Vec<String>> = records.iter().map(|x| x.cells()).collect();
+    let title_cells: Vec<CellStruct> = titles.iter().map(|x| x.cell().bold(true)).collect();
+    let display_table: TableDisplay = match display_vec.table().title(title_cells).display() {
+        Ok(s) => s,
+        Err(e) => return Err(AppError::TableDisplayParseError(e.to_string())),
+    };
+    println!("{}", display_table);
+    Ok(())
+}
+
+/// Convert the records to JSON values
+fn to_values<T: Serialize>(records: &[&T]) -> Result<Vec<Value>, AppError> {
+    let mut values: Vec<Value> = Vec::new();
+    for record in records {
+        match serde_json::to_value(record) {
+            Ok(s) => values.push(s),
+            Err(e) => return Err(AppError::OutputError(e.to_string())),
+        }
+    }
+    Ok(values)
+}
+
+/// Print the JSON values in given format.
+/// A single value is printed as a JSON object instead of an array holding it.
+fn print_values(format: OutputFormat, values: Vec<Value>, single: bool) -> Result<(), AppError> {
+    print!("{}", render_values(format, values, single)?);
+    Ok(())
+}
+
+/// Render the JSON values in given format, each line ending with a newline.
+/// A single value is rendered as a JSON object instead of an array holding it.
+fn render_values(
+    format: OutputFormat,
+    values: Vec<Value>,
+    single: bool,
+) -> Result<String, AppError> {
+    match format {
+        OutputFormat::Ndjson => Ok(values.iter().map(|x| format!("{}\n", x)).collect()),
+        OutputFormat::Csv => render_csv(&values),
+        _ => {
+            let document: Value = match (single, values.first()) {
+                (true, Some(s)) => s.clone(),
+                _ => Value::Array(values),
+            };
+            match serde_json::to_string_pretty(&document) {
+                Ok(s) => Ok(format!("{}\n", s)),
+                Err(e) => Err(AppError::OutputError(e.to_string())),
+            }
+        }
+    }
+}
+
+/// Render the JSON values as CSV, with the fields of the first value as the header row.
+/// Missing values are left empty and lists are joined with "; ".
+fn render_csv(values: &[Value]) -> Result<String, AppError> {
+    let headers: Vec<String> = match values.first() {
+        Some(Value::Object(s)) => s.keys().cloned().collect(),
+        _ => return Ok(String::new()),
+    };
+
+    let mut writer: csv::Writer<Vec<u8>> = csv::Writer::from_writer(Vec::new());
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    for value in values {
+        rows.push(
+            headers
+                .iter()
+                .map(|x| csv_field(value.get(x).unwrap_or(&Value::Null)))
+                .collect(),
+        );
+    }
+    for row in std::iter::once(&headers).chain(rows.iter()) {
+        if let Err(e) = writer.write_record(row) {
+            return Err(AppError::OutputError(e.to_string()));
+        }
+    }
+    let data: Vec<u8> = match writer.into_inner() {
+        Ok(s) => s,
+        Err(e) => return Err(AppError::OutputError(e.to_string())),
+    };
+    match String::from_utf8(data) {
+        Ok(s) => Ok(s),
+        Err(e) => Err(AppError::OutputError(e.to_string())),
+    }
+}
+
+/// Convert a JSON value to a CSV field
+fn csv_field(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        Value::Array(s) => s.iter().map(csv_field).collect::<Vec<_>>().join("; "),
+        _ => value.to_string(),
+    }
+}
+
+/// Format an optional date for the table format ("None" if missing)
+pub fn display_date(date: &Option<NaiveDate>) -> String {
+    match date {
+        Some(s) => s.format("%b %e, %Y").to_string(),
+        None => "None".to_string(),
+    }
+}
+
+/// Format a list for the table format ("None" if empty)
+pub fn display_list(values: &[String]) -> String {
+    match values.is_empty() {
+        true => "None".to_string(),
+        false => values.join(", "),
+    }
+}
+
+/// Serialize a date in yyyy-mm-dd format
+pub fn serialize_date<S: Serializer>(date: &NaiveDate, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&date.format("%Y-%m-%d").to_string())
+}
+
+/// Serialize an optional date in yyyy-mm-dd format (null if missing)
+pub fn serialize_optional_date<S: Serializer>(
+    date: &Option<NaiveDate>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match date {
+        Some(s) => serialize_date(s, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// Serialize a value as its display text
+pub fn serialize_display<T: std::fmt::Display, S: Serializer>(
+    value: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&value.to_string())
+}
+
+/// Serialize a value as its lowercase display text (e.g. a priority as the keyword it is passed with)
+pub fn serialize_lowercase<T: std::fmt::Display, S: Serializer>(
+    value: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&value.to_string().to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Record with the kinds of fields the read commands return
+    #[derive(Serialize)]
+    struct TestRecord {
+        /// Text field
+        name: String,
+
+        /// Optional number field
+        points: Option<u32>,
+
+        /// List field
+        tags: Vec<String>,
+    }
+
+    impl Record for TestRecord {
+        fn cells(&self) -> Vec<String> {
+            vec![self.name.clone()]
+        }
+    }
+
+    /// Render given records in given format
+    fn render(format: OutputFormat, records: &[TestRecord], single: bool) -> String {
+        let records: Vec<&TestRecord> = records.iter().collect();
+        render_values(format, to_values(&records).unwrap(), single).unwrap()
+    }
+
+    /// Records with text which needs quoting in CSV
+    fn test_records() -> Vec<TestRecord> {
+        vec![
+            TestRecord {
+                name: "Plain".to_string(),
+                points: Some(3),
+                tags: vec!["backend".to_string(), "urgent".to_string()],
+            },
+            TestRecord {
+                name: "Comma, \"quotes\"\nand a newline".to_string(),
+                points: None,
+                tags: Vec::new(),
+            },
+        ]
+    }
+
+    #[test]
+    fn records_are_rendered_as_json() {
+        let records: Vec<TestRecord> = test_records();
+        let document: Value =
+            serde_json::from_str(&render(OutputFormat::Json, &records, false)).unwrap();
+        assert_eq!(
+            document,
+            serde_json::json!([
+                {"name": "Plain", "points": 3, "tags": ["backend", "urgent"]},
+                {"name": "Comma, \"quotes\"\nand a newline", "points": null, "tags": []}
+            ])
+        );
+
+        let document: Value =
+            serde_json::from_str(&render(OutputFormat::Json, &records[..1], true)).unwrap();
+        assert_eq!(document["name"], "Plain");
+    }
+
+    #[test]
+    fn records_are_rendered_as_ndjson() {
+        let output: String = render(OutputFormat::Ndjson, &test_records(), false);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(
+            lines,
+            [
+                r#"{"name":"Plain","points":3,"tags":["backend","urgent"]}"#,
+                r#"{"name":"Comma, \"quotes\"\nand a newline","points":null,"tags":[]}"#,
+            ]
+        );
+        assert!(output.ends_with('\n'));
+    }
+
+    #[test]
+    fn records_are_rendered_as_csv_with_quoting() {
+        assert_eq!(
+            render(OutputFormat::Csv, &test_records(), false),
+            "name,points,tags\n\
+             Plain,3,backend; urgent\n\
+             \"Comma, \"\"quotes\"\"\nand a newline\",,\n"
+        );
+        assert_eq!(render(OutputFormat::Csv, &[], false), "");
+    }
+}
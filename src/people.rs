@@ -0,0 +1,109 @@
+//! Defines the people who can be assigned to tasks and subtasks on a board, along with the lookup
+//! of the current user
+
+use crate::{constants::USER_ENV_VAR, error::AppError, output::Record};
+use serde::{Deserialize, Serialize};
+
+/// Rust structure for a person registered on a board
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct Person {
+    /// Short name used to assign tasks to the person (lowercase, e.g. jdoe)
+    pub handle: String,
+
+    /// Name shown for the person
+    pub name: String,
+
+    /// Email address of the person
+    pub email: Option<String>,
+}
+
+/// Person as listed by `user list`
+#[derive(Debug, Clone, Serialize)]
+pub struct PersonRecord {
+    /// Handle of the person
+    pub handle: String,
+
+    /// Name of the person
+    pub name: String,
+
+    /// Email address of the person
+    pub email: Option<String>,
+
+    /// Number of tasks on the board assigned to the person
+    pub tasks: usize,
+
+    /// Number of subtasks on the board assigned to the person
+    pub subtasks: usize,
+}
+
+impl Record for PersonRecord {
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.handle.clone(),
+            self.name.clone(),
+            self.email.clone().unwrap_or("None".to_string()),
+            self.tasks.to_string(),
+            self.subtasks.to_string(),
+        ]
+    }
+}
+
+/// Check that given handle is usable (letters, digits, `-`, `_` and `.`) and convert it to
+/// lowercase. `none` is kept for unassigning tasks.
+pub fn validate_handle(handle: &str) -> Result<String, AppError> {
+    if handle.is_empty()
+        || handle.eq_ignore_ascii_case("none")
+        || !handle
+            .chars()
+            .all(|x| x.is_alphanumeric() || x == '-' || x == '_' || x == '.')
+    {
+        return Err(AppError::InvalidArgument(format!(
+            "{} - Handles can only contain letters, digits, '-', '_' and '.' (e.g. jdoe), and cannot be none.",
+            handle
+        )));
+    }
+    Ok(handle.to_lowercase())
+}
+
+/// Check that given email address looks valid (a name and a domain joined by `@`)
+pub fn validate_email(email: &str) -> Result<String, AppError> {
+    let valid: bool = match email.split_once('@') {
+        Some((name, domain)) => {
+            !name.is_empty()
+                && domain.contains('.')
+                && !domain.starts_with('.')
+                && !domain.ends_with('.')
+                && !domain.contains('@')
+                && !email.contains(char::is_whitespace)
+        }
+        None => false,
+    };
+    match valid {
+        true => Ok(email.to_string()),
+        false => Err(AppError::InvalidArgument(format!(
+            "{} - Please enter a valid email address (e.g. jdoe@example.com).",
+            email
+        ))),
+    }
+}
+
+/// Get the handle of the current user if one is configured (None when `RUSTIC_BOARDS_USER` is not
+/// set), for features which also work without one
+pub fn optional_current_user() -> Result<Option<String>, AppError> {
+    match std::env::var(USER_ENV_VAR) {
+        Ok(s) if !s.is_empty() => Ok(Some(validate_handle(&s)?)),
+        _ => Ok(None),
+    }
+}
+
+/// Get the handle of the current user, configured with the `RUSTIC_BOARDS_USER` environment
+/// variable (so that everyone sharing a project board can have their own)
+pub fn current_user() -> Result<String, AppError> {
+    match std::env::var(USER_ENV_VAR) {
+        Ok(s) if !s.is_empty() => validate_handle(&s),
+        _ => Err(AppError::MissingArgument(format!(
+            "No current user configured. Please set {} to your handle from user list.",
+            USER_ENV_VAR
+        ))),
+    }
+}
@@ -1,11 +1,14 @@
 //! Defines the different user input prompts for interacting with the application
 
-use crate::{boards::KanbanBoard, error::AppError, utils::TaskPriority};
 use chrono::prelude::{Local, NaiveDate};
 use inquire::{
     formatter::DEFAULT_DATE_FORMATTER, ui::RenderConfig, validator::Validation, Confirm,
     CustomType, Select, Text,
 };
+use rustic_boards::{
+    boards::KanbanBoard, error::AppError, storage::Storage, swimlanes::LaneStage,
+    utils::TaskPriority,
+};
 
 /// Standard text prompt that returns the user string input
 pub fn text_input_prompt(message: &str, default: Option<&str>) -> Result<String, AppError> {
@@ -61,7 +64,7 @@ pub fn date_input_prompt(message: &str) -> Result<NaiveDate, AppError> {
         .with_formatter(DEFAULT_DATE_FORMATTER)
         .with_error_message("Please enter a valid date in dd/mm/yyyy format.")
         .with_validator(|val: &NaiveDate| {
-            if val < &Local::now().date_naive().into() {
+            if val < &Local::now().date_naive() {
                 Ok(Validation::Invalid(
                     "Entered date cannot be prior to current date.".into(),
                 ))
@@ -86,15 +89,49 @@ pub fn select_prompt(message: &str) -> Result<TaskPriority, AppError> {
     Ok(task_priority)
 }
 
-/// Select prompt to ask the user to select one option among different Task IDs
-pub fn tasks_select_prompt(message: &str, boards: &KanbanBoard) -> Result<String, AppError> {
-    let tasks_list: String = match Select::new(message, boards.get_tasks_list()?).prompt() {
+/// Select prompt to ask the user to select one option among the Task and SubTask IDs on the board
+pub fn tasks_select_prompt(
+    message: &str,
+    storage: &dyn Storage,
+    boards: &KanbanBoard,
+) -> Result<String, AppError> {
+    let tasks_list: String = match Select::new(message, boards.get_tasks_list(storage)?).prompt() {
         Ok(s) => s,
         Err(e) => return Err(AppError::SelectPromptError(e.to_string())),
     };
     Ok(tasks_list)
 }
 
+/// Select prompt to ask the user to select one option among different swimlane stages
+pub fn stage_select_prompt(message: &str) -> Result<LaneStage, AppError> {
+    let stages: Vec<LaneStage> = vec![LaneStage::Pending, LaneStage::Started, LaneStage::Done];
+    let lane_stage: LaneStage = match Select::new(message, stages).prompt() {
+        Ok(s) => s,
+        Err(e) => return Err(AppError::SelectPromptError(e.to_string())),
+    };
+    Ok(lane_stage)
+}
+
+/// Select prompt to ask the user to select one option among the swimlanes on the board
+/// (other than the given swimlane)
+pub fn swimlane_select_prompt(
+    message: &str,
+    boards: &KanbanBoard,
+    exclude: &str,
+) -> Result<String, AppError> {
+    let swimlanes: Vec<String> = boards
+        .swimlanes()
+        .iter()
+        .filter(|x| x.slug != exclude)
+        .map(|x| x.slug.clone())
+        .collect();
+    let swimlane: String = match Select::new(message, swimlanes).prompt() {
+        Ok(s) => s,
+        Err(e) => return Err(AppError::SelectPromptError(e.to_string())),
+    };
+    Ok(swimlane)
+}
+
 fn task_priority() -> Vec<TaskPriority> {
     vec![TaskPriority::Low, TaskPriority::Medium, TaskPriority::High]
 }
@@ -0,0 +1,274 @@
+//! Defines the sprints of a board (time-boxes with a goal which tasks are planned into), along with
+//! the reports shown for them
+
+use crate::{
+    boards::ItemRecord,
+    error::AppError,
+    estimates::EstimateTotals,
+    output::{self, Record, Section},
+    TimeStamp,
+};
+use chrono::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Stages a sprint goes through
+#[derive(Debug, Clone, Copy, Serialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SprintStatus {
+    /// Open sprint which has not started yet
+    Planned,
+
+    /// Open sprint which is running today
+    Active,
+
+    /// Open sprint whose last day has passed
+    Overdue,
+
+    /// Sprint which was closed
+    Closed,
+}
+
+impl std::fmt::Display for SprintStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SprintStatus::Planned => write!(f, "planned"),
+            SprintStatus::Active => write!(f, "active"),
+            SprintStatus::Overdue => write!(f, "overdue"),
+            SprintStatus::Closed => write!(f, "closed"),
+        }
+    }
+}
+
+/// Rust structure for a sprint of a board
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct Sprint {
+    /// Number of the sprint (sprints are numbered from 1 in the order they are created)
+    pub number: u32,
+
+    /// What the sprint is meant to achieve
+    pub goal: String,
+
+    /// First day of the sprint
+    pub start_on: TimeStamp,
+
+    /// Last day of the sprint
+    pub end_on: TimeStamp,
+
+    /// Task IDs planned into the sprint, in the order they were pulled in
+    pub items: Vec<String>,
+
+    /// Day the sprint was closed (None while it is open)
+    pub closed_on: Option<TimeStamp>,
+
+    /// Task IDs which were done when the sprint was closed
+    pub completed: Vec<String>,
+
+    /// Task IDs which were not done when the sprint was closed (carried over to the next sprint or
+    /// put back in the backlog)
+    pub carried_over: Vec<String>,
+}
+
+impl Sprint {
+    /// Create a new open sprint with given number, goal and days
+    pub fn new(
+        number: u32,
+        goal: &str,
+        start_on: NaiveDate,
+        end_on: NaiveDate,
+    ) -> Result<Self, AppError> {
+        if goal.trim().is_empty() {
+            return Err(AppError::InvalidArgument(
+                "Please enter a goal for the sprint.".to_string(),
+            ));
+        }
+        if end_on < start_on {
+            return Err(AppError::InvalidArgument(format!(
+                "{} - The end of a sprint cannot be prior to its start ({}).",
+                end_on.format("%d/%m/%Y"),
+                start_on.format("%d/%m/%Y")
+            )));
+        }
+        Ok(Sprint {
+            number,
+            goal: goal.trim().to_string(),
+            start_on: TimeStamp::convert(start_on),
+            end_on: TimeStamp::convert(end_on),
+            items: Vec::new(),
+            closed_on: None,
+            completed: Vec::new(),
+            carried_over: Vec::new(),
+        })
+    }
+
+    /// Check if the sprint is still open
+    pub fn is_open(&self) -> bool {
+        self.closed_on.is_none()
+    }
+
+    /// Check if given Task ID is planned into the sprint
+    pub fn contains(&self, item_id: &str) -> bool {
+        self.items.iter().any(|x| x == item_id)
+    }
+
+    /// Stage the sprint is in as of today
+    pub fn status(&self) -> Result<SprintStatus, AppError> {
+        let today: NaiveDate = TimeStamp::new().to_naivedate()?;
+        if !self.is_open() {
+            return Ok(SprintStatus::Closed);
+        }
+        if today < self.start_on.to_naivedate()? {
+            return Ok(SprintStatus::Planned);
+        }
+        match today > self.end_on.to_naivedate()? {
+            true => Ok(SprintStatus::Overdue),
+            false => Ok(SprintStatus::Active),
+        }
+    }
+
+    /// Describe the sprint for the heading of `sprint show`, e.g.
+    /// "Sprint 3: Ship the importer (Oct 12, 2026 - Oct 25, 2026) - 9 days left"
+    pub fn describe(&self) -> Result<String, AppError> {
+        let end_on: NaiveDate = self.end_on.to_naivedate()?;
+        let state: String = match self.status()? {
+            SprintStatus::Active => {
+                let today: NaiveDate = TimeStamp::new().to_naivedate()?;
+                match (end_on - today).num_days() + 1 {
+                    1 => "1 day left".to_string(),
+                    days => format!("{} days left", days),
+                }
+            }
+            status => status.to_string(),
+        };
+        Ok(format!(
+            "Sprint {}: {} ({} - {}) - {}",
+            self.number,
+            self.goal,
+            output::display_date(&Some(self.start_on.to_naivedate()?)),
+            output::display_date(&Some(end_on)),
+            state
+        ))
+    }
+}
+
+/// Sprint as listed by `sprint list`
+#[derive(Debug, Clone, Serialize)]
+pub struct SprintRecord {
+    /// Number of the sprint
+    pub number: u32,
+
+    /// What the sprint is meant to achieve
+    pub goal: String,
+
+    /// First day of the sprint
+    #[serde(serialize_with = "output::serialize_date")]
+    pub start_on: NaiveDate,
+
+    /// Last day of the sprint
+    #[serde(serialize_with = "output::serialize_date")]
+    pub end_on: NaiveDate,
+
+    /// Stage the sprint is in as of today
+    pub status: SprintStatus,
+
+    /// Number of tasks planned into the sprint
+    pub tasks: usize,
+
+    /// Number of those tasks which are done (or were done when the sprint was closed)
+    pub completed: usize,
+
+    /// Estimates of the tasks planned into the sprint (rolled up from their subtasks)
+    pub estimate: EstimateTotals,
+}
+
+impl SprintRecord {
+    /// Create the record for given sprint, with the number of its tasks done and their estimates
+    pub fn from_sprint(
+        sprint: &Sprint,
+        completed: usize,
+        estimate: EstimateTotals,
+    ) -> Result<SprintRecord, AppError> {
+        Ok(SprintRecord {
+            number: sprint.number,
+            goal: sprint.goal.clone(),
+            start_on: sprint.start_on.to_naivedate()?,
+            end_on: sprint.end_on.to_naivedate()?,
+            status: sprint.status()?,
+            tasks: sprint.items.len(),
+            completed,
+            estimate,
+        })
+    }
+}
+
+impl Record for SprintRecord {
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.number.to_string(),
+            self.goal.clone(),
+            output::display_date(&Some(self.start_on)),
+            output::display_date(&Some(self.end_on)),
+            self.status.to_string(),
+            self.tasks.to_string(),
+            self.completed.to_string(),
+            self.estimate.to_string(),
+        ]
+    }
+}
+
+/// Outcome of closing a sprint, as reported by `sprint close`
+#[derive(Debug, Clone)]
+pub struct SprintReport {
+    /// Sprint which was closed
+    pub sprint: Sprint,
+
+    /// Tasks which were done
+    pub completed: Vec<ItemRecord>,
+
+    /// Tasks which were not done
+    pub carried_over: Vec<ItemRecord>,
+
+    /// Number of the sprint the unfinished tasks were moved to (None if they went back to the
+    /// backlog)
+    pub carried_into: Option<u32>,
+}
+
+impl SprintReport {
+    /// Summary of the work done, e.g. "Sprint 3 closed: 4 of 6 tasks completed (13 of 18 pts)."
+    pub fn summary(&self) -> String {
+        let completed: u32 = self.completed.iter().map(|x| x.estimate.points).sum();
+        let carried_over: u32 = self.carried_over.iter().map(|x| x.estimate.points).sum();
+        let mut summary: String = format!(
+            "Sprint {} closed: {} of {} tasks completed",
+            self.sprint.number,
+            self.completed.len(),
+            self.completed.len() + self.carried_over.len()
+        );
+        if completed + carried_over > 0 {
+            summary.push_str(&format!(
+                " ({} of {} pts)",
+                completed,
+                completed + carried_over
+            ));
+        }
+        summary.push('.');
+        summary
+    }
+
+    /// Completed and carried-over tasks, each along with their story points
+    pub fn sections(&self) -> Vec<Section<ItemRecord>> {
+        let carried_over: String = match self.carried_into {
+            Some(s) => format!("Carried over to sprint {}", s),
+            None => "Back to the backlog".to_string(),
+        };
+        [
+            ("Completed".to_string(), &self.completed),
+            (carried_over, &self.carried_over),
+        ]
+        .into_iter()
+        .map(|(heading, records)| Section {
+            heading: ItemRecord::heading_with_points(&heading, records),
+            records: records.clone(),
+        })
+        .collect()
+    }
+}
@@ -0,0 +1,382 @@
+//! Defines the bincode storage backend, which keeps one bincode file per task and subtask
+//! along with separate files for the Kanban Board and notes
+
+use crate::{
+    boards::KanbanBoard,
+    constants::{
+        ACTIVE_SUBTASKS_PATH, ACTIVE_TASKS_PATH, ITEMS_PATH, KANBAN_BOARD_FILE, NOTES_FILE,
+        TAKS_LINK_FILE,
+    },
+    error::AppError,
+    items::WorkItem,
+    migrations::{self, RecordKind},
+    notes::TaskNotes,
+    storage::Storage,
+    utils::{create_dir, list_file_stems, record_write, write_file_atomic},
+};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+/// Rust structure for the bincode storage backend
+pub struct BincodeStorage {
+    /// Board directory holding all the bincode files
+    board_dir: PathBuf,
+}
+
+impl BincodeStorage {
+    /// Create new bincode storage in given board directory
+    pub fn new(board_dir: PathBuf) -> Self {
+        BincodeStorage { board_dir }
+    }
+
+    /// Open the bincode storage in given board directory, merging any tasks and subtasks kept in
+    /// the layout before work items were introduced
+    pub fn open(board_dir: PathBuf) -> Result<Self, AppError> {
+        let storage: BincodeStorage = BincodeStorage::new(board_dir);
+        storage.merge_items(false)?;
+        Ok(storage)
+    }
+
+    /// Get the file path for given file name in the board directory
+    fn file_path(&self, file_name: &str) -> PathBuf {
+        self.board_dir.join(file_name)
+    }
+
+    /// Get the file path for given Task or SubTask ID in given items directory
+    fn item_path(&self, items_dir: &str, item_id: &str) -> PathBuf {
+        self.board_dir
+            .join(items_dir)
+            .join(format!("{}.bin", item_id))
+    }
+
+    /// Read the raw contents of given file (None if the file is not present)
+    fn read_bytes(&self, file_path: &Path) -> Result<Option<Vec<u8>>, AppError> {
+        if !file_path.exists() {
+            return Ok(None);
+        }
+        match std::fs::read(file_path) {
+            Ok(s) => Ok(Some(s)),
+            Err(e) => Err(AppError::FileReadError(format!(
+                "{} - {}",
+                file_path.display(),
+                e
+            ))),
+        }
+    }
+
+    /// Read and deserialize given file (None if the file is not present).
+    /// Files written by an older version are upgraded and written back in the current format.
+    fn read_file<T: Serialize + DeserializeOwned>(
+        &self,
+        kind: RecordKind,
+        file_path: &Path,
+    ) -> Result<Option<T>, AppError> {
+        let data: Vec<u8> = match self.read_bytes(file_path)? {
+            Some(s) => s,
+            None => return Ok(None),
+        };
+        let (value, migrated): (T, bool) = migrations::deserialize(kind, &data)?;
+        if migrated {
+            self.write_file(file_path, &value)?;
+        }
+        Ok(Some(value))
+    }
+
+    /// Serialize and write given value to file
+    fn write_file<T: Serialize>(&self, file_path: &Path, value: &T) -> Result<(), AppError> {
+        let data: Vec<u8> = migrations::serialize(value)?;
+        write_file_atomic(file_path, &data)
+    }
+
+    /// Delete given file
+    fn delete_file(&self, file_path: &Path) -> Result<(), AppError> {
+        match std::fs::remove_file(file_path) {
+            Ok(_) => {
+                record_write();
+                Ok(())
+            }
+            Err(e) => Err(AppError::FileDeleteError(format!(
+                "{} - {}",
+                file_path.display(),
+                e
+            ))),
+        }
+    }
+
+    /// Read and deserialize given item file (error if the file is not present)
+    fn read_item(&self, item_id: &str) -> Result<WorkItem, AppError> {
+        match self.read_file(RecordKind::Item, &self.item_path(ITEMS_PATH, item_id))? {
+            Some(s) => Ok(s),
+            None => Err(AppError::FileReadError(format!(
+                "{} - {} not found",
+                ITEMS_PATH, item_id
+            ))),
+        }
+    }
+
+    /// Check the schema version of given file, upgrading it unless `check_only` is set.
+    /// Returns the description of pending migrations (None if the file is missing or up to date).
+    fn migrate_file<T: Serialize + DeserializeOwned>(
+        &self,
+        kind: RecordKind,
+        file_path: &Path,
+        check_only: bool,
+    ) -> Result<Option<String>, AppError> {
+        let data: Vec<u8> = match self.read_bytes(file_path)? {
+            Some(s) => s,
+            None => return Ok(None),
+        };
+        let change: Option<String> = migrations::describe_pending(
+            &file_path.display().to_string(),
+            migrations::read_version(&data)?,
+        );
+        if change.is_some() && !check_only {
+            self.read_file::<T>(kind, file_path)?;
+        }
+        Ok(change)
+    }
+
+    /// Move the tasks and subtasks kept in separate directories (up to schema version 9) into
+    /// work items, nesting each subtask under its parent task from the task-to-subtask links,
+    /// unless `check_only` is set. Returns the description of the change (None if there is no
+    /// data in the legacy layout).
+    fn merge_items(&self, check_only: bool) -> Result<Option<String>, AppError> {
+        let links_file: PathBuf = self.file_path(TAKS_LINK_FILE);
+        let tasks_dir: PathBuf = self.board_dir.join(ACTIVE_TASKS_PATH);
+        let subtasks_dir: PathBuf = self.board_dir.join(ACTIVE_SUBTASKS_PATH);
+        let task_ids: Vec<String> = list_file_stems(&tasks_dir, "bin")?;
+        let subtask_ids: Vec<String> = list_file_stems(&subtasks_dir, "bin")?;
+        if !links_file.exists() && task_ids.is_empty() && subtask_ids.is_empty() {
+            return Ok(None);
+        }
+
+        let change: String = format!(
+            "{} (legacy layout): Merge tasks and subtasks into work items with a parent pointer",
+            self.board_dir.display()
+        );
+        if check_only {
+            return Ok(Some(change));
+        }
+
+        let tasks_link: HashMap<String, Vec<String>> = match self.read_bytes(&links_file)? {
+            Some(data) => migrations::deserialize(RecordKind::Links, &data)?.0,
+            None => HashMap::new(),
+        };
+        let mut parents: HashMap<String, String> = HashMap::new();
+        for (task_id, subtasks_list) in tasks_link {
+            for subtask_id in subtasks_list {
+                parents.insert(subtask_id, task_id.clone());
+            }
+        }
+
+        // Each item is written in its new place before its legacy file is deleted
+        create_dir(&self.board_dir.join(ITEMS_PATH))?;
+        let legacy_items = task_ids
+            .into_iter()
+            .map(|x| (RecordKind::Task, ACTIVE_TASKS_PATH, x))
+            .chain(
+                subtask_ids
+                    .into_iter()
+                    .map(|x| (RecordKind::SubTask, ACTIVE_SUBTASKS_PATH, x)),
+            );
+        for (kind, items_dir, item_id) in legacy_items {
+            let item_file: PathBuf = self.item_path(items_dir, &item_id);
+            let mut work_item: WorkItem = match self.read_bytes(&item_file)? {
+                Some(data) => migrations::deserialize(kind, &data)?.0,
+                None => continue,
+            };
+            work_item.parent = match kind {
+                RecordKind::SubTask => parents.get(&item_id).cloned(),
+                _ => None,
+            };
+            self.write_file(&self.item_path(ITEMS_PATH, &item_id), &work_item)?;
+            self.delete_file(&item_file)?;
+        }
+
+        if links_file.exists() {
+            self.delete_file(&links_file)?;
+        }
+        // The legacy directories are only removed once empty, anything else in them is left alone
+        let _ = std::fs::remove_dir(&tasks_dir);
+        let _ = std::fs::remove_dir(&subtasks_dir);
+        Ok(Some(change))
+    }
+}
+
+impl Storage for BincodeStorage {
+    fn load_board(&self) -> Result<Option<KanbanBoard>, AppError> {
+        self.read_file(RecordKind::Board, &self.file_path(KANBAN_BOARD_FILE))
+    }
+
+    fn save_board(&self, boards: &KanbanBoard) -> Result<(), AppError> {
+        self.write_file(&self.file_path(KANBAN_BOARD_FILE), boards)
+    }
+
+    fn load_notes(&self) -> Result<Option<TaskNotes>, AppError> {
+        self.read_file(RecordKind::Notes, &self.file_path(NOTES_FILE))
+    }
+
+    fn save_notes(&self, task_notes: &TaskNotes) -> Result<(), AppError> {
+        self.write_file(&self.file_path(NOTES_FILE), task_notes)
+    }
+
+    fn get_item(&self, item_id: &str) -> Result<WorkItem, AppError> {
+        self.read_item(item_id)
+    }
+
+    fn save_item(&self, work_item: &WorkItem) -> Result<(), AppError> {
+        self.write_file(&self.item_path(ITEMS_PATH, &work_item.id), work_item)
+    }
+
+    fn delete_item(&self, item_id: &str) -> Result<(), AppError> {
+        self.delete_file(&self.item_path(ITEMS_PATH, item_id))
+    }
+
+    fn item_exists(&self, item_id: &str) -> Result<bool, AppError> {
+        Ok(self.item_path(ITEMS_PATH, item_id).exists())
+    }
+
+    fn list_items(&self) -> Result<Vec<String>, AppError> {
+        list_file_stems(&self.board_dir.join(ITEMS_PATH), "bin")
+    }
+
+    fn migrate(&self, check_only: bool) -> Result<Vec<String>, AppError> {
+        let mut changes: Vec<String> = Vec::new();
+        changes.extend(self.merge_items(check_only)?);
+        let board_file: PathBuf = self.file_path(KANBAN_BOARD_FILE);
+        changes.extend(self.migrate_file::<KanbanBoard>(
+            RecordKind::Board,
+            &board_file,
+            check_only,
+        )?);
+        let notes_file: PathBuf = self.file_path(NOTES_FILE);
+        changes.extend(self.migrate_file::<TaskNotes>(
+            RecordKind::Notes,
+            &notes_file,
+            check_only,
+        )?);
+
+        // Read the board from raw bytes as it may not have been upgraded in check mode
+        let boards: KanbanBoard = match self.read_bytes(&board_file)? {
+            Some(data) => migrations::deserialize::<KanbanBoard>(RecordKind::Board, &data)?.0,
+            None => return Ok(changes),
+        };
+        for item_id in boards.get_stored_items() {
+            let item_file: PathBuf = self.item_path(ITEMS_PATH, &item_id);
+            changes.extend(self.migrate_file::<WorkItem>(
+                RecordKind::Item,
+                &item_file,
+                check_only,
+            )?);
+        }
+        Ok(changes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{migrations::SCHEMA_VERSION, utils::test_dir, TaskPriority};
+    use std::collections::BTreeMap;
+
+    /// Date as written before the format header was introduced (year, month, day)
+    type DateV0 = (i32, u32, u32);
+
+    /// Write given value as bincode without a format header, the way the first version did
+    fn write_v0<T: Serialize>(file_path: &Path, value: &T) {
+        std::fs::write(file_path, bincode::serialize(value).unwrap()).unwrap();
+    }
+
+    /// Write a task or subtask file the way the first version did, with given status (index of
+    /// to-do, in-progress, blocked, in-review, done) and priority (index of high, medium, low)
+    fn write_item_v0(file_path: &Path, item_id: &str, status: u32, priority: u32) {
+        let added_on: DateV0 = (2024, 3, 1);
+        let item: (&str, &str, &str, DateV0, Option<DateV0>, Option<DateV0>) = (
+            item_id,
+            item_id,
+            "Written by the first version",
+            added_on,
+            None,
+            Some((2024, 4, 1)),
+        );
+        let rest: (Option<DateV0>, u32, u32) = (None, status, priority);
+        let mut data: Vec<u8> = bincode::serialize(&item).unwrap();
+        data.extend(bincode::serialize(&rest).unwrap());
+        std::fs::write(file_path, data).unwrap();
+    }
+
+    #[test]
+    fn first_version_files_are_migrated_and_round_trip() {
+        let board_dir: PathBuf = test_dir("bincode_migration");
+        for items_dir in [ACTIVE_TASKS_PATH, ACTIVE_SUBTASKS_PATH] {
+            create_dir(&board_dir.join(items_dir)).unwrap();
+        }
+        let board_v0: BTreeMap<u32, Vec<&str>> = BTreeMap::from([
+            (0, vec!["TASK-1"]),
+            (1, vec![]),
+            (2, vec!["TASK-1.1"]),
+            (3, vec![]),
+            (4, vec![]),
+        ]);
+        write_v0(&board_dir.join(KANBAN_BOARD_FILE), &board_v0);
+        write_v0(
+            &board_dir.join(TAKS_LINK_FILE),
+            &HashMap::from([("TASK-1", vec!["TASK-1.1"])]),
+        );
+        write_v0(
+            &board_dir.join(NOTES_FILE),
+            &HashMap::from([("TASK-1.1", vec!["Waiting on review"])]),
+        );
+        write_item_v0(
+            &board_dir.join(ACTIVE_TASKS_PATH).join("TASK-1.bin"),
+            "TASK-1",
+            0,
+            0,
+        );
+        write_item_v0(
+            &board_dir.join(ACTIVE_SUBTASKS_PATH).join("TASK-1.1.bin"),
+            "TASK-1.1",
+            2,
+            2,
+        );
+
+        let storage: BincodeStorage = BincodeStorage::new(board_dir.clone());
+        let pending: Vec<String> = storage.migrate(true).unwrap();
+        assert_eq!(pending.len(), 3, "{:?}", pending);
+        assert!(board_dir.join(ACTIVE_TASKS_PATH).exists());
+        assert_eq!(storage.migrate(false).unwrap(), pending);
+        assert!(storage.migrate(true).unwrap().is_empty());
+        assert!(!board_dir.join(ACTIVE_TASKS_PATH).exists());
+        assert!(!board_dir.join(TAKS_LINK_FILE).exists());
+
+        let boards: KanbanBoard = storage.load_board().unwrap().unwrap();
+        let slugs: Vec<&str> = boards.swimlanes.iter().map(|x| x.slug.as_str()).collect();
+        assert_eq!(
+            slugs,
+            ["to-do", "in-progress", "blocked", "in-review", "done"]
+        );
+        assert_eq!(boards.swimlanes[0].items, ["TASK-1"]);
+        assert_eq!(boards.swimlanes[2].items, ["TASK-1.1"]);
+        assert!(boards.swimlanes[2].blocked);
+        assert_eq!(boards.id_sequence.prefix, "TASK");
+
+        let subtask_item: WorkItem = storage.get_item("TASK-1.1").unwrap();
+        assert_eq!(subtask_item.parent, Some("TASK-1".to_string()));
+        assert_eq!(subtask_item.status, "blocked");
+        assert_eq!(subtask_item.priority, TaskPriority::Low);
+        assert_eq!(subtask_item.description, "Written by the first version");
+        assert!(subtask_item.tags.is_empty() && subtask_item.estimate.is_none());
+        let task_notes: TaskNotes = storage.load_notes().unwrap().unwrap();
+        assert_eq!(task_notes.notes["TASK-1.1"], ["Waiting on review"]);
+
+        let data: Vec<u8> = std::fs::read(board_dir.join(KANBAN_BOARD_FILE)).unwrap();
+        assert_eq!(migrations::read_version(&data).unwrap(), SCHEMA_VERSION);
+        storage.save_board(&boards).unwrap();
+        storage.save_item(&subtask_item).unwrap();
+        assert_eq!(storage.load_board().unwrap(), Some(boards));
+        assert_eq!(storage.get_item("TASK-1.1").unwrap(), subtask_item);
+    }
+}
@@ -0,0 +1,191 @@
+//! Defines the storage trait through which all Kanban Board data is persisted, along with the
+//! available storage backends
+
+pub mod bincode_store;
+pub mod sqlite_store;
+pub mod text_store;
+pub mod unit_of_work;
+
+use crate::{
+    boards::KanbanBoard,
+    config::AppConfig,
+    constants::{DEFAULT_BOARD, SQLITE_DB_FILE},
+    error::AppError,
+    items::WorkItem,
+    migrations,
+    notes::TaskNotes,
+    utils::{create_app_dirs, create_board_dirs, get_board_dir},
+};
+use bincode_store::BincodeStorage;
+use serde::{Deserialize, Serialize};
+use sqlite_store::SqliteStorage;
+use std::path::PathBuf;
+use text_store::TextStorage;
+
+/// Storage backend for persisting the Kanban Board, notes and work items (tasks and subtasks)
+pub trait Storage {
+    /// Load the Kanban Board (None if it was never stored)
+    fn load_board(&self) -> Result<Option<KanbanBoard>, AppError>;
+
+    /// Store the Kanban Board
+    fn save_board(&self, boards: &KanbanBoard) -> Result<(), AppError>;
+
+    /// Load the notes for Tasks and SubTasks (None if they were never stored)
+    fn load_notes(&self) -> Result<Option<TaskNotes>, AppError>;
+
+    /// Store the notes for Tasks and SubTasks
+    fn save_notes(&self, task_notes: &TaskNotes) -> Result<(), AppError>;
+
+    /// Fetch the item information for given Task or SubTask ID
+    fn get_item(&self, item_id: &str) -> Result<WorkItem, AppError>;
+
+    /// Store the item information
+    fn save_item(&self, work_item: &WorkItem) -> Result<(), AppError>;
+
+    /// Delete the item information for given Task or SubTask ID
+    fn delete_item(&self, item_id: &str) -> Result<(), AppError>;
+
+    /// Check if the item information is present for given Task or SubTask ID
+    fn item_exists(&self, item_id: &str) -> Result<bool, AppError>;
+
+    /// List the Task and SubTask IDs of all stored items
+    fn list_items(&self) -> Result<Vec<String>, AppError>;
+
+    /// Upgrade all stored data to the current schema version (only report what would change when
+    /// `check_only` is set). Returns a description of each pending migration.
+    fn migrate(&self, check_only: bool) -> Result<Vec<String>, AppError>;
+
+    /// Start holding all writes back until `commit_transaction` is called. Returns false if the
+    /// backend has no transactions, in which case every write is applied as it is made.
+    fn begin_transaction(&self) -> Result<bool, AppError> {
+        Ok(false)
+    }
+
+    /// Apply all writes made since `begin_transaction` together
+    fn commit_transaction(&self) -> Result<(), AppError> {
+        Ok(())
+    }
+
+    /// Discard all writes made since `begin_transaction`
+    fn rollback_transaction(&self) -> Result<(), AppError> {
+        Ok(())
+    }
+}
+
+/// Available storage backends
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, Eq, PartialEq)]
+pub enum StorageBackend {
+    /// One bincode file per task and subtask, along with separate files for board and notes
+    #[default]
+    Bincode,
+
+    /// Embedded SQLite database
+    Sqlite,
+
+    /// One Markdown file per task and subtask, along with a TOML index file for the board
+    Text,
+}
+
+impl std::fmt::Display for StorageBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StorageBackend::Bincode => write!(f, "bincode"),
+            StorageBackend::Sqlite => write!(f, "sqlite"),
+            StorageBackend::Text => write!(f, "text"),
+        }
+    }
+}
+
+impl std::str::FromStr for StorageBackend {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bincode" => Ok(StorageBackend::Bincode),
+            "sqlite" => Ok(StorageBackend::Sqlite),
+            "text" => Ok(StorageBackend::Text),
+            _ => Err(AppError::InvalidArgument(format!(
+                "{} \nPlease select from following storage backends: \n1) bincode 2) sqlite 3) text\n",
+                s
+            ))),
+        }
+    }
+}
+
+/// Open the given storage backend for given board
+pub fn open_backend(
+    backend: StorageBackend,
+    board_name: &str,
+) -> Result<Box<dyn Storage>, AppError> {
+    let board_dir: PathBuf = create_board_dirs(board_name)?;
+    match backend {
+        StorageBackend::Bincode => Ok(Box::new(BincodeStorage::open(board_dir)?)),
+        StorageBackend::Sqlite => Ok(Box::new(SqliteStorage::open(&board_dir)?)),
+        StorageBackend::Text => Ok(Box::new(TextStorage::open(board_dir)?)),
+    }
+}
+
+/// Open the configured storage backend for the active board, moving any board data kept in the
+/// legacy layout into the default board first
+pub fn open_storage(app_config: &AppConfig) -> Result<Box<dyn Storage>, AppError> {
+    migrations::migrate_layout(&create_app_dirs()?, false)?;
+    open_backend(app_config.storage_backend, &app_config.active_board)
+}
+
+/// Upgrade the application configuration and the data of all boards to the current schema version
+/// (only report what would change when `check_only` is set).
+/// Returns a description of each pending migration.
+pub fn migrate_storage(check_only: bool) -> Result<Vec<String>, AppError> {
+    let mut changes: Vec<String> = AppConfig::migrate(check_only)?;
+    let app_config: AppConfig = match check_only {
+        true => AppConfig::read()?,
+        false => AppConfig::load()?,
+    };
+    let app_dir: PathBuf = create_app_dirs()?;
+    let layout_change: Option<String> = migrations::migrate_layout(&app_dir, check_only)?;
+    let legacy_layout: bool = layout_change.is_some();
+    changes.extend(layout_change);
+
+    for board_name in &app_config.boards {
+        // Data in the legacy layout is only moved into the default board when not checking
+        let board_dir: PathBuf = match legacy_layout && check_only && board_name == DEFAULT_BOARD {
+            true => app_dir.clone(),
+            false => get_board_dir(board_name)?,
+        };
+        let storage: Box<dyn Storage> = match app_config.storage_backend {
+            StorageBackend::Bincode => Box::new(BincodeStorage::new(board_dir)),
+            StorageBackend::Sqlite => {
+                if !board_dir.join(SQLITE_DB_FILE).exists() {
+                    continue;
+                }
+                Box::new(SqliteStorage::connect(&board_dir)?)
+            }
+            StorageBackend::Text => Box::new(TextStorage::new(board_dir)),
+        };
+        changes.extend(storage.migrate(check_only)?);
+    }
+    Ok(changes)
+}
+
+/// Copy the Kanban Board along with all tasks, subtasks and notes from one storage to another.
+/// Returns the number of tasks and subtasks copied.
+pub fn copy_storage(from: &dyn Storage, to: &dyn Storage) -> Result<usize, AppError> {
+    let boards: KanbanBoard = match from.load_board()? {
+        Some(s) => s,
+        None => return Ok(0),
+    };
+
+    let mut items_copied: usize = 0;
+    for item_id in boards.get_stored_items() {
+        if from.item_exists(&item_id)? {
+            to.save_item(&from.get_item(&item_id)?)?;
+            items_copied += 1;
+        }
+    }
+
+    to.save_board(&boards)?;
+    if let Some(task_notes) = from.load_notes()? {
+        to.save_notes(&task_notes)?;
+    }
+    Ok(items_copied)
+}
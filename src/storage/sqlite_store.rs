@@ -0,0 +1,1174 @@
+//! Defines the SQLite storage backend, which keeps the Kanban Board, tasks, subtasks and notes in
+//! an embedded database with foreign keys between the tables
+
+use crate::{
+    boards::{HiddenItem, KanbanBoard, Shelf},
+    constants::{DEFAULT_ID_PREFIX, SQLITE_DB_FILE},
+    dependencies::Dependencies,
+    error::AppError,
+    estimates::{Estimate, EstimateUnit},
+    ids::IdSequence,
+    items::WorkItem,
+    migrations::SCHEMA_VERSION,
+    notes::TaskNotes,
+    people::Person,
+    sprints::Sprint,
+    storage::Storage,
+    swimlanes::{LaneStage, Swimlane, WipLimit},
+    tags::TagColor,
+    utils::{record_write, TaskPriority, TimeStamp},
+    worklog::{RunningTimer, WorkLog},
+};
+use chrono::prelude::NaiveDate;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+};
+
+/// Database schema for the SQLite storage backend
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS items (
+        item_id      TEXT PRIMARY KEY,
+        item_kind    TEXT NOT NULL CHECK (item_kind IN ('task', 'subtask')),
+        name         TEXT NOT NULL,
+        description  TEXT NOT NULL,
+        added_on     TEXT NOT NULL,
+        started_on   TEXT,
+        deadline     TEXT,
+        completed_on TEXT,
+        status       TEXT NOT NULL,
+        priority     TEXT NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS swimlanes (
+        status TEXT PRIMARY KEY
+    );
+    CREATE TABLE IF NOT EXISTS board_items (
+        status   TEXT NOT NULL REFERENCES swimlanes (status) ON DELETE CASCADE,
+        position INTEGER NOT NULL,
+        item_id  TEXT NOT NULL REFERENCES items (item_id) ON DELETE CASCADE,
+        PRIMARY KEY (status, position)
+    );
+    CREATE TABLE IF NOT EXISTS task_links (
+        task_id    TEXT NOT NULL REFERENCES items (item_id) ON DELETE CASCADE,
+        position   INTEGER NOT NULL,
+        subtask_id TEXT NOT NULL REFERENCES items (item_id) ON DELETE CASCADE,
+        PRIMARY KEY (task_id, position)
+    );
+    CREATE TABLE IF NOT EXISTS notes (
+        item_id  TEXT NOT NULL REFERENCES items (item_id) ON DELETE CASCADE,
+        position INTEGER NOT NULL,
+        note     TEXT NOT NULL,
+        PRIMARY KEY (item_id, position)
+    );
+    CREATE TABLE IF NOT EXISTS setup (
+        component TEXT PRIMARY KEY
+    );
+";
+
+/// Schema migrations for the SQLite database in ascending order of schema version, each holding
+/// the version produced, a description and the statements to run.
+/// The schema version of the database is kept in `PRAGMA user_version`.
+const SCHEMA_MIGRATIONS: &[(u32, &str, &str)] = &[
+    (
+        1,
+        "Create tables for the board, items, links and notes",
+        SCHEMA,
+    ),
+    (
+        3,
+        "Replace hard-coded task status with user-defined swimlanes",
+        SWIMLANES_SCHEMA,
+    ),
+    (
+        4,
+        "Add work-in-progress limits to swimlanes",
+        WIP_LIMITS_SCHEMA,
+    ),
+    (
+        5,
+        "Add sequential Task and SubTask IDs per board",
+        ID_SEQUENCE_SCHEMA,
+    ),
+    (6, "Add trash and archive to boards", HIDDEN_ITEMS_SCHEMA),
+    (7, "Add tags to tasks and subtasks", TAGS_SCHEMA),
+    (8, "Add people and assignees to boards", PEOPLE_SCHEMA),
+    (
+        9,
+        "Add dependencies between tasks and subtasks",
+        DEPENDENCIES_SCHEMA,
+    ),
+    (
+        10,
+        "Merge tasks and subtasks into work items with a parent pointer",
+        PARENTS_SCHEMA,
+    ),
+    (
+        11,
+        "Add worklogs to tasks and subtasks and timers to boards",
+        WORKLOGS_SCHEMA,
+    ),
+    (
+        12,
+        "Add estimates and remaining effort to tasks and subtasks",
+        ESTIMATES_SCHEMA,
+    ),
+    (13, "Add sprints to boards", SPRINTS_SCHEMA),
+    (
+        14,
+        "Mark the swimlanes holding blocked tasks and subtasks",
+        BLOCKED_SWIMLANES_SCHEMA,
+    ),
+];
+
+/// Columns describing user-defined swimlanes, filled in for the swimlanes which used to be hard-coded
+const SWIMLANES_SCHEMA: &str = "
+    ALTER TABLE swimlanes ADD COLUMN name TEXT NOT NULL DEFAULT '';
+    ALTER TABLE swimlanes ADD COLUMN position INTEGER NOT NULL DEFAULT 0;
+    ALTER TABLE swimlanes ADD COLUMN stage TEXT NOT NULL DEFAULT 'started';
+    UPDATE swimlanes SET name = 'To-Do', position = 0, stage = 'pending' WHERE status = 'to-do';
+    UPDATE swimlanes SET name = 'In Progress', position = 1 WHERE status = 'in-progress';
+    UPDATE swimlanes SET name = 'Blocked', position = 2 WHERE status = 'blocked';
+    UPDATE swimlanes SET name = 'In Review', position = 3 WHERE status = 'in-review';
+    UPDATE swimlanes SET name = 'Done', position = 4, stage = 'done' WHERE status = 'done';
+";
+
+/// Columns holding the WIP limit of a swimlane (no limit when `wip_limit` is NULL)
+const WIP_LIMITS_SCHEMA: &str = "
+    ALTER TABLE swimlanes ADD COLUMN wip_limit INTEGER;
+    ALTER TABLE swimlanes ADD COLUMN wip_strict INTEGER NOT NULL DEFAULT 1;
+";
+
+/// Tables holding the ID sequence of the board. `id_sequences` holds the last number handed out
+/// for Task IDs (empty `task_id`) and for SubTask IDs under each parent Task ID.
+const ID_SEQUENCE_SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS board_settings (
+        name  TEXT PRIMARY KEY,
+        value TEXT NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS id_sequences (
+        task_id     TEXT PRIMARY KEY,
+        last_number INTEGER NOT NULL
+    );
+";
+
+/// Table holding the tasks and subtasks in the trash or the archive (`shelf`). `swimlane` is not a
+/// reference, as the swimlane an item is put back into may be removed in the meantime.
+const HIDDEN_ITEMS_SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS hidden_items (
+        shelf       TEXT NOT NULL CHECK (shelf IN ('trash', 'archive')),
+        position    INTEGER NOT NULL,
+        item_id     TEXT NOT NULL REFERENCES items (item_id) ON DELETE CASCADE,
+        swimlane    TEXT NOT NULL,
+        hidden_on   TEXT NOT NULL,
+        hidden_with TEXT,
+        PRIMARY KEY (shelf, position)
+    );
+";
+
+/// Tables holding the tags of each task and subtask (in the order they were added) and the colors
+/// given to tags on the board
+const TAGS_SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS item_tags (
+        item_id  TEXT NOT NULL REFERENCES items (item_id) ON DELETE CASCADE,
+        position INTEGER NOT NULL,
+        tag      TEXT NOT NULL,
+        PRIMARY KEY (item_id, position)
+    );
+    CREATE TABLE IF NOT EXISTS tag_colors (
+        tag   TEXT PRIMARY KEY,
+        color TEXT NOT NULL
+    );
+";
+
+/// Table holding the people registered on the board (in the order they were added) and the column
+/// holding the handle of the person each task or subtask is assigned to. `assignee` is not a
+/// reference, so that removing a person from a shared board never drops items along with them.
+const PEOPLE_SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS people (
+        handle   TEXT PRIMARY KEY,
+        position INTEGER NOT NULL,
+        name     TEXT NOT NULL,
+        email    TEXT
+    );
+    ALTER TABLE items ADD COLUMN assignee TEXT;
+";
+
+/// Table holding the Task and SubTask IDs blocking each task or subtask, in the order they were
+/// added
+const DEPENDENCIES_SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS dependencies (
+        item_id    TEXT NOT NULL,
+        position   INTEGER NOT NULL,
+        blocker_id TEXT NOT NULL,
+        PRIMARY KEY (item_id, position)
+    );
+";
+
+/// Column holding the ID of the item each task or subtask is nested under (NULL for tasks), filled
+/// in from the task-to-subtask links which it replaces. `parent_id` is not a reference, so that
+/// deleting an item never drops the items nested under it along with it.
+const PARENTS_SCHEMA: &str = "
+    ALTER TABLE items ADD COLUMN parent_id TEXT;
+    UPDATE items SET parent_id = (
+        SELECT task_id FROM task_links WHERE task_links.subtask_id = items.item_id LIMIT 1
+    );
+    DROP TABLE task_links;
+    ALTER TABLE items DROP COLUMN item_kind;
+    DELETE FROM setup WHERE component = 'links';
+";
+
+/// Tables holding the time logged against each task or subtask (in the order it was logged) and the
+/// timers running on the board (in the order they were started). `user_handle` is empty for the
+/// timer started without a current user.
+const WORKLOGS_SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS worklogs (
+        item_id   TEXT NOT NULL REFERENCES items (item_id) ON DELETE CASCADE,
+        position  INTEGER NOT NULL,
+        logged_on TEXT NOT NULL,
+        minutes   INTEGER NOT NULL,
+        message   TEXT NOT NULL,
+        author    TEXT,
+        PRIMARY KEY (item_id, position)
+    );
+    CREATE TABLE IF NOT EXISTS timers (
+        user_handle TEXT PRIMARY KEY,
+        position    INTEGER NOT NULL,
+        item_id     TEXT NOT NULL,
+        started_at  INTEGER NOT NULL
+    );
+";
+
+/// Columns holding the estimate of each task or subtask and the effort remaining on it, in story
+/// points or minutes depending on `estimate_unit` (no estimate when it is NULL)
+const ESTIMATES_SCHEMA: &str = "
+    ALTER TABLE items ADD COLUMN estimate_unit TEXT CHECK (estimate_unit IN ('points', 'time'));
+    ALTER TABLE items ADD COLUMN estimate INTEGER;
+    ALTER TABLE items ADD COLUMN remaining INTEGER;
+";
+
+/// Tables holding the sprints of the board and the Task IDs in each of them, in the order they were
+/// added to one of its lists (`planned`, `completed` or `carried_over`). `item_id` is not a
+/// reference, so that tasks in the trash stay in the sprints they were planned into.
+const SPRINTS_SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS sprints (
+        number    INTEGER PRIMARY KEY,
+        goal      TEXT NOT NULL,
+        start_on  TEXT NOT NULL,
+        end_on    TEXT NOT NULL,
+        closed_on TEXT
+    );
+    CREATE TABLE IF NOT EXISTS sprint_items (
+        number   INTEGER NOT NULL REFERENCES sprints (number) ON DELETE CASCADE,
+        list     TEXT NOT NULL CHECK (list IN ('planned', 'completed', 'carried_over')),
+        position INTEGER NOT NULL,
+        item_id  TEXT NOT NULL,
+        PRIMARY KEY (number, list, position)
+    );
+";
+
+/// Column marking the swimlanes holding tasks and subtasks waiting on their blockers, set for the
+/// swimlane which used to be recognized by its slug
+const BLOCKED_SWIMLANES_SCHEMA: &str = "
+    ALTER TABLE swimlanes ADD COLUMN blocked INTEGER NOT NULL DEFAULT 0;
+    UPDATE swimlanes SET blocked = 1 WHERE status = 'blocked';
+";
+
+/// Columns selected when reading a task or subtask row
+const ITEM_COLUMNS: &str = "item_id, name, description, added_on, started_on, deadline, \
+     completed_on, status, priority, assignee, parent_id, estimate_unit, estimate, remaining";
+
+/// Rust structure for the SQLite storage backend
+pub struct SqliteStorage {
+    /// Connection to the SQLite database
+    conn: Connection,
+}
+
+impl SqliteStorage {
+    /// Open (creating if required) the SQLite database in given board directory, upgrading it to
+    /// the current schema version
+    pub fn open(board_dir: &Path) -> Result<Self, AppError> {
+        let storage: SqliteStorage = SqliteStorage::connect(board_dir)?;
+        storage.migrate(false)?;
+        Ok(storage)
+    }
+
+    /// Connect to the SQLite database in given board directory without upgrading it
+    pub fn connect(board_dir: &Path) -> Result<Self, AppError> {
+        let db_path: PathBuf = board_dir.join(SQLITE_DB_FILE);
+        let conn: Connection = match Connection::open(&db_path) {
+            Ok(s) => s,
+            Err(e) => {
+                return Err(AppError::DatabaseError(format!(
+                    "{} - {}",
+                    db_path.display(),
+                    e
+                )))
+            }
+        };
+        db_result(conn.execute_batch("PRAGMA foreign_keys = ON;"))?;
+        Ok(SqliteStorage { conn })
+    }
+
+    /// Read the schema version of the database
+    fn schema_version(&self) -> Result<u32, AppError> {
+        let version: u32 = db_result(
+            self.conn
+                .query_row("PRAGMA user_version", [], |row| row.get(0)),
+        )?;
+        if version > SCHEMA_VERSION {
+            return Err(AppError::UnsupportedSchemaVersion(format!(
+                "{} has schema version {} but this version of the application supports up to {}. Please upgrade rustic_boards.",
+                SQLITE_DB_FILE, version, SCHEMA_VERSION
+            )));
+        }
+        Ok(version)
+    }
+
+    /// Check if given component (board, notes) was ever stored
+    fn is_setup(&self, component: &str) -> Result<bool, AppError> {
+        let found: Option<String> = db_result(
+            self.conn
+                .query_row(
+                    "SELECT component FROM setup WHERE component = ?1",
+                    params![component],
+                    |row| row.get(0),
+                )
+                .optional(),
+        )?;
+        Ok(found.is_some())
+    }
+
+    /// Mark given component (board, notes) as stored
+    fn mark_setup(&self, component: &str) -> Result<(), AppError> {
+        db_result(self.conn.execute(
+            "INSERT OR IGNORE INTO setup (component) VALUES (?1)",
+            params![component],
+        ))?;
+        record_write();
+        Ok(())
+    }
+
+    /// Fetch the tags of given Task or SubTask ID
+    fn get_tags(&self, item_id: &str) -> Result<Vec<String>, AppError> {
+        let mut stmt = db_result(
+            self.conn
+                .prepare("SELECT tag FROM item_tags WHERE item_id = ?1 ORDER BY position"),
+        )?;
+        let tags: Vec<String> = db_result(
+            db_result(stmt.query_map(params![item_id], |row| row.get(0)))?
+                .collect::<rusqlite::Result<_>>(),
+        )?;
+        Ok(tags)
+    }
+
+    /// Fetch the time logged against given Task or SubTask ID
+    fn get_worklog(&self, item_id: &str) -> Result<Vec<WorkLog>, AppError> {
+        let mut stmt = db_result(self.conn.prepare(
+            "SELECT logged_on, minutes, message, author FROM worklogs WHERE item_id = ?1
+             ORDER BY position",
+        ))?;
+        let worklog: Vec<WorkLog> = db_result(
+            db_result(stmt.query_map(params![item_id], |row| {
+                Ok(WorkLog {
+                    logged_on: date_from_sql(row.get(0)?)?,
+                    minutes: row.get(1)?,
+                    message: row.get(2)?,
+                    author: row.get(3)?,
+                })
+            }))?
+            .collect::<rusqlite::Result<_>>(),
+        )?;
+        Ok(worklog)
+    }
+
+    /// Run given list of statements in a single database transaction. Inside the transaction begun
+    /// for a unit of work, they are committed along with the rest of it.
+    fn in_transaction<F>(&self, statements: F) -> Result<(), AppError>
+    where
+        F: FnOnce(&Connection) -> rusqlite::Result<()>,
+    {
+        if !self.conn.is_autocommit() {
+            return db_result(statements(&self.conn));
+        }
+        let tx = db_result(self.conn.unchecked_transaction())?;
+        db_result(statements(&tx))?;
+        db_result(tx.commit())?;
+        record_write();
+        Ok(())
+    }
+}
+
+impl Storage for SqliteStorage {
+    fn load_board(&self) -> Result<Option<KanbanBoard>, AppError> {
+        if !self.is_setup("board")? {
+            return Ok(None);
+        }
+
+        let mut stmt = db_result(self.conn.prepare(
+            "SELECT status, name, stage, blocked, wip_limit, wip_strict FROM swimlanes
+             ORDER BY position",
+        ))?;
+        let rows: Vec<(String, String, String, bool, Option<i64>, bool)> = db_result(
+            db_result(stmt.query_map([], |row| {
+                Ok((
+                    row.get(0)?,
+                    row.get(1)?,
+                    row.get(2)?,
+                    row.get(3)?,
+                    row.get(4)?,
+                    row.get(5)?,
+                ))
+            }))?
+            .collect::<rusqlite::Result<_>>(),
+        )?;
+        let mut swimlanes: Vec<Swimlane> = Vec::new();
+        for (slug, name, stage, blocked, wip_limit, wip_strict) in rows {
+            swimlanes.push(Swimlane {
+                slug,
+                name,
+                stage: stage.parse::<LaneStage>()?,
+                blocked,
+                wip_limit: wip_limit.map(|x| WipLimit {
+                    max_items: x as usize,
+                    strict: wip_strict,
+                }),
+                items: Vec::new(),
+            });
+        }
+
+        let mut stmt = db_result(
+            self.conn
+                .prepare("SELECT status, item_id FROM board_items ORDER BY status, position"),
+        )?;
+        let rows: Vec<(String, String)> = db_result(
+            db_result(stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?))))?
+                .collect::<rusqlite::Result<_>>(),
+        )?;
+        for (slug, item_id) in rows {
+            if let Some(swimlane) = swimlanes.iter_mut().find(|x| x.slug == slug) {
+                swimlane.items.push(item_id);
+            }
+        }
+
+        let prefix: Option<String> = db_result(
+            self.conn
+                .query_row(
+                    "SELECT value FROM board_settings WHERE name = 'id_prefix'",
+                    [],
+                    |row| row.get(0),
+                )
+                .optional(),
+        )?;
+        let mut id_sequence: IdSequence =
+            IdSequence::new(prefix.as_deref().unwrap_or(DEFAULT_ID_PREFIX));
+        let mut stmt = db_result(
+            self.conn
+                .prepare("SELECT task_id, last_number FROM id_sequences"),
+        )?;
+        let rows: Vec<(String, i64)> = db_result(
+            db_result(stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?))))?
+                .collect::<rusqlite::Result<_>>(),
+        )?;
+        for (task_id, last_number) in rows {
+            match task_id.is_empty() {
+                true => id_sequence.last_task_number = last_number as u64,
+                false => {
+                    id_sequence
+                        .last_subtask_numbers
+                        .insert(task_id, last_number as u64);
+                }
+            }
+        }
+
+        let mut stmt = db_result(self.conn.prepare(
+            "SELECT shelf, item_id, swimlane, hidden_on, hidden_with FROM hidden_items
+             ORDER BY shelf, position",
+        ))?;
+        let rows: Vec<(String, HiddenItem)> = db_result(
+            db_result(stmt.query_map([], |row| {
+                Ok((
+                    row.get(0)?,
+                    HiddenItem {
+                        item_id: row.get(1)?,
+                        swimlane: row.get(2)?,
+                        hidden_on: date_from_sql(row.get(3)?)?,
+                        hidden_with: row.get(4)?,
+                    },
+                ))
+            }))?
+            .collect::<rusqlite::Result<_>>(),
+        )?;
+        let mut trash: Vec<HiddenItem> = Vec::new();
+        let mut archive: Vec<HiddenItem> = Vec::new();
+        for (shelf, hidden_item) in rows {
+            match shelf.as_str() {
+                "trash" => trash.push(hidden_item),
+                _ => archive.push(hidden_item),
+            }
+        }
+
+        let mut stmt = db_result(self.conn.prepare("SELECT tag, color FROM tag_colors"))?;
+        let rows: Vec<(String, String)> = db_result(
+            db_result(stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?))))?
+                .collect::<rusqlite::Result<_>>(),
+        )?;
+        let mut tag_colors: BTreeMap<String, TagColor> = BTreeMap::new();
+        for (tag, color) in rows {
+            tag_colors.insert(tag, color.parse::<TagColor>()?);
+        }
+
+        let mut stmt = db_result(
+            self.conn
+                .prepare("SELECT handle, name, email FROM people ORDER BY position"),
+        )?;
+        let people: Vec<Person> = db_result(
+            db_result(stmt.query_map([], |row| {
+                Ok(Person {
+                    handle: row.get(0)?,
+                    name: row.get(1)?,
+                    email: row.get(2)?,
+                })
+            }))?
+            .collect::<rusqlite::Result<_>>(),
+        )?;
+
+        let mut stmt = db_result(
+            self.conn
+                .prepare("SELECT item_id, blocker_id FROM dependencies ORDER BY item_id, position"),
+        )?;
+        let rows: Vec<(String, String)> = db_result(
+            db_result(stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?))))?
+                .collect::<rusqlite::Result<_>>(),
+        )?;
+        let mut dependencies: Dependencies = Dependencies::default();
+        for (item_id, blocker_id) in rows {
+            dependencies
+                .blocked_by
+                .entry(item_id)
+                .or_default()
+                .push(blocker_id);
+        }
+
+        let mut stmt = db_result(
+            self.conn
+                .prepare("SELECT user_handle, item_id, started_at FROM timers ORDER BY position"),
+        )?;
+        let timers: Vec<RunningTimer> = db_result(
+            db_result(stmt.query_map([], |row| {
+                let user_handle: String = row.get(0)?;
+                Ok(RunningTimer {
+                    item_id: row.get(1)?,
+                    user: Some(user_handle).filter(|x| !x.is_empty()),
+                    started_at: row.get(2)?,
+                })
+            }))?
+            .collect::<rusqlite::Result<_>>(),
+        )?;
+
+        let mut stmt = db_result(self.conn.prepare(
+            "SELECT number, goal, start_on, end_on, closed_on FROM sprints ORDER BY number",
+        ))?;
+        let mut sprints: Vec<Sprint> = db_result(
+            db_result(stmt.query_map([], |row| {
+                Ok(Sprint {
+                    number: row.get(0)?,
+                    goal: row.get(1)?,
+                    start_on: date_from_sql(row.get(2)?)?,
+                    end_on: date_from_sql(row.get(3)?)?,
+                    items: Vec::new(),
+                    closed_on: optional_date_from_sql(row.get(4)?)?,
+                    completed: Vec::new(),
+                    carried_over: Vec::new(),
+                })
+            }))?
+            .collect::<rusqlite::Result<_>>(),
+        )?;
+        let mut stmt = db_result(self.conn.prepare(
+            "SELECT number, list, item_id FROM sprint_items ORDER BY number, list, position",
+        ))?;
+        let rows: Vec<(u32, String, String)> = db_result(
+            db_result(stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?))))?
+                .collect::<rusqlite::Result<_>>(),
+        )?;
+        for (number, list, item_id) in rows {
+            if let Some(sprint) = sprints.iter_mut().find(|x| x.number == number) {
+                match list.as_str() {
+                    "completed" => sprint.completed.push(item_id),
+                    "carried_over" => sprint.carried_over.push(item_id),
+                    _ => sprint.items.push(item_id),
+                }
+            }
+        }
+
+        Ok(Some(KanbanBoard {
+            swimlanes,
+            id_sequence,
+            trash,
+            archive,
+            tag_colors,
+            people,
+            dependencies,
+            timers,
+            sprints,
+        }))
+    }
+
+    fn save_board(&self, boards: &KanbanBoard) -> Result<(), AppError> {
+        self.in_transaction(|tx| {
+            tx.execute("DELETE FROM board_items", [])?;
+            tx.execute("DELETE FROM swimlanes", [])?;
+            for (lane_position, swimlane) in boards.swimlanes.iter().enumerate() {
+                tx.execute(
+                    "INSERT INTO swimlanes
+                     (status, name, position, stage, blocked, wip_limit, wip_strict)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                    params![
+                        swimlane.slug,
+                        swimlane.name,
+                        lane_position as i64,
+                        swimlane.stage.to_string(),
+                        swimlane.blocked,
+                        swimlane.wip_limit.map(|x| x.max_items as i64),
+                        swimlane.wip_limit.map(|x| x.strict).unwrap_or(true)
+                    ],
+                )?;
+                for (position, item_id) in swimlane.items.iter().enumerate() {
+                    tx.execute(
+                        "INSERT INTO board_items (status, position, item_id) VALUES (?1, ?2, ?3)",
+                        params![swimlane.slug, position as i64, item_id],
+                    )
+                    .map_err(|e| missing_item(e, item_id))?;
+                }
+            }
+
+            tx.execute("DELETE FROM hidden_items", [])?;
+            for (shelf, hidden_items) in [
+                (Shelf::Trash, &boards.trash),
+                (Shelf::Archive, &boards.archive),
+            ] {
+                for (position, hidden_item) in hidden_items.iter().enumerate() {
+                    tx.execute(
+                        "INSERT INTO hidden_items
+                         (shelf, position, item_id, swimlane, hidden_on, hidden_with)
+                         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                        params![
+                            shelf.to_string(),
+                            position as i64,
+                            hidden_item.item_id,
+                            hidden_item.swimlane,
+                            column_from_sql(date_to_sql(&hidden_item.hidden_on))?,
+                            hidden_item.hidden_with
+                        ],
+                    )
+                    .map_err(|e| missing_item(e, &hidden_item.item_id))?;
+                }
+            }
+
+            tx.execute("DELETE FROM tag_colors", [])?;
+            for (tag, color) in &boards.tag_colors {
+                tx.execute(
+                    "INSERT INTO tag_colors (tag, color) VALUES (?1, ?2)",
+                    params![tag, color.to_string()],
+                )?;
+            }
+
+            tx.execute("DELETE FROM people", [])?;
+            for (position, person) in boards.people.iter().enumerate() {
+                tx.execute(
+                    "INSERT INTO people (handle, position, name, email) VALUES (?1, ?2, ?3, ?4)",
+                    params![person.handle, position as i64, person.name, person.email],
+                )?;
+            }
+
+            tx.execute("DELETE FROM dependencies", [])?;
+            for (item_id, blockers) in &boards.dependencies.blocked_by {
+                for (position, blocker_id) in blockers.iter().enumerate() {
+                    tx.execute(
+                        "INSERT INTO dependencies (item_id, position, blocker_id)
+                         VALUES (?1, ?2, ?3)",
+                        params![item_id, position as i64, blocker_id],
+                    )?;
+                }
+            }
+
+            tx.execute("DELETE FROM timers", [])?;
+            for (position, timer) in boards.timers.iter().enumerate() {
+                tx.execute(
+                    "INSERT INTO timers (user_handle, position, item_id, started_at)
+                     VALUES (?1, ?2, ?3, ?4)",
+                    params![
+                        timer.user.as_deref().unwrap_or(""),
+                        position as i64,
+                        timer.item_id,
+                        timer.started_at
+                    ],
+                )?;
+            }
+
+            tx.execute("DELETE FROM sprints", [])?;
+            for sprint in &boards.sprints {
+                tx.execute(
+                    "INSERT INTO sprints (number, goal, start_on, end_on, closed_on)
+                     VALUES (?1, ?2, ?3, ?4, ?5)",
+                    params![
+                        sprint.number,
+                        sprint.goal,
+                        column_from_sql(date_to_sql(&sprint.start_on))?,
+                        column_from_sql(date_to_sql(&sprint.end_on))?,
+                        column_from_sql(optional_date_to_sql(&sprint.closed_on))?
+                    ],
+                )?;
+                for (list, item_ids) in [
+                    ("planned", &sprint.items),
+                    ("completed", &sprint.completed),
+                    ("carried_over", &sprint.carried_over),
+                ] {
+                    for (position, item_id) in item_ids.iter().enumerate() {
+                        tx.execute(
+                            "INSERT INTO sprint_items (number, list, position, item_id)
+                             VALUES (?1, ?2, ?3, ?4)",
+                            params![sprint.number, list, position as i64, item_id],
+                        )?;
+                    }
+                }
+            }
+
+            let id_sequence: &IdSequence = &boards.id_sequence;
+            tx.execute(
+                "INSERT OR REPLACE INTO board_settings (name, value) VALUES ('id_prefix', ?1)",
+                params![id_sequence.prefix],
+            )?;
+            tx.execute("DELETE FROM id_sequences", [])?;
+            tx.execute(
+                "INSERT INTO id_sequences (task_id, last_number) VALUES ('', ?1)",
+                params![id_sequence.last_task_number as i64],
+            )?;
+            for (task_id, last_number) in &id_sequence.last_subtask_numbers {
+                tx.execute(
+                    "INSERT INTO id_sequences (task_id, last_number) VALUES (?1, ?2)",
+                    params![task_id, *last_number as i64],
+                )?;
+            }
+            Ok(())
+        })?;
+        self.mark_setup("board")
+    }
+
+    fn load_notes(&self) -> Result<Option<TaskNotes>, AppError> {
+        if !self.is_setup("notes")? {
+            return Ok(None);
+        }
+
+        let mut notes: HashMap<String, Vec<String>> = HashMap::new();
+        let mut stmt = db_result(
+            self.conn
+                .prepare("SELECT item_id, note FROM notes ORDER BY item_id, position"),
+        )?;
+        let rows: Vec<(String, String)> = db_result(
+            db_result(stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?))))?
+                .collect::<rusqlite::Result<_>>(),
+        )?;
+        for (item_id, note) in rows {
+            notes.entry(item_id).or_default().push(note);
+        }
+
+        Ok(Some(TaskNotes { notes }))
+    }
+
+    fn save_notes(&self, task_notes: &TaskNotes) -> Result<(), AppError> {
+        self.in_transaction(|tx| {
+            tx.execute("DELETE FROM notes", [])?;
+            for (item_id, notes_list) in &task_notes.notes {
+                for (position, note) in notes_list.iter().enumerate() {
+                    tx.execute(
+                        "INSERT INTO notes (item_id, position, note) VALUES (?1, ?2, ?3)",
+                        params![item_id, position as i64, note],
+                    )
+                    .map_err(|e| missing_item(e, item_id))?;
+                }
+            }
+            Ok(())
+        })?;
+        self.mark_setup("notes")
+    }
+
+    fn get_item(&self, item_id: &str) -> Result<WorkItem, AppError> {
+        let work_item: Option<WorkItem> = db_result(
+            self.conn
+                .query_row(
+                    &format!("SELECT {} FROM items WHERE item_id = ?1", ITEM_COLUMNS),
+                    params![item_id],
+                    |row| {
+                        Ok(WorkItem {
+                            id: row.get(0)?,
+                            name: row.get(1)?,
+                            description: row.get(2)?,
+                            added_on: date_from_sql(row.get(3)?)?,
+                            started_on: optional_date_from_sql(row.get(4)?)?,
+                            deadline: optional_date_from_sql(row.get(5)?)?,
+                            completed_on: optional_date_from_sql(row.get(6)?)?,
+                            status: row.get(7)?,
+                            priority: column_from_sql(priority_from_sql(
+                                &row.get::<_, String>(8)?,
+                            ))?,
+                            tags: Vec::new(),
+                            assignee: row.get(9)?,
+                            parent: row.get(10)?,
+                            worklog: Vec::new(),
+                            estimate: match row.get::<_, Option<String>>(11)? {
+                                Some(s) => Some(Estimate {
+                                    unit: column_from_sql(s.parse::<EstimateUnit>())?,
+                                    original: row.get(12)?,
+                                    remaining: row.get(13)?,
+                                }),
+                                None => None,
+                            },
+                        })
+                    },
+                )
+                .optional(),
+        )?;
+        match work_item {
+            Some(mut s) => {
+                s.tags = self.get_tags(item_id)?;
+                s.worklog = self.get_worklog(item_id)?;
+                Ok(s)
+            }
+            None => Err(AppError::TaskNotFound(item_id.to_string())),
+        }
+    }
+
+    /// Insert or update the item row (without replacing it, so linked rows are kept) along with
+    /// its tags and worklog
+    fn save_item(&self, work_item: &WorkItem) -> Result<(), AppError> {
+        let added_on: String = date_to_sql(&work_item.added_on)?;
+        let started_on: Option<String> = optional_date_to_sql(&work_item.started_on)?;
+        let deadline: Option<String> = optional_date_to_sql(&work_item.deadline)?;
+        let completed_on: Option<String> = optional_date_to_sql(&work_item.completed_on)?;
+        self.in_transaction(|tx| {
+            tx.execute(
+                "INSERT INTO items (item_id, name, description, added_on, started_on, deadline,
+                                completed_on, status, priority, assignee, parent_id,
+                                estimate_unit, estimate, remaining)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)
+             ON CONFLICT (item_id) DO UPDATE SET
+                name = excluded.name,
+                description = excluded.description,
+                added_on = excluded.added_on,
+                started_on = excluded.started_on,
+                deadline = excluded.deadline,
+                completed_on = excluded.completed_on,
+                status = excluded.status,
+                priority = excluded.priority,
+                assignee = excluded.assignee,
+                parent_id = excluded.parent_id,
+                estimate_unit = excluded.estimate_unit,
+                estimate = excluded.estimate,
+                remaining = excluded.remaining",
+                params![
+                    work_item.id,
+                    work_item.name,
+                    work_item.description,
+                    added_on,
+                    started_on,
+                    deadline,
+                    completed_on,
+                    work_item.status,
+                    priority_to_sql(&work_item.priority),
+                    work_item.assignee,
+                    work_item.parent,
+                    work_item.estimate.map(|x| x.unit.to_string()),
+                    work_item.estimate.map(|x| x.original),
+                    work_item.estimate.map(|x| x.remaining),
+                ],
+            )?;
+            tx.execute(
+                "DELETE FROM item_tags WHERE item_id = ?1",
+                params![work_item.id],
+            )?;
+            for (position, tag) in work_item.tags.iter().enumerate() {
+                tx.execute(
+                    "INSERT INTO item_tags (item_id, position, tag) VALUES (?1, ?2, ?3)",
+                    params![work_item.id, position as i64, tag],
+                )?;
+            }
+            tx.execute(
+                "DELETE FROM worklogs WHERE item_id = ?1",
+                params![work_item.id],
+            )?;
+            for (position, work_log) in work_item.worklog.iter().enumerate() {
+                tx.execute(
+                    "INSERT INTO worklogs (item_id, position, logged_on, minutes, message, author)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                    params![
+                        work_item.id,
+                        position as i64,
+                        column_from_sql(date_to_sql(&work_log.logged_on))?,
+                        work_log.minutes,
+                        work_log.message,
+                        work_log.author
+                    ],
+                )?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Delete the item row (linked rows are removed by the foreign keys)
+    fn delete_item(&self, item_id: &str) -> Result<(), AppError> {
+        let deleted: usize = db_result(
+            self.conn
+                .execute("DELETE FROM items WHERE item_id = ?1", params![item_id]),
+        )?;
+        match deleted {
+            0 => Err(AppError::TaskNotFound(item_id.to_string())),
+            _ => {
+                record_write();
+                Ok(())
+            }
+        }
+    }
+
+    fn item_exists(&self, item_id: &str) -> Result<bool, AppError> {
+        let found: Option<String> = db_result(
+            self.conn
+                .query_row(
+                    "SELECT item_id FROM items WHERE item_id = ?1",
+                    params![item_id],
+                    |row| row.get(0),
+                )
+                .optional(),
+        )?;
+        Ok(found.is_some())
+    }
+
+    fn list_items(&self) -> Result<Vec<String>, AppError> {
+        let mut stmt = db_result(
+            self.conn
+                .prepare("SELECT item_id FROM items ORDER BY item_id"),
+        )?;
+        let item_ids: Vec<String> = db_result(
+            db_result(stmt.query_map([], |row| row.get(0)))?.collect::<rusqlite::Result<_>>(),
+        )?;
+        Ok(item_ids)
+    }
+
+    fn begin_transaction(&self) -> Result<bool, AppError> {
+        db_result(self.conn.execute_batch("BEGIN IMMEDIATE"))?;
+        Ok(true)
+    }
+
+    fn commit_transaction(&self) -> Result<(), AppError> {
+        db_result(self.conn.execute_batch("COMMIT"))?;
+        record_write();
+        Ok(())
+    }
+
+    fn rollback_transaction(&self) -> Result<(), AppError> {
+        db_result(self.conn.execute_batch("ROLLBACK"))
+    }
+
+    fn migrate(&self, check_only: bool) -> Result<Vec<String>, AppError> {
+        let version: u32 = self.schema_version()?;
+        let pending: Vec<&(u32, &str, &str)> =
+            SCHEMA_MIGRATIONS.iter().filter(|x| x.0 > version).collect();
+        if pending.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let steps: Vec<String> = pending
+            .iter()
+            .map(|x| format!("v{}: {}", x.0, x.1))
+            .collect();
+        let change: String = format!(
+            "{} (schema version {} -> {}): {}",
+            SQLITE_DB_FILE,
+            version,
+            SCHEMA_VERSION,
+            steps.join("; ")
+        );
+        if !check_only {
+            self.in_transaction(|tx| {
+                for (_, _, statements) in &pending {
+                    tx.execute_batch(statements)?;
+                }
+                tx.pragma_update(None, "user_version", SCHEMA_VERSION)
+            })?;
+        }
+        Ok(vec![change])
+    }
+}
+
+/// Name the Task or SubTask ID in the error of a row which references it while it isn't stored, so
+/// that the board or notes are never saved without it unnoticed
+fn missing_item(err: rusqlite::Error, item_id: &str) -> rusqlite::Error {
+    match err {
+        rusqlite::Error::SqliteFailure(e, _)
+            if e.extended_code == rusqlite::ffi::SQLITE_CONSTRAINT_FOREIGNKEY =>
+        {
+            rusqlite::Error::SqliteFailure(
+                e,
+                Some(format!(
+                    "{} - Task or subtask is not stored. Run `fsck` to check the board.",
+                    item_id
+                )),
+            )
+        }
+        _ => err,
+    }
+}
+
+/// Convert a rusqlite result into the application result
+fn db_result<T>(result: rusqlite::Result<T>) -> Result<T, AppError> {
+    match result {
+        Ok(s) => Ok(s),
+        Err(e) => Err(AppError::DatabaseError(e.to_string())),
+    }
+}
+
+/// Convert an application result into a rusqlite result while reading a row
+fn column_from_sql<T>(result: Result<T, AppError>) -> rusqlite::Result<T> {
+    match result {
+        Ok(s) => Ok(s),
+        Err(e) => Err(rusqlite::Error::InvalidColumnType(
+            0,
+            e.to_string(),
+            rusqlite::types::Type::Text,
+        )),
+    }
+}
+
+/// Store dates as ISO-8601 text (yyyy-mm-dd)
+fn date_to_sql(date: &TimeStamp) -> Result<String, AppError> {
+    Ok(date.to_naivedate()?.format("%Y-%m-%d").to_string())
+}
+
+fn optional_date_to_sql(date: &Option<TimeStamp>) -> Result<Option<String>, AppError> {
+    match date {
+        Some(s) => Ok(Some(date_to_sql(s)?)),
+        None => Ok(None),
+    }
+}
+
+fn date_from_sql(date: String) -> rusqlite::Result<TimeStamp> {
+    match NaiveDate::parse_from_str(&date, "%Y-%m-%d") {
+        Ok(s) => Ok(TimeStamp::convert(s)),
+        Err(e) => Err(rusqlite::Error::InvalidColumnType(
+            0,
+            format!("{} - {}", date, e),
+            rusqlite::types::Type::Text,
+        )),
+    }
+}
+
+fn optional_date_from_sql(date: Option<String>) -> rusqlite::Result<Option<TimeStamp>> {
+    match date {
+        Some(s) => Ok(Some(date_from_sql(s)?)),
+        None => Ok(None),
+    }
+}
+
+fn priority_to_sql(priority: &TaskPriority) -> &'static str {
+    match priority {
+        TaskPriority::High => "high",
+        TaskPriority::Medium => "medium",
+        TaskPriority::Low => "low",
+    }
+}
+
+fn priority_from_sql(priority: &str) -> Result<TaskPriority, AppError> {
+    priority.parse::<TaskPriority>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_dir;
+
+    /// Rows written by the first schema version: a blocked subtask nested under a task with a note
+    const ROWS_V1: &str = "
+        INSERT INTO swimlanes (status) VALUES
+            ('to-do'), ('in-progress'), ('blocked'), ('in-review'), ('done');
+        INSERT INTO items VALUES
+            ('TASK-1', 'task', 'TASK-1', 'Written by the first version', '2024-03-01', NULL,
+             '2024-04-01', NULL, 'to-do', 'high'),
+            ('TASK-1.1', 'subtask', 'TASK-1.1', 'Written by the first version', '2024-03-01',
+             NULL, NULL, NULL, 'blocked', 'low');
+        INSERT INTO board_items VALUES ('to-do', 0, 'TASK-1'), ('blocked', 0, 'TASK-1.1');
+        INSERT INTO task_links VALUES ('TASK-1', 0, 'TASK-1.1');
+        INSERT INTO notes VALUES ('TASK-1.1', 0, 'Waiting on review');
+        INSERT INTO setup VALUES ('board'), ('notes'), ('links');
+    ";
+
+    #[test]
+    fn first_version_database_is_migrated_and_round_trips() {
+        let board_dir: PathBuf = test_dir("sqlite_migration");
+        let storage: SqliteStorage = SqliteStorage::connect(&board_dir).unwrap();
+        storage.conn.execute_batch(SCHEMA).unwrap();
+        storage.conn.execute_batch(ROWS_V1).unwrap();
+        storage.conn.pragma_update(None, "user_version", 1).unwrap();
+
+        let pending: Vec<String> = storage.migrate(true).unwrap();
+        assert_eq!(pending.len(), 1, "{:?}", pending);
+        assert_eq!(storage.schema_version().unwrap(), 1);
+        assert_eq!(storage.migrate(false).unwrap(), pending);
+        assert!(storage.migrate(true).unwrap().is_empty());
+        assert_eq!(storage.schema_version().unwrap(), SCHEMA_VERSION);
+        assert!(!storage.is_setup("links").unwrap());
+
+        let boards: KanbanBoard = storage.load_board().unwrap().unwrap();
+        let slugs: Vec<&str> = boards.swimlanes.iter().map(|x| x.slug.as_str()).collect();
+        assert_eq!(
+            slugs,
+            ["to-do", "in-progress", "blocked", "in-review", "done"]
+        );
+        assert_eq!(boards.swimlanes[0].items, ["TASK-1"]);
+        assert_eq!(boards.swimlanes[2].items, ["TASK-1.1"]);
+        assert!(boards.swimlanes[2].blocked);
+        assert_eq!(boards.swimlanes[4].stage, LaneStage::Done);
+
+        let subtask_item: WorkItem = storage.get_item("TASK-1.1").unwrap();
+        assert_eq!(subtask_item.parent, Some("TASK-1".to_string()));
+        assert_eq!(subtask_item.status, "blocked");
+        assert_eq!(subtask_item.priority, TaskPriority::Low);
+        assert!(subtask_item.tags.is_empty() && subtask_item.estimate.is_none());
+        let task_item: WorkItem = storage.get_item("TASK-1").unwrap();
+        let deadline: NaiveDate = NaiveDate::from_ymd_opt(2024, 4, 1).unwrap();
+        assert_eq!(task_item.deadline, Some(TimeStamp::convert(deadline)));
+        let task_notes: TaskNotes = storage.load_notes().unwrap().unwrap();
+        assert_eq!(task_notes.notes["TASK-1.1"], ["Waiting on review"]);
+
+        storage.save_board(&boards).unwrap();
+        storage.save_item(&subtask_item).unwrap();
+        assert_eq!(storage.load_board().unwrap(), Some(boards));
+        assert_eq!(storage.get_item("TASK-1.1").unwrap(), subtask_item);
+    }
+
+    #[test]
+    fn board_and_notes_referencing_missing_items_are_refused() {
+        let board_dir: PathBuf = test_dir("sqlite_missing_items");
+        let storage: SqliteStorage = SqliteStorage::open(&board_dir).unwrap();
+        let mut boards: KanbanBoard = KanbanBoard::new();
+        storage.save_board(&boards).unwrap();
+
+        boards.swimlanes[0].items.push("TASK-9".to_string());
+        let err: AppError = storage.save_board(&boards).unwrap_err();
+        assert!(
+            matches!(&err, AppError::DatabaseError(s) if s.starts_with("TASK-9 - ")),
+            "{}",
+            err
+        );
+        assert_eq!(storage.load_board().unwrap(), Some(KanbanBoard::new()));
+
+        let task_notes: TaskNotes = TaskNotes {
+            notes: HashMap::from([("TASK-9".to_string(), vec!["Lost".to_string()])]),
+        };
+        let err: AppError = storage.save_notes(&task_notes).unwrap_err();
+        assert!(
+            matches!(&err, AppError::DatabaseError(s) if s.starts_with("TASK-9 - ")),
+            "{}",
+            err
+        );
+    }
+}
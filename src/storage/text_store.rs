@@ -0,0 +1,1174 @@
+//! Defines the plain-text storage backend, which keeps one Markdown file per task and subtask
+//! (with the fields as TOML front-matter and the description as the body) along with a TOML index
+//! file holding the swimlanes, so that project boards can be diffed, reviewed and merged in git
+
+use crate::{
+    boards::{HiddenItem, KanbanBoard},
+    constants::{ACTIVE_SUBTASKS_PATH, ACTIVE_TASKS_PATH, ITEMS_PATH, TEXT_INDEX_FILE},
+    dependencies::Dependencies,
+    error::AppError,
+    estimates::{Estimate, EstimateUnit},
+    ids::{id_sort_key, IdSequence},
+    items::WorkItem,
+    migrations::{self, BLOCKED_SWIMLANES_VERSION, LEGACY_BLOCKED_SWIMLANE, SCHEMA_VERSION},
+    notes::TaskNotes,
+    people::Person,
+    sprints::Sprint,
+    storage::Storage,
+    swimlanes::{LaneStage, Swimlane, WipLimit},
+    tags::TagColor,
+    utils::{
+        create_dir, list_file_stems, record_write, write_file_atomic, TaskPriority, TimeStamp,
+    },
+    worklog::{RunningTimer, WorkLog},
+};
+use chrono::prelude::{DateTime, Local, NaiveDate, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+};
+
+/// Line opening and closing the TOML front-matter of a task or subtask file
+const FRONT_MATTER_DELIMITER: &str = "+++";
+
+/// Line opening the version of the current branch in a file with git conflict markers
+const CONFLICT_OURS_MARKER: &str = "<<<<<<<";
+
+/// Line opening the version of the common ancestor (diff3 style) in a file with git conflict markers
+const CONFLICT_BASE_MARKER: &str = "|||||||";
+
+/// Line opening the version of the merged branch in a file with git conflict markers
+const CONFLICT_THEIRS_MARKER: &str = "=======";
+
+/// Line closing a conflict in a file with git conflict markers
+const CONFLICT_END_MARKER: &str = ">>>>>>>";
+
+/// Contents of the index file. Items in each swimlane are kept sorted by ID (rather than in the
+/// order they were added), so that branches which each add tasks touch different lines.
+#[derive(Serialize, Deserialize, PartialEq)]
+struct BoardIndex {
+    /// Schema version the index was written with
+    schema_version: u32,
+
+    /// Prefix of the Task IDs
+    id_prefix: String,
+
+    /// Number of the most recently generated Task ID
+    #[serde(default)]
+    last_task_number: u64,
+
+    /// Number of the most recently generated SubTask ID for each parent Task or SubTask ID
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    last_subtask_numbers: BTreeMap<String, u64>,
+
+    /// Colors given to tags used on the board
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    tag_colors: BTreeMap<String, String>,
+
+    /// Task and SubTask IDs blocking each task or subtask
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    dependencies: BTreeMap<String, Vec<String>>,
+
+    /// Swimlanes in display order
+    swimlanes: Vec<SwimlaneEntry>,
+
+    /// Deleted tasks and subtasks, in the order they were deleted
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    trash: Vec<HiddenEntry>,
+
+    /// Archived tasks and subtasks, in the order they were archived
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    archive: Vec<HiddenEntry>,
+
+    /// People who can be assigned to tasks and subtasks, in the order they were added
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    people: Vec<PersonEntry>,
+
+    /// Timers running on tasks and subtasks, in the order they were started
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    timers: Vec<TimerEntry>,
+
+    /// Sprints of the board, in the order they were created
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    sprints: Vec<SprintEntry>,
+}
+
+/// Swimlane as written to the index file
+#[derive(Serialize, Deserialize, PartialEq)]
+struct SwimlaneEntry {
+    slug: String,
+    name: String,
+    stage: String,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    blocked: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    wip_limit: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    wip_strict: Option<bool>,
+    items: Vec<String>,
+}
+
+/// Task or subtask in the trash or the archive as written to the index file
+#[derive(Serialize, Deserialize, PartialEq)]
+struct HiddenEntry {
+    id: String,
+    swimlane: String,
+    hidden_on: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    hidden_with: Option<String>,
+}
+
+/// Person registered on the board as written to the index file
+#[derive(Serialize, Deserialize, PartialEq)]
+struct PersonEntry {
+    handle: String,
+    name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    email: Option<String>,
+}
+
+/// Timer running on a task or subtask as written to the index file
+#[derive(Serialize, Deserialize, PartialEq)]
+struct TimerEntry {
+    id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    user: Option<String>,
+    started_at: String,
+}
+
+/// Sprint as written to the index file
+#[derive(Serialize, Deserialize, PartialEq)]
+struct SprintEntry {
+    number: u32,
+    goal: String,
+    start_on: String,
+    end_on: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    closed_on: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    items: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    completed: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    carried_over: Vec<String>,
+}
+
+/// Time logged against a task or subtask as written to its front-matter
+#[derive(Serialize, Deserialize, PartialEq)]
+struct WorkLogEntry {
+    logged_on: String,
+    minutes: u32,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    author: Option<String>,
+}
+
+/// Estimate of a task or subtask as written to its front-matter
+#[derive(Serialize, Deserialize, PartialEq)]
+struct EstimateEntry {
+    unit: String,
+    original: u32,
+    remaining: u32,
+}
+
+/// Front-matter of a task or subtask file
+#[derive(Serialize, Deserialize, PartialEq)]
+struct ItemFields {
+    id: String,
+    name: String,
+    status: String,
+    priority: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    assignee: Option<String>,
+    added_on: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    started_on: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    deadline: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    completed_on: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    parent: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    estimate: Option<EstimateEntry>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    notes: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    worklog: Vec<WorkLogEntry>,
+}
+
+/// Task or subtask file, split into the front-matter and the description
+#[derive(PartialEq)]
+struct ItemFile {
+    fields: ItemFields,
+    description: String,
+}
+
+/// Rust structure for the plain-text storage backend
+pub struct TextStorage {
+    /// Board directory holding the index file and the task and subtask files
+    board_dir: PathBuf,
+}
+
+impl TextStorage {
+    /// Create new plain-text storage in given board directory
+    pub fn new(board_dir: PathBuf) -> Self {
+        TextStorage { board_dir }
+    }
+
+    /// Open the plain-text storage in given board directory, merging any tasks and subtasks kept
+    /// in the layout before work items were introduced and splitting the items which two merged
+    /// branches both added under the same ID
+    pub fn open(board_dir: PathBuf) -> Result<Self, AppError> {
+        let storage: TextStorage = TextStorage::new(board_dir);
+        storage.merge_items(false)?;
+        storage.resolve_index()?;
+        storage.split_collisions()?;
+        Ok(storage)
+    }
+
+    /// Get the file path for given Task or SubTask ID in given items directory
+    fn item_path(&self, items_dir: &str, item_id: &str) -> PathBuf {
+        self.board_dir
+            .join(items_dir)
+            .join(format!("{}.md", item_id))
+    }
+
+    /// Read given text file (None if the file is not present)
+    fn read_text(&self, file_path: &Path) -> Result<Option<String>, AppError> {
+        if !file_path.exists() {
+            return Ok(None);
+        }
+        match std::fs::read_to_string(file_path) {
+            Ok(s) => Ok(Some(s)),
+            Err(e) => Err(AppError::FileReadError(format!(
+                "{} - {}",
+                file_path.display(),
+                e
+            ))),
+        }
+    }
+
+    /// Write given text to file
+    fn write_text(&self, file_path: &Path, text: &str) -> Result<(), AppError> {
+        write_file_atomic(file_path, text.as_bytes())
+    }
+
+    /// Delete given file
+    fn delete_file(&self, file_path: &Path) -> Result<(), AppError> {
+        match std::fs::remove_file(file_path) {
+            Ok(_) => {
+                record_write();
+                Ok(())
+            }
+            Err(e) => Err(AppError::FileDeleteError(format!(
+                "{} - {}",
+                file_path.display(),
+                e
+            ))),
+        }
+    }
+
+    /// Read the index file (None if the board was never stored). An index left with git conflict
+    /// markers by two branches which each added items is read with the highest ID counters of
+    /// both branches, as long as nothing else conflicts.
+    fn read_index(&self) -> Result<Option<BoardIndex>, AppError> {
+        let index_file: PathBuf = self.board_dir.join(TEXT_INDEX_FILE);
+        let text: String = match self.read_text(&index_file)? {
+            Some(s) => s,
+            None => return Ok(None),
+        };
+        let board_index: BoardIndex = match split_conflict(&text) {
+            Some((ours, theirs)) => {
+                let mut board_index: BoardIndex = parse_index(&index_file, &ours)?;
+                let mut theirs_index: BoardIndex = parse_index(&index_file, &theirs)?;
+                board_index.last_task_number = board_index
+                    .last_task_number
+                    .max(theirs_index.last_task_number);
+                for (parent_id, number) in &theirs_index.last_subtask_numbers {
+                    let last_number: &mut u64 = board_index
+                        .last_subtask_numbers
+                        .entry(parent_id.clone())
+                        .or_insert(0);
+                    *last_number = (*last_number).max(*number);
+                }
+                theirs_index.last_task_number = board_index.last_task_number;
+                theirs_index.last_subtask_numbers = board_index.last_subtask_numbers.clone();
+                if theirs_index != board_index {
+                    return Err(AppError::TextFormatError(format!(
+                        "{} - unresolved merge conflict, please resolve it in git",
+                        index_file.display()
+                    )));
+                }
+                board_index
+            }
+            None => parse_index(&index_file, &text)?,
+        };
+        if board_index.schema_version > SCHEMA_VERSION {
+            return Err(AppError::UnsupportedSchemaVersion(format!(
+                "{} was written with schema version {}, but this version of the application only supports up to {}. Please upgrade the application.",
+                index_file.display(),
+                board_index.schema_version,
+                SCHEMA_VERSION
+            )));
+        }
+        Ok(Some(board_index))
+    }
+
+    /// Write the index file
+    fn write_index(&self, board_index: &BoardIndex) -> Result<(), AppError> {
+        let text: String = match toml::to_string_pretty(board_index) {
+            Ok(s) => s,
+            Err(e) => return Err(AppError::TextFormatError(e.to_string())),
+        };
+        self.write_text(&self.board_dir.join(TEXT_INDEX_FILE), &text)
+    }
+
+    /// Read and parse given task or subtask file (None if the file is not present)
+    fn read_item(&self, item_id: &str) -> Result<Option<ItemFile>, AppError> {
+        let item_file: PathBuf = self.item_path(ITEMS_PATH, item_id);
+        match self.read_text(&item_file)? {
+            Some(text) => Ok(Some(parse_item_file(&item_file, &text)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Read and parse given task or subtask file (error if the file is not present)
+    fn read_item_file(&self, item_id: &str) -> Result<ItemFile, AppError> {
+        match self.read_item(item_id)? {
+            Some(s) => Ok(s),
+            None => Err(AppError::FileReadError(format!(
+                "{} - {} not found",
+                ITEMS_PATH, item_id
+            ))),
+        }
+    }
+
+    /// Write given task or subtask file
+    fn write_item(&self, item: &ItemFile) -> Result<(), AppError> {
+        let front_matter: String = match toml::to_string_pretty(&item.fields) {
+            Ok(s) => s,
+            Err(e) => return Err(AppError::TextFormatError(e.to_string())),
+        };
+        let mut text: String = format!(
+            "{}\n{}{}\n",
+            FRONT_MATTER_DELIMITER, front_matter, FRONT_MATTER_DELIMITER
+        );
+        if !item.description.is_empty() {
+            text.push_str(&format!("\n{}\n", item.description));
+        }
+        self.write_text(&self.item_path(ITEMS_PATH, &item.fields.id), &text)
+    }
+
+    /// List the IDs of all task and subtask files, sorted by ID
+    fn list_item_files(&self) -> Result<Vec<String>, AppError> {
+        let mut item_ids: Vec<String> = list_file_stems(&self.board_dir.join(ITEMS_PATH), "md")?;
+        item_ids.sort_by_key(|x| id_sort_key(x));
+        Ok(item_ids)
+    }
+
+    /// Work out the ID sequence from the counters stored in the index and the stored task and
+    /// subtask files, taking the highest number of each. Numbers are never handed out twice, even
+    /// when the index was merged from a branch which hadn't seen the newest files.
+    fn id_sequence(&self, board_index: &BoardIndex) -> Result<IdSequence, AppError> {
+        let prefix: &str = &board_index.id_prefix;
+        let mut id_sequence: IdSequence = IdSequence::new(prefix);
+        id_sequence.last_task_number = board_index.last_task_number;
+        id_sequence.last_subtask_numbers = board_index
+            .last_subtask_numbers
+            .iter()
+            .map(|(parent_id, number)| (parent_id.clone(), *number))
+            .collect();
+        for item_id in self.list_item_files()? {
+            if let Some(number) = item_id
+                .strip_prefix(&format!("{}-", prefix))
+                .and_then(|x| x.parse::<u64>().ok())
+            {
+                id_sequence.last_task_number = id_sequence.last_task_number.max(number);
+            } else if let Some((parent_id, number)) = item_id.rsplit_once('.') {
+                if let Ok(number) = number.parse::<u64>() {
+                    let last_number: &mut u64 = id_sequence
+                        .last_subtask_numbers
+                        .entry(parent_id.to_string())
+                        .or_insert(0);
+                    *last_number = (*last_number).max(number);
+                }
+            }
+        }
+        Ok(id_sequence)
+    }
+
+    /// Rewrite the index file without the git conflict markers left on the ID counters by two
+    /// merged branches which each added items
+    fn resolve_index(&self) -> Result<(), AppError> {
+        let index_file: PathBuf = self.board_dir.join(TEXT_INDEX_FILE);
+        let conflicted: bool = match self.read_text(&index_file)? {
+            Some(text) => split_conflict(&text).is_some(),
+            None => false,
+        };
+        if conflicted {
+            if let Some(board_index) = self.read_index()? {
+                self.write_index(&board_index)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Split the task and subtask files which two merged branches both added under the same ID
+    /// (left with git conflict markers) into the version of each branch. The current branch keeps
+    /// the ID, while the version of the merged branch gets the next free ID under the same parent
+    /// and is listed in the swimlane named by its status. Returns the original and new ID of each
+    /// item split.
+    fn split_collisions(&self) -> Result<Vec<(String, String)>, AppError> {
+        let mut collisions: Vec<(String, ItemFile, ItemFile)> = Vec::new();
+        for item_id in self.list_item_files()? {
+            let item_file: PathBuf = self.item_path(ITEMS_PATH, &item_id);
+            let text: String = match self.read_text(&item_file)? {
+                Some(s) => s,
+                None => continue,
+            };
+            if let Some((ours, theirs)) = split_conflict(&text) {
+                collisions.push((
+                    item_id,
+                    parse_item_file(&item_file, &ours)?,
+                    parse_item_file(&item_file, &theirs)?,
+                ));
+            }
+        }
+        if collisions.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut board_index: BoardIndex = match self.read_index()? {
+            Some(s) => s,
+            None => {
+                return Err(AppError::TextFormatError(format!(
+                    "{} - missing, so the IDs added by both merged branches can't be told apart",
+                    self.board_dir.join(TEXT_INDEX_FILE).display()
+                )))
+            }
+        };
+        let mut id_sequence: IdSequence = self.id_sequence(&board_index)?;
+        let mut new_ids: Vec<(String, String)> = Vec::new();
+        for (item_id, ours, mut theirs) in collisions {
+            self.write_item(&ours)?;
+            if theirs == ours {
+                continue;
+            }
+
+            let new_id: String = loop {
+                let candidate_id: String = match &theirs.fields.parent {
+                    None => {
+                        id_sequence.last_task_number += 1;
+                        format!("{}-{}", id_sequence.prefix, id_sequence.last_task_number)
+                    }
+                    Some(parent_id) => {
+                        let last_number: &mut u64 = id_sequence
+                            .last_subtask_numbers
+                            .entry(parent_id.clone())
+                            .or_insert(0);
+                        *last_number += 1;
+                        format!("{}.{}", parent_id, last_number)
+                    }
+                };
+                if !self.item_path(ITEMS_PATH, &candidate_id).exists() {
+                    break candidate_id;
+                }
+            };
+            theirs.fields.id = new_id.clone();
+            self.write_item(&theirs)?;
+
+            let swimlane_index: Option<usize> = board_index
+                .swimlanes
+                .iter()
+                .position(|x| x.slug == theirs.fields.status)
+                .or_else(|| {
+                    board_index
+                        .swimlanes
+                        .iter()
+                        .position(|x| x.items.contains(&item_id))
+                });
+            if let Some(swimlane_index) = swimlane_index {
+                board_index.swimlanes[swimlane_index]
+                    .items
+                    .push(new_id.clone());
+            }
+            new_ids.push((item_id, new_id));
+        }
+        board_index.last_task_number = id_sequence.last_task_number;
+        board_index.last_subtask_numbers = id_sequence.last_subtask_numbers.into_iter().collect();
+        self.write_index(&board_index)?;
+        Ok(new_ids)
+    }
+
+    /// Move the task and subtask files kept in separate directories (up to schema version 9) into
+    /// the items directory, unless `check_only` is set. Subtask files already name their parent
+    /// task. Returns the description of the change (None if there is no data in the legacy layout).
+    fn merge_items(&self, check_only: bool) -> Result<Option<String>, AppError> {
+        let mut legacy_files: Vec<(&str, String)> = Vec::new();
+        for items_dir in [ACTIVE_TASKS_PATH, ACTIVE_SUBTASKS_PATH] {
+            for item_id in list_file_stems(&self.board_dir.join(items_dir), "md")? {
+                legacy_files.push((items_dir, item_id));
+            }
+        }
+        if legacy_files.is_empty() {
+            return Ok(None);
+        }
+
+        let change: String = format!(
+            "{} (legacy layout): Merge tasks and subtasks into work items with a parent pointer",
+            self.board_dir.display()
+        );
+        if check_only {
+            return Ok(Some(change));
+        }
+
+        create_dir(&self.board_dir.join(ITEMS_PATH))?;
+        for (items_dir, item_id) in legacy_files {
+            migrations::move_file(
+                &self.item_path(items_dir, &item_id),
+                &self.item_path(ITEMS_PATH, &item_id),
+            )?;
+        }
+        // The legacy directories are only removed once empty, anything else in them is left alone
+        for items_dir in [ACTIVE_TASKS_PATH, ACTIVE_SUBTASKS_PATH] {
+            let _ = std::fs::remove_dir(self.board_dir.join(items_dir));
+        }
+        Ok(Some(change))
+    }
+}
+
+impl Storage for TextStorage {
+    fn load_board(&self) -> Result<Option<KanbanBoard>, AppError> {
+        let board_index: BoardIndex = match self.read_index()? {
+            Some(s) => s,
+            None => return Ok(None),
+        };
+        let id_sequence: IdSequence = self.id_sequence(&board_index)?;
+
+        let mut swimlanes: Vec<Swimlane> = Vec::new();
+        let legacy_blocked: bool = board_index.schema_version < BLOCKED_SWIMLANES_VERSION;
+        for entry in board_index.swimlanes {
+            swimlanes.push(Swimlane {
+                blocked: entry.blocked || legacy_blocked && entry.slug == LEGACY_BLOCKED_SWIMLANE,
+                slug: entry.slug,
+                name: entry.name,
+                stage: entry.stage.parse::<LaneStage>()?,
+                wip_limit: entry.wip_limit.map(|x| WipLimit {
+                    max_items: x,
+                    strict: entry.wip_strict.unwrap_or(true),
+                }),
+                items: entry.items,
+            });
+        }
+        let mut tag_colors: BTreeMap<String, TagColor> = BTreeMap::new();
+        for (tag, color) in board_index.tag_colors {
+            tag_colors.insert(tag, color.parse::<TagColor>()?);
+        }
+        let hidden_items = |entries: Vec<HiddenEntry>| -> Result<Vec<HiddenItem>, AppError> {
+            entries
+                .into_iter()
+                .map(|x| {
+                    Ok(HiddenItem {
+                        item_id: x.id,
+                        swimlane: x.swimlane,
+                        hidden_on: date_from_text(&x.hidden_on)?,
+                        hidden_with: x.hidden_with,
+                    })
+                })
+                .collect()
+        };
+        Ok(Some(KanbanBoard {
+            swimlanes,
+            id_sequence,
+            trash: hidden_items(board_index.trash)?,
+            archive: hidden_items(board_index.archive)?,
+            tag_colors,
+            dependencies: Dependencies {
+                blocked_by: board_index.dependencies,
+            },
+            people: board_index
+                .people
+                .into_iter()
+                .map(|x| Person {
+                    handle: x.handle,
+                    name: x.name,
+                    email: x.email,
+                })
+                .collect(),
+            timers: board_index
+                .timers
+                .into_iter()
+                .map(|x| {
+                    Ok(RunningTimer {
+                        item_id: x.id,
+                        user: x.user,
+                        started_at: timestamp_from_text(&x.started_at)?,
+                    })
+                })
+                .collect::<Result<Vec<RunningTimer>, AppError>>()?,
+            sprints: board_index
+                .sprints
+                .into_iter()
+                .map(|x| {
+                    Ok(Sprint {
+                        number: x.number,
+                        goal: x.goal,
+                        start_on: date_from_text(&x.start_on)?,
+                        end_on: date_from_text(&x.end_on)?,
+                        items: x.items,
+                        closed_on: optional_date_from_text(&x.closed_on)?,
+                        completed: x.completed,
+                        carried_over: x.carried_over,
+                    })
+                })
+                .collect::<Result<Vec<Sprint>, AppError>>()?,
+        }))
+    }
+
+    fn save_board(&self, boards: &KanbanBoard) -> Result<(), AppError> {
+        let swimlanes: Vec<SwimlaneEntry> = boards
+            .swimlanes
+            .iter()
+            .map(|x| {
+                let mut items: Vec<String> = x.items.clone();
+                items.sort_by_key(|x| id_sort_key(x));
+                SwimlaneEntry {
+                    slug: x.slug.clone(),
+                    name: x.name.clone(),
+                    stage: x.stage.to_string(),
+                    blocked: x.blocked,
+                    wip_limit: x.wip_limit.map(|x| x.max_items),
+                    wip_strict: x.wip_limit.map(|x| x.strict),
+                    items,
+                }
+            })
+            .collect();
+        let hidden_entries = |hidden_items: &[HiddenItem]| -> Result<Vec<HiddenEntry>, AppError> {
+            hidden_items
+                .iter()
+                .map(|x| {
+                    Ok(HiddenEntry {
+                        id: x.item_id.clone(),
+                        swimlane: x.swimlane.clone(),
+                        hidden_on: date_to_text(&x.hidden_on)?,
+                        hidden_with: x.hidden_with.clone(),
+                    })
+                })
+                .collect()
+        };
+        self.write_index(&BoardIndex {
+            schema_version: SCHEMA_VERSION,
+            id_prefix: boards.id_sequence.prefix.clone(),
+            last_task_number: boards.id_sequence.last_task_number,
+            last_subtask_numbers: boards
+                .id_sequence
+                .last_subtask_numbers
+                .iter()
+                .map(|(parent_id, number)| (parent_id.clone(), *number))
+                .collect(),
+            tag_colors: boards
+                .tag_colors
+                .iter()
+                .map(|(tag, color)| (tag.clone(), color.to_string()))
+                .collect(),
+            dependencies: boards.dependencies.blocked_by.clone(),
+            swimlanes,
+            trash: hidden_entries(&boards.trash)?,
+            archive: hidden_entries(&boards.archive)?,
+            people: boards
+                .people
+                .iter()
+                .map(|x| PersonEntry {
+                    handle: x.handle.clone(),
+                    name: x.name.clone(),
+                    email: x.email.clone(),
+                })
+                .collect(),
+            timers: boards
+                .timers
+                .iter()
+                .map(|x| {
+                    Ok(TimerEntry {
+                        id: x.item_id.clone(),
+                        user: x.user.clone(),
+                        started_at: timestamp_to_text(x.started_at)?,
+                    })
+                })
+                .collect::<Result<Vec<TimerEntry>, AppError>>()?,
+            sprints: boards
+                .sprints
+                .iter()
+                .map(|x| {
+                    Ok(SprintEntry {
+                        number: x.number,
+                        goal: x.goal.clone(),
+                        start_on: date_to_text(&x.start_on)?,
+                        end_on: date_to_text(&x.end_on)?,
+                        closed_on: optional_date_to_text(&x.closed_on)?,
+                        items: x.items.clone(),
+                        completed: x.completed.clone(),
+                        carried_over: x.carried_over.clone(),
+                    })
+                })
+                .collect::<Result<Vec<SprintEntry>, AppError>>()?,
+        })
+    }
+
+    fn load_notes(&self) -> Result<Option<TaskNotes>, AppError> {
+        if self.read_index()?.is_none() {
+            return Ok(None);
+        }
+
+        let mut notes: HashMap<String, Vec<String>> = HashMap::new();
+        for item_id in self.list_item_files()? {
+            let item: ItemFile = self.read_item_file(&item_id)?;
+            if !item.fields.notes.is_empty() {
+                notes.insert(item_id, item.fields.notes);
+            }
+        }
+        Ok(Some(TaskNotes { notes }))
+    }
+
+    fn save_notes(&self, task_notes: &TaskNotes) -> Result<(), AppError> {
+        // Only files whose notes changed are written, to keep the diffs small
+        for item_id in self.list_item_files()? {
+            let mut item: ItemFile = self.read_item_file(&item_id)?;
+            let notes: Vec<String> = task_notes.notes.get(&item_id).cloned().unwrap_or_default();
+            if item.fields.notes != notes {
+                item.fields.notes = notes;
+                self.write_item(&item)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn get_item(&self, item_id: &str) -> Result<WorkItem, AppError> {
+        let item: ItemFile = self.read_item_file(item_id)?;
+        Ok(WorkItem {
+            id: item.fields.id,
+            name: item.fields.name,
+            description: item.description,
+            added_on: date_from_text(&item.fields.added_on)?,
+            started_on: optional_date_from_text(&item.fields.started_on)?,
+            deadline: optional_date_from_text(&item.fields.deadline)?,
+            completed_on: optional_date_from_text(&item.fields.completed_on)?,
+            status: item.fields.status,
+            priority: item.fields.priority.parse::<TaskPriority>()?,
+            tags: item.fields.tags,
+            assignee: item.fields.assignee,
+            parent: item.fields.parent,
+            worklog: item
+                .fields
+                .worklog
+                .iter()
+                .map(|x| {
+                    Ok(WorkLog {
+                        logged_on: date_from_text(&x.logged_on)?,
+                        minutes: x.minutes,
+                        message: x.message.clone(),
+                        author: x.author.clone(),
+                    })
+                })
+                .collect::<Result<Vec<WorkLog>, AppError>>()?,
+            estimate: match &item.fields.estimate {
+                Some(s) => Some(Estimate {
+                    unit: s.unit.parse::<EstimateUnit>()?,
+                    original: s.original,
+                    remaining: s.remaining,
+                }),
+                None => None,
+            },
+        })
+    }
+
+    fn save_item(&self, work_item: &WorkItem) -> Result<(), AppError> {
+        // Notes are kept in the file, but stored separately through `save_notes`
+        let notes: Vec<String> = match self.read_item(&work_item.id)? {
+            Some(s) => s.fields.notes,
+            None => Vec::new(),
+        };
+        self.write_item(&ItemFile {
+            fields: ItemFields {
+                id: work_item.id.clone(),
+                name: work_item.name.clone(),
+                status: work_item.status.clone(),
+                priority: work_item.priority.to_string().to_lowercase(),
+                tags: work_item.tags.clone(),
+                assignee: work_item.assignee.clone(),
+                added_on: date_to_text(&work_item.added_on)?,
+                started_on: optional_date_to_text(&work_item.started_on)?,
+                deadline: optional_date_to_text(&work_item.deadline)?,
+                completed_on: optional_date_to_text(&work_item.completed_on)?,
+                parent: work_item.parent.clone(),
+                estimate: work_item.estimate.map(|x| EstimateEntry {
+                    unit: x.unit.to_string(),
+                    original: x.original,
+                    remaining: x.remaining,
+                }),
+                notes,
+                worklog: work_item
+                    .worklog
+                    .iter()
+                    .map(|x| {
+                        Ok(WorkLogEntry {
+                            logged_on: date_to_text(&x.logged_on)?,
+                            minutes: x.minutes,
+                            message: x.message.clone(),
+                            author: x.author.clone(),
+                        })
+                    })
+                    .collect::<Result<Vec<WorkLogEntry>, AppError>>()?,
+            },
+            description: work_item.description.clone(),
+        })
+    }
+
+    fn delete_item(&self, item_id: &str) -> Result<(), AppError> {
+        self.delete_file(&self.item_path(ITEMS_PATH, item_id))
+    }
+
+    fn item_exists(&self, item_id: &str) -> Result<bool, AppError> {
+        Ok(self.item_path(ITEMS_PATH, item_id).exists())
+    }
+
+    fn list_items(&self) -> Result<Vec<String>, AppError> {
+        self.list_item_files()
+    }
+
+    fn migrate(&self, check_only: bool) -> Result<Vec<String>, AppError> {
+        let mut changes: Vec<String> = self.merge_items(check_only)?.into_iter().collect();
+        let board_index: BoardIndex = match self.read_index()? {
+            Some(s) => s,
+            None => return Ok(changes),
+        };
+        let change: Option<String> = migrations::describe_pending(
+            &self.board_dir.join(TEXT_INDEX_FILE).display().to_string(),
+            board_index.schema_version,
+        );
+        // Fields added by later schema versions are optional in the text format, so upgrading only
+        // records the current schema version in the index (and which swimlane holds blocked items)
+        if change.is_some() && !check_only {
+            let mut swimlanes: Vec<SwimlaneEntry> = board_index.swimlanes;
+            if board_index.schema_version < BLOCKED_SWIMLANES_VERSION {
+                for entry in &mut swimlanes {
+                    entry.blocked = entry.blocked || entry.slug == LEGACY_BLOCKED_SWIMLANE;
+                }
+            }
+            self.write_index(&BoardIndex {
+                schema_version: SCHEMA_VERSION,
+                swimlanes,
+                ..board_index
+            })?;
+        }
+        changes.extend(change);
+        Ok(changes)
+    }
+}
+
+/// Parse given index file
+fn parse_index(index_file: &Path, text: &str) -> Result<BoardIndex, AppError> {
+    match toml::from_str(text) {
+        Ok(s) => Ok(s),
+        Err(e) => Err(AppError::TextFormatError(format!(
+            "{} - {}",
+            index_file.display(),
+            e
+        ))),
+    }
+}
+
+/// Split given text with git conflict markers into the version of the current branch and the
+/// version of the merged branch (None if the text has no conflict markers)
+fn split_conflict(text: &str) -> Option<(String, String)> {
+    if !text.lines().any(|x| x.starts_with(CONFLICT_OURS_MARKER)) {
+        return None;
+    }
+
+    let mut ours: String = String::new();
+    let mut theirs: String = String::new();
+    // Section of the conflict the current line belongs to (None outside of a conflict)
+    let mut section: Option<&str> = None;
+    for line in text.lines() {
+        if line.starts_with(CONFLICT_OURS_MARKER) {
+            section = Some(CONFLICT_OURS_MARKER);
+        } else if line.starts_with(CONFLICT_BASE_MARKER) && section.is_some() {
+            section = Some(CONFLICT_BASE_MARKER);
+        } else if line == CONFLICT_THEIRS_MARKER && section.is_some() {
+            section = Some(CONFLICT_THEIRS_MARKER);
+        } else if line.starts_with(CONFLICT_END_MARKER) && section.is_some() {
+            section = None;
+        } else {
+            match section {
+                None => {
+                    ours.push_str(&format!("{}\n", line));
+                    theirs.push_str(&format!("{}\n", line));
+                }
+                Some(CONFLICT_OURS_MARKER) => ours.push_str(&format!("{}\n", line)),
+                Some(CONFLICT_THEIRS_MARKER) => theirs.push_str(&format!("{}\n", line)),
+                Some(_) => {}
+            }
+        }
+    }
+    Some((ours, theirs))
+}
+
+/// Split given task or subtask file into the front-matter and the description
+fn parse_item_file(item_file: &Path, text: &str) -> Result<ItemFile, AppError> {
+    let format_error =
+        |message: &str| AppError::TextFormatError(format!("{} - {}", item_file.display(), message));
+
+    let text: String = text.replace("\r\n", "\n");
+    let rest: &str = match text.strip_prefix(&format!("{}\n", FRONT_MATTER_DELIMITER)) {
+        Some(s) => s,
+        None => return Err(format_error("missing front-matter")),
+    };
+    let (front_matter, description): (&str, &str) =
+        match rest.split_once(&format!("\n{}\n", FRONT_MATTER_DELIMITER)) {
+            Some(s) => s,
+            None => match rest.strip_suffix(&format!("\n{}", FRONT_MATTER_DELIMITER)) {
+                Some(s) => (s, ""),
+                None => return Err(format_error("unterminated front-matter")),
+            },
+        };
+    let fields: ItemFields = match toml::from_str(front_matter) {
+        Ok(s) => s,
+        Err(e) => return Err(format_error(&e.to_string())),
+    };
+    Ok(ItemFile {
+        fields,
+        description: description.trim_matches('\n').to_string(),
+    })
+}
+
+/// Store dates as ISO-8601 text (yyyy-mm-dd)
+fn date_to_text(date: &TimeStamp) -> Result<String, AppError> {
+    Ok(date.to_naivedate()?.format("%Y-%m-%d").to_string())
+}
+
+fn optional_date_to_text(date: &Option<TimeStamp>) -> Result<Option<String>, AppError> {
+    match date {
+        Some(s) => Ok(Some(date_to_text(s)?)),
+        None => Ok(None),
+    }
+}
+
+fn date_from_text(date: &str) -> Result<TimeStamp, AppError> {
+    match NaiveDate::parse_from_str(date, "%Y-%m-%d") {
+        Ok(s) => Ok(TimeStamp::convert(s)),
+        Err(e) => Err(AppError::TextFormatError(format!("{} - {}", date, e))),
+    }
+}
+
+/// Store times as RFC 3339 text in the local time zone (e.g. 2024-05-01T09:30:00+02:00)
+fn timestamp_to_text(timestamp: i64) -> Result<String, AppError> {
+    match Local.timestamp_opt(timestamp, 0).single() {
+        Some(s) => Ok(s.to_rfc3339()),
+        None => Err(AppError::TextFormatError(timestamp.to_string())),
+    }
+}
+
+fn timestamp_from_text(timestamp: &str) -> Result<i64, AppError> {
+    match DateTime::parse_from_rfc3339(timestamp) {
+        Ok(s) => Ok(s.timestamp()),
+        Err(e) => Err(AppError::TextFormatError(format!("{} - {}", timestamp, e))),
+    }
+}
+
+fn optional_date_from_text(date: &Option<String>) -> Result<Option<TimeStamp>, AppError> {
+    match date {
+        Some(s) => Ok(Some(date_from_text(s)?)),
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_dir;
+
+    /// Store a new task with given ID and name in the first swimlane of given board
+    fn add_task(storage: &TextStorage, boards: &mut KanbanBoard, item_id: &str, name: &str) {
+        create_dir(&storage.board_dir.join(ITEMS_PATH)).unwrap();
+        let work_item: WorkItem = WorkItem::new(
+            item_id.to_string(),
+            name.to_string(),
+            String::new(),
+            None,
+            TaskPriority::Medium,
+            &boards.swimlanes[0],
+            None,
+        )
+        .unwrap();
+        storage.save_item(&work_item).unwrap();
+        boards.swimlanes[0].items.push(item_id.to_string());
+    }
+
+    #[test]
+    fn split_conflict_keeps_both_versions() {
+        let text: &str = "a\n<<<<<<< HEAD\nb\n||||||| base\nc\n=======\nd\n>>>>>>> branch\ne\n";
+        assert_eq!(
+            split_conflict(text),
+            Some(("a\nb\ne\n".to_string(), "a\nd\ne\n".to_string()))
+        );
+        assert_eq!(split_conflict("a\n=======\nb\n"), None);
+    }
+
+    #[test]
+    fn id_numbers_are_not_reused_after_deleting_newest_task() {
+        let storage: TextStorage = TextStorage::new(test_dir("text_id_numbers"));
+        let mut boards: KanbanBoard = KanbanBoard::new();
+        add_task(&storage, &mut boards, "TASK-1", "First");
+        add_task(&storage, &mut boards, "TASK-2", "Second");
+        boards.id_sequence.last_task_number = 2;
+        storage.save_board(&boards).unwrap();
+
+        storage.delete_item("TASK-2").unwrap();
+        let mut boards: KanbanBoard = storage.load_board().unwrap().unwrap();
+        assert_eq!(boards.id_sequence.last_task_number, 2);
+        assert_eq!(boards.id_sequence.next_task_id(&storage).unwrap(), "TASK-3");
+    }
+
+    #[test]
+    fn id_counters_conflicting_in_index_take_the_higher_number() {
+        let board_dir: PathBuf = test_dir("text_index_conflict");
+        let storage: TextStorage = TextStorage::new(board_dir.clone());
+        let mut boards: KanbanBoard = KanbanBoard::new();
+        boards.id_sequence.last_task_number = 4;
+        storage.save_board(&boards).unwrap();
+
+        let index_file: PathBuf = board_dir.join(TEXT_INDEX_FILE);
+        let text: String = std::fs::read_to_string(&index_file).unwrap().replace(
+            "last_task_number = 4\n",
+            "<<<<<<< HEAD\nlast_task_number = 4\n=======\nlast_task_number = 7\n>>>>>>> branch\n",
+        );
+        std::fs::write(&index_file, text).unwrap();
+
+        TextStorage::open(board_dir).unwrap();
+        let boards: KanbanBoard = storage.load_board().unwrap().unwrap();
+        assert_eq!(boards.id_sequence.last_task_number, 7);
+    }
+
+    #[test]
+    fn items_added_on_both_branches_are_split() {
+        let board_dir: PathBuf = test_dir("text_item_collision");
+        let storage: TextStorage = TextStorage::new(board_dir.clone());
+        let mut boards: KanbanBoard = KanbanBoard::new();
+        add_task(&storage, &mut boards, "TASK-1", "Ours");
+        boards.id_sequence.last_task_number = 1;
+        storage.save_board(&boards).unwrap();
+        let item_file: PathBuf = storage.item_path(ITEMS_PATH, "TASK-1");
+        let ours: String = std::fs::read_to_string(&item_file).unwrap();
+        let theirs: String = ours.replace("Ours", "Theirs");
+        std::fs::write(
+            &item_file,
+            format!("<<<<<<< HEAD\n{}=======\n{}>>>>>>> branch\n", ours, theirs),
+        )
+        .unwrap();
+
+        let storage: TextStorage = TextStorage::open(board_dir).unwrap();
+        assert_eq!(storage.get_item("TASK-1").unwrap().name, "Ours");
+        assert_eq!(storage.get_item("TASK-2").unwrap().name, "Theirs");
+        let boards: KanbanBoard = storage.load_board().unwrap().unwrap();
+        assert_eq!(boards.id_sequence.last_task_number, 2);
+        assert_eq!(boards.swimlanes[0].items, vec!["TASK-1", "TASK-2"]);
+    }
+
+    /// Index file written with schema version 9, before swimlanes were flagged as blocked
+    const INDEX_V9: &str = r#"
+        schema_version = 9
+        id_prefix = "TASK"
+        last_task_number = 1
+
+        [[swimlanes]]
+        slug = "to-do"
+        name = "To-Do"
+        stage = "pending"
+        items = ["TASK-1"]
+
+        [[swimlanes]]
+        slug = "blocked"
+        name = "Blocked"
+        stage = "started"
+        items = ["TASK-1.1"]
+
+        [[swimlanes]]
+        slug = "done"
+        name = "Done"
+        stage = "done"
+        items = []
+    "#;
+
+    /// Front-matter of a task file kept in the tasks directory of the legacy layout
+    const TASK_V9: &str = r#"
+        id = "TASK-1"
+        name = "Parent"
+        status = "to-do"
+        priority = "high"
+        added_on = "2024-03-01"
+    "#;
+
+    /// Front-matter of a subtask file kept in the subtasks directory of the legacy layout
+    const SUBTASK_V9: &str = r#"
+        id = "TASK-1.1"
+        name = "Child"
+        status = "blocked"
+        priority = "low"
+        added_on = "2024-03-01"
+        parent = "TASK-1"
+        notes = ["Waiting on review"]
+    "#;
+
+    #[test]
+    fn legacy_layout_is_migrated_and_round_trips() {
+        let board_dir: PathBuf = test_dir("text_migration");
+        let storage: TextStorage = TextStorage::new(board_dir.clone());
+        std::fs::write(board_dir.join(TEXT_INDEX_FILE), INDEX_V9).unwrap();
+        for (items_dir, item_id, front_matter) in [
+            (ACTIVE_TASKS_PATH, "TASK-1", TASK_V9),
+            (ACTIVE_SUBTASKS_PATH, "TASK-1.1", SUBTASK_V9),
+        ] {
+            create_dir(&board_dir.join(items_dir)).unwrap();
+            std::fs::write(
+                storage.item_path(items_dir, item_id),
+                format!(
+                    "+++\n{}\n+++\n\nWritten in the legacy layout\n",
+                    front_matter.trim()
+                ),
+            )
+            .unwrap();
+        }
+
+        let pending: Vec<String> = storage.migrate(true).unwrap();
+        assert_eq!(pending.len(), 2, "{:?}", pending);
+        assert!(board_dir.join(ACTIVE_SUBTASKS_PATH).exists());
+        let storage: TextStorage = TextStorage::open(board_dir.clone()).unwrap();
+        assert!(!board_dir.join(ACTIVE_SUBTASKS_PATH).exists());
+        assert_eq!(storage.migrate(false).unwrap().len(), 1);
+        assert!(storage.migrate(true).unwrap().is_empty());
+        let index_text: String = std::fs::read_to_string(board_dir.join(TEXT_INDEX_FILE)).unwrap();
+        assert!(index_text.starts_with(&format!("schema_version = {}\n", SCHEMA_VERSION)));
+
+        let boards: KanbanBoard = storage.load_board().unwrap().unwrap();
+        assert_eq!(boards.swimlanes[0].items, ["TASK-1"]);
+        assert_eq!(boards.swimlanes[1].items, ["TASK-1.1"]);
+        assert!(boards.swimlanes[1].blocked && !boards.swimlanes[0].blocked);
+        assert_eq!(boards.id_sequence.last_task_number, 1);
+
+        let subtask_item: WorkItem = storage.get_item("TASK-1.1").unwrap();
+        assert_eq!(subtask_item.parent, Some("TASK-1".to_string()));
+        assert_eq!(subtask_item.priority, TaskPriority::Low);
+        assert_eq!(subtask_item.description, "Written in the legacy layout");
+        let task_notes: TaskNotes = storage.load_notes().unwrap().unwrap();
+        assert_eq!(task_notes.notes["TASK-1.1"], ["Waiting on review"]);
+
+        storage.save_board(&boards).unwrap();
+        storage.save_item(&subtask_item).unwrap();
+        assert_eq!(storage.load_board().unwrap(), Some(boards));
+        assert_eq!(storage.get_item("TASK-1.1").unwrap(), subtask_item);
+    }
+}
@@ -0,0 +1,505 @@
+//! Defines the unit of work, which stages changes to the Kanban Board, notes and work items in
+//! memory and commits them to the storage backend together
+
+use crate::{
+    boards::KanbanBoard, error::AppError, items::WorkItem, notes::TaskNotes, storage::Storage,
+};
+use serde::{Deserialize, Serialize};
+use std::{cell::RefCell, collections::BTreeMap};
+
+/// Change to apply to the storage backend
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum Change {
+    SaveBoard(KanbanBoard),
+    SaveNotes(TaskNotes),
+    SaveItem(WorkItem),
+    DeleteItem(String),
+}
+
+/// Changes written by a committed unit of work, along with the changes which undo them
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChangeSet {
+    /// Changes in the order they were written
+    pub changes: Vec<Change>,
+
+    /// Changes which undo them, in the order they have to be written
+    pub inverses: Vec<Change>,
+
+    /// Parts of the storage which were written, as read back once all changes were written
+    pub written: Vec<Change>,
+}
+
+impl ChangeSet {
+    /// Check if nothing was written
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Fetch the Task and SubTask IDs which were saved or deleted
+    pub fn item_ids(&self) -> Vec<String> {
+        self.changes
+            .iter()
+            .filter_map(|x| match x {
+                Change::SaveItem(work_item) => Some(work_item.id.clone()),
+                Change::DeleteItem(item_id) => Some(item_id.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// Rust structure for a unit of work over a storage backend. It is passed wherever a storage is
+/// expected: reads see the staged changes, while writes are only staged until `commit` is called.
+/// Dropping it without committing discards all staged changes.
+pub struct UnitOfWork<'a> {
+    /// Storage backend the changes are committed to
+    storage: &'a dyn Storage,
+
+    /// Staged Kanban Board
+    board: RefCell<Option<KanbanBoard>>,
+
+    /// Staged notes for Tasks and SubTasks
+    notes: RefCell<Option<TaskNotes>>,
+
+    /// Staged items for each Task or SubTask ID (None if deleted)
+    items: RefCell<BTreeMap<String, Option<WorkItem>>>,
+}
+
+impl<'a> UnitOfWork<'a> {
+    /// Start a new unit of work over given storage backend
+    pub fn new(storage: &'a dyn Storage) -> Self {
+        UnitOfWork {
+            storage,
+            board: RefCell::new(None),
+            notes: RefCell::new(None),
+            items: RefCell::new(BTreeMap::new()),
+        }
+    }
+
+    /// Stage given change, as if it was written through the storage methods
+    pub fn stage(&self, change: &Change) -> Result<(), AppError> {
+        apply(self, change)
+    }
+
+    /// Write all staged changes to the storage backend. Tasks and subtasks are saved first and
+    /// deleted last, so that the board never points at missing items. Backends with transactions
+    /// write all changes in a single one; on the others, if any write fails, the changes written
+    /// so far are rolled back before the error is returned.
+    /// Returns the changes written along with the changes which undo them.
+    pub fn commit(self) -> Result<ChangeSet, AppError> {
+        let mut changes: Vec<Change> = Vec::new();
+        let mut deletes: Vec<Change> = Vec::new();
+        for (item_id, work_item) in self.items.into_inner() {
+            match work_item {
+                Some(s) => changes.push(Change::SaveItem(s)),
+                None => deletes.push(Change::DeleteItem(item_id)),
+            }
+        }
+        // Some backends drop the notes of deleted items, so they are written along with the
+        // deletes to be restored by a rollback or undo
+        if !deletes.is_empty() && self.notes.borrow().is_none() {
+            *self.notes.borrow_mut() = self.storage.load_notes()?;
+        }
+        changes.extend(self.board.into_inner().map(Change::SaveBoard));
+        changes.extend(self.notes.into_inner().map(Change::SaveNotes));
+        changes.extend(deletes);
+
+        let transaction: bool = self.storage.begin_transaction()?;
+        let mut inverses: Vec<Change> = Vec::new();
+        let mut result: Result<(), AppError> = Ok(());
+        for change in &changes {
+            result = match inverse_of(self.storage, change) {
+                Ok(inverse) => apply(self.storage, change).map(|_| inverses.extend(inverse)),
+                Err(e) => Err(e),
+            };
+            if result.is_err() {
+                break;
+            }
+        }
+        if transaction {
+            result = result.and_then(|_| self.storage.commit_transaction());
+        }
+        if let Err(e) = result {
+            let rollback: Result<(), AppError> = match transaction {
+                true => self.storage.rollback_transaction(),
+                false => inverses
+                    .iter()
+                    .rev()
+                    .try_for_each(|inverse| apply(self.storage, inverse)),
+            };
+            return match rollback {
+                Ok(_) => Err(e),
+                Err(rollback_error) => Err(AppError::TransactionError(format!(
+                    "{} (rolling back also failed: {})",
+                    e, rollback_error
+                ))),
+            };
+        }
+        inverses.reverse();
+        let written: Vec<Change> = snapshot(self.storage, &changes)?;
+        Ok(ChangeSet {
+            changes,
+            inverses,
+            written,
+        })
+    }
+}
+
+/// Write given changes to the storage backend together (e.g. to undo or redo a command)
+pub fn replay(storage: &dyn Storage, changes: &[Change]) -> Result<ChangeSet, AppError> {
+    let unit: UnitOfWork = UnitOfWork::new(storage);
+    for change in changes {
+        unit.stage(change)?;
+    }
+    unit.commit()
+}
+
+/// Read the parts of the storage which given changes write, in the same form as the changes
+/// (an item which is not stored is given as deleted)
+pub fn snapshot(storage: &dyn Storage, changes: &[Change]) -> Result<Vec<Change>, AppError> {
+    let mut parts: Vec<Change> = Vec::new();
+    for change in changes {
+        let part: Option<Change> = match change {
+            Change::SaveBoard(_) => storage.load_board()?.map(Change::SaveBoard),
+            Change::SaveNotes(_) => storage.load_notes()?.map(Change::SaveNotes),
+            Change::SaveItem(WorkItem { id: item_id, .. }) | Change::DeleteItem(item_id) => {
+                match storage.item_exists(item_id)? {
+                    true => Some(Change::SaveItem(storage.get_item(item_id)?)),
+                    false => Some(Change::DeleteItem(item_id.clone())),
+                }
+            }
+        };
+        parts.extend(part);
+    }
+    Ok(parts)
+}
+
+/// Describe the parts of the storage which no longer hold what given changes wrote (empty if all
+/// of them still do)
+pub fn changed_since(storage: &dyn Storage, written: &[Change]) -> Result<Vec<String>, AppError> {
+    let current: Vec<Change> = snapshot(storage, written)?;
+    let mut changed: Vec<String> = Vec::new();
+    for change in written {
+        if current.contains(change) {
+            continue;
+        }
+        changed.push(match change {
+            Change::SaveBoard(_) => "the board".to_string(),
+            Change::SaveNotes(_) => "the notes".to_string(),
+            Change::SaveItem(WorkItem { id: item_id, .. }) | Change::DeleteItem(item_id) => {
+                item_id.clone()
+            }
+        });
+    }
+    Ok(changed)
+}
+
+impl Storage for UnitOfWork<'_> {
+    fn load_board(&self) -> Result<Option<KanbanBoard>, AppError> {
+        match &*self.board.borrow() {
+            Some(s) => Ok(Some(s.clone())),
+            None => self.storage.load_board(),
+        }
+    }
+
+    fn save_board(&self, boards: &KanbanBoard) -> Result<(), AppError> {
+        *self.board.borrow_mut() = Some(boards.clone());
+        Ok(())
+    }
+
+    fn load_notes(&self) -> Result<Option<TaskNotes>, AppError> {
+        match &*self.notes.borrow() {
+            Some(s) => Ok(Some(s.clone())),
+            None => self.storage.load_notes(),
+        }
+    }
+
+    fn save_notes(&self, task_notes: &TaskNotes) -> Result<(), AppError> {
+        *self.notes.borrow_mut() = Some(task_notes.clone());
+        Ok(())
+    }
+
+    fn get_item(&self, item_id: &str) -> Result<WorkItem, AppError> {
+        match self.items.borrow().get(item_id) {
+            Some(Some(s)) => Ok(s.clone()),
+            Some(None) => Err(AppError::TaskNotFound(item_id.to_string())),
+            None => self.storage.get_item(item_id),
+        }
+    }
+
+    fn save_item(&self, work_item: &WorkItem) -> Result<(), AppError> {
+        self.items
+            .borrow_mut()
+            .insert(work_item.id.clone(), Some(work_item.clone()));
+        Ok(())
+    }
+
+    fn delete_item(&self, item_id: &str) -> Result<(), AppError> {
+        self.items.borrow_mut().insert(item_id.to_string(), None);
+        Ok(())
+    }
+
+    fn item_exists(&self, item_id: &str) -> Result<bool, AppError> {
+        match self.items.borrow().get(item_id) {
+            Some(s) => Ok(s.is_some()),
+            None => self.storage.item_exists(item_id),
+        }
+    }
+
+    fn list_items(&self) -> Result<Vec<String>, AppError> {
+        Ok(staged_list(
+            self.storage.list_items()?,
+            &self.items.borrow(),
+        ))
+    }
+
+    fn migrate(&self, check_only: bool) -> Result<Vec<String>, AppError> {
+        self.storage.migrate(check_only)
+    }
+}
+
+/// Combine the IDs listed by the storage backend with the staged saves and deletes
+fn staged_list<T>(mut item_ids: Vec<String>, staged: &BTreeMap<String, Option<T>>) -> Vec<String> {
+    item_ids.retain(|x| !staged.contains_key(x));
+    item_ids.extend(
+        staged
+            .iter()
+            .filter(|(_, item)| item.is_some())
+            .map(|(item_id, _)| item_id.clone()),
+    );
+    item_ids
+}
+
+/// Apply given change to the storage backend
+fn apply(storage: &dyn Storage, change: &Change) -> Result<(), AppError> {
+    match change {
+        Change::SaveBoard(boards) => storage.save_board(boards),
+        Change::SaveNotes(task_notes) => storage.save_notes(task_notes),
+        Change::SaveItem(work_item) => storage.save_item(work_item),
+        Change::DeleteItem(item_id) => storage.delete_item(item_id),
+    }
+}
+
+/// Work out the change which undoes given change, from what is currently stored
+/// (None if there is nothing to undo, e.g. when saving the board for the first time)
+fn inverse_of(storage: &dyn Storage, change: &Change) -> Result<Option<Change>, AppError> {
+    let inverse: Option<Change> = match change {
+        Change::SaveBoard(_) => storage.load_board()?.map(Change::SaveBoard),
+        Change::SaveNotes(_) => storage.load_notes()?.map(Change::SaveNotes),
+        Change::SaveItem(work_item) => match storage.item_exists(&work_item.id)? {
+            true => Some(Change::SaveItem(storage.get_item(&work_item.id)?)),
+            false => Some(Change::DeleteItem(work_item.id.clone())),
+        },
+        Change::DeleteItem(item_id) => match storage.item_exists(item_id)? {
+            true => Some(Change::SaveItem(storage.get_item(item_id)?)),
+            false => None,
+        },
+    };
+    Ok(inverse)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        constants::ITEMS_PATH,
+        storage::{bincode_store::BincodeStorage, sqlite_store::SqliteStorage},
+        utils::{create_dir, test_dir, TaskPriority},
+    };
+
+    /// Storage which fails to save the item with given ID, passing everything else through
+    struct FailingStorage {
+        /// Storage the calls are passed to
+        inner: BincodeStorage,
+
+        /// Task or SubTask ID which can't be saved
+        fail_on: String,
+    }
+
+    impl Storage for FailingStorage {
+        fn load_board(&self) -> Result<Option<KanbanBoard>, AppError> {
+            self.inner.load_board()
+        }
+
+        fn save_board(&self, boards: &KanbanBoard) -> Result<(), AppError> {
+            self.inner.save_board(boards)
+        }
+
+        fn load_notes(&self) -> Result<Option<TaskNotes>, AppError> {
+            self.inner.load_notes()
+        }
+
+        fn save_notes(&self, task_notes: &TaskNotes) -> Result<(), AppError> {
+            self.inner.save_notes(task_notes)
+        }
+
+        fn get_item(&self, item_id: &str) -> Result<WorkItem, AppError> {
+            self.inner.get_item(item_id)
+        }
+
+        fn save_item(&self, work_item: &WorkItem) -> Result<(), AppError> {
+            match work_item.id == self.fail_on {
+                true => Err(AppError::FileWriteError(work_item.id.clone())),
+                false => self.inner.save_item(work_item),
+            }
+        }
+
+        fn delete_item(&self, item_id: &str) -> Result<(), AppError> {
+            self.inner.delete_item(item_id)
+        }
+
+        fn item_exists(&self, item_id: &str) -> Result<bool, AppError> {
+            self.inner.item_exists(item_id)
+        }
+
+        fn list_items(&self) -> Result<Vec<String>, AppError> {
+            self.inner.list_items()
+        }
+
+        fn migrate(&self, check_only: bool) -> Result<Vec<String>, AppError> {
+            self.inner.migrate(check_only)
+        }
+    }
+
+    /// Open a storage in a blank test directory with given name, failing to save given ID
+    fn test_storage(name: &str, fail_on: &str) -> FailingStorage {
+        let board_dir: std::path::PathBuf = test_dir(name);
+        create_dir(&board_dir.join(ITEMS_PATH)).unwrap();
+        FailingStorage {
+            inner: BincodeStorage::new(board_dir),
+            fail_on: fail_on.to_string(),
+        }
+    }
+
+    /// Create a task with given ID and name in the first swimlane of given board
+    fn new_task(boards: &KanbanBoard, item_id: &str, name: &str) -> WorkItem {
+        WorkItem::new(
+            item_id.to_string(),
+            name.to_string(),
+            String::new(),
+            None,
+            TaskPriority::Medium,
+            &boards.swimlanes[0],
+            None,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn staged_changes_are_only_written_on_commit() {
+        let storage: FailingStorage = test_storage("unit_staged", "");
+        let boards: KanbanBoard = KanbanBoard::new();
+
+        let unit: UnitOfWork = UnitOfWork::new(&storage);
+        unit.save_item(&new_task(&boards, "TASK-1", "First"))
+            .unwrap();
+        assert!(unit.item_exists("TASK-1").unwrap());
+        assert_eq!(unit.list_items().unwrap(), ["TASK-1"]);
+        assert!(!storage.item_exists("TASK-1").unwrap());
+        drop(unit);
+        assert!(!storage.item_exists("TASK-1").unwrap());
+
+        let unit: UnitOfWork = UnitOfWork::new(&storage);
+        unit.save_item(&new_task(&boards, "TASK-1", "First"))
+            .unwrap();
+        let change_set: ChangeSet = unit.commit().unwrap();
+        assert_eq!(storage.get_item("TASK-1").unwrap().name, "First");
+        assert_eq!(
+            change_set.inverses,
+            [Change::DeleteItem("TASK-1".to_string())]
+        );
+    }
+
+    #[test]
+    fn failed_commit_rolls_back_the_changes_written_so_far() {
+        let storage: FailingStorage = test_storage("unit_rollback", "TASK-3");
+        let mut boards: KanbanBoard = KanbanBoard::new();
+        storage
+            .save_item(&new_task(&boards, "TASK-1", "Old"))
+            .unwrap();
+        storage
+            .save_item(&new_task(&boards, "TASK-4", "Kept"))
+            .unwrap();
+        boards.swimlanes[0].items = vec!["TASK-1".to_string(), "TASK-4".to_string()];
+        storage.save_board(&boards).unwrap();
+
+        let unit: UnitOfWork = UnitOfWork::new(&storage);
+        unit.save_item(&new_task(&boards, "TASK-1", "New")).unwrap();
+        unit.save_item(&new_task(&boards, "TASK-2", "Added"))
+            .unwrap();
+        unit.save_item(&new_task(&boards, "TASK-3", "Failing"))
+            .unwrap();
+        unit.delete_item("TASK-4").unwrap();
+        let mut changed_boards: KanbanBoard = boards.clone();
+        changed_boards.swimlanes[0].items.clear();
+        unit.save_board(&changed_boards).unwrap();
+
+        assert!(matches!(unit.commit(), Err(AppError::FileWriteError(_))));
+        assert_eq!(storage.get_item("TASK-1").unwrap().name, "Old");
+        assert!(!storage.item_exists("TASK-2").unwrap());
+        assert!(storage.item_exists("TASK-4").unwrap());
+        assert_eq!(storage.load_board().unwrap(), Some(boards));
+    }
+
+    #[test]
+    fn replaying_the_inverses_undoes_a_commit() {
+        let storage: FailingStorage = test_storage("unit_replay", "");
+        let boards: KanbanBoard = KanbanBoard::new();
+        storage
+            .save_item(&new_task(&boards, "TASK-1", "Old"))
+            .unwrap();
+
+        let unit: UnitOfWork = UnitOfWork::new(&storage);
+        unit.save_item(&new_task(&boards, "TASK-1", "New")).unwrap();
+        unit.save_item(&new_task(&boards, "TASK-2", "Added"))
+            .unwrap();
+        let change_set: ChangeSet = unit.commit().unwrap();
+        assert!(changed_since(&storage, &change_set.written)
+            .unwrap()
+            .is_empty());
+
+        let undone: ChangeSet = replay(&storage, &change_set.inverses).unwrap();
+        assert_eq!(storage.get_item("TASK-1").unwrap().name, "Old");
+        assert!(!storage.item_exists("TASK-2").unwrap());
+        assert_eq!(
+            changed_since(&storage, &change_set.written).unwrap(),
+            ["TASK-1", "TASK-2"]
+        );
+        assert!(changed_since(&storage, &undone.written).unwrap().is_empty());
+    }
+
+    #[test]
+    fn failed_commit_on_sqlite_leaves_nothing_written() {
+        let storage: SqliteStorage = SqliteStorage::open(&test_dir("unit_sqlite")).unwrap();
+        let mut boards: KanbanBoard = KanbanBoard::new();
+        storage
+            .save_item(&new_task(&boards, "TASK-1", "Old"))
+            .unwrap();
+        boards.swimlanes[0].items = vec!["TASK-1".to_string()];
+        storage.save_board(&boards).unwrap();
+
+        // The board is saved after the items, and fails on the task which isn't stored
+        let unit: UnitOfWork = UnitOfWork::new(&storage);
+        unit.save_item(&new_task(&boards, "TASK-1", "New")).unwrap();
+        unit.save_item(&new_task(&boards, "TASK-2", "Added"))
+            .unwrap();
+        let mut changed_boards: KanbanBoard = boards.clone();
+        changed_boards.swimlanes[0].items = vec!["TASK-1".to_string(), "TASK-9".to_string()];
+        unit.save_board(&changed_boards).unwrap();
+
+        assert!(matches!(unit.commit(), Err(AppError::DatabaseError(_))));
+        assert_eq!(storage.get_item("TASK-1").unwrap().name, "Old");
+        assert!(!storage.item_exists("TASK-2").unwrap());
+        assert_eq!(storage.load_board().unwrap(), Some(boards.clone()));
+
+        let unit: UnitOfWork = UnitOfWork::new(&storage);
+        unit.save_item(&new_task(&boards, "TASK-2", "Added"))
+            .unwrap();
+        let change_set: ChangeSet = unit.commit().unwrap();
+        assert!(storage.item_exists("TASK-2").unwrap());
+        assert_eq!(
+            change_set.inverses,
+            [Change::DeleteItem("TASK-2".to_string())]
+        );
+    }
+}
@@ -0,0 +1,168 @@
+//! Defines the user-defined swimlanes of a Kanban Board along with associated helper methods
+
+use crate::error::AppError;
+use serde::{Deserialize, Serialize};
+
+/// Stage of work which a swimlane represents, driving when tasks get their started and
+/// completed dates
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq)]
+pub enum LaneStage {
+    /// Work has not started yet (e.g. Backlog, To-Do)
+    Pending,
+
+    /// Work has started (moving a task here sets its started date)
+    Started,
+
+    /// Work is done (moving a task here sets its completed date)
+    Done,
+}
+
+impl std::fmt::Display for LaneStage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LaneStage::Pending => write!(f, "pending"),
+            LaneStage::Started => write!(f, "started"),
+            LaneStage::Done => write!(f, "done"),
+        }
+    }
+}
+
+impl std::str::FromStr for LaneStage {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(LaneStage::Pending),
+            "started" => Ok(LaneStage::Started),
+            "done" => Ok(LaneStage::Done),
+            _ => Err(AppError::InvalidArgument(format!(
+                "{} \nPlease select from following stages: \n1) pending 2) started 3) done\n",
+                s
+            ))),
+        }
+    }
+}
+
+/// Rust structure for a swimlane in the Kanban Board
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct Swimlane {
+    /// Slug used to refer to the swimlane in commands (e.g. in-progress)
+    pub slug: String,
+
+    /// Display name of the swimlane (e.g. In Progress)
+    pub name: String,
+
+    /// Stage of work which the swimlane represents
+    pub stage: LaneStage,
+
+    /// Whether the swimlane holds tasks and subtasks waiting on their blockers, which are moved on
+    /// once their last blocker is done
+    pub blocked: bool,
+
+    /// Maximum number of tasks and subtasks allowed in the swimlane (None if unlimited)
+    pub wip_limit: Option<WipLimit>,
+
+    /// Task and SubTask IDs in the swimlane
+    pub items: Vec<String>,
+}
+
+impl Swimlane {
+    /// Create new empty swimlane with given display name (slug is derived from the name)
+    pub fn new(name: &str, stage: LaneStage, blocked: bool) -> Result<Self, AppError> {
+        Ok(Swimlane {
+            slug: slugify(name)?,
+            name: name.to_string(),
+            stage,
+            blocked,
+            wip_limit: None,
+            items: Vec::new(),
+        })
+    }
+
+    /// Swimlanes set up for a new Kanban Board
+    pub fn default_swimlanes() -> Vec<Swimlane> {
+        [
+            ("to-do", "To-Do", LaneStage::Pending, false),
+            ("in-progress", "In Progress", LaneStage::Started, false),
+            ("blocked", "Blocked", LaneStage::Started, true),
+            ("in-review", "In Review", LaneStage::Started, false),
+            ("done", "Done", LaneStage::Done, false),
+        ]
+        .iter()
+        .map(|(slug, name, stage, blocked)| Swimlane {
+            slug: slug.to_string(),
+            name: name.to_string(),
+            stage: *stage,
+            blocked: *blocked,
+            wip_limit: None,
+            items: Vec::new(),
+        })
+        .collect()
+    }
+
+    /// Header shown above the swimlane, with the number of items against its WIP limit
+    pub fn header(&self) -> String {
+        match self.wip_limit {
+            Some(wip_limit) if self.items.len() > wip_limit.max_items => format!(
+                "{} {}/{} - over WIP limit",
+                self.name,
+                self.items.len(),
+                wip_limit.max_items
+            ),
+            Some(wip_limit) => {
+                format!("{} {}/{}", self.name, self.items.len(), wip_limit.max_items)
+            }
+            None => self.name.clone(),
+        }
+    }
+}
+
+/// Work-in-progress limit of a swimlane
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq)]
+pub struct WipLimit {
+    /// Maximum number of tasks and subtasks in the swimlane
+    pub max_items: usize,
+
+    /// Whether moves beyond the limit are refused (unless forced) instead of only warned about
+    pub strict: bool,
+}
+
+impl std::fmt::Display for WipLimit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.strict {
+            true => write!(f, "{}", self.max_items),
+            false => write!(f, "{} (soft)", self.max_items),
+        }
+    }
+}
+
+/// Outcome of checking the WIP limit before moving a task or subtask into a swimlane
+#[derive(Debug)]
+pub enum WipCheck {
+    /// The swimlane stays within its WIP limit (or has none)
+    Allowed,
+
+    /// A soft WIP limit is exceeded, so the move goes ahead with a warning
+    Warning(String),
+
+    /// A strict WIP limit is exceeded and was overridden with `--force`
+    Overridden(String),
+}
+
+/// Derive the swimlane slug from given display name (e.g. "Code Review" -> "code-review")
+pub fn slugify(name: &str) -> Result<String, AppError> {
+    let slug: String = name
+        .to_lowercase()
+        .split(|x: char| !x.is_ascii_alphanumeric())
+        .filter(|x| !x.is_empty())
+        .collect::<Vec<&str>>()
+        .join("-");
+
+    if slug.is_empty() || slug == "all" {
+        return Err(AppError::InvalidArgument(format!(
+            "{} - Swimlane names need at least one letter or digit and cannot be 'all'.",
+            name
+        )));
+    }
+    Ok(slug)
+}
@@ -0,0 +1,116 @@
+//! Defines the free-form labels (tags) which can be put on tasks and subtasks, along with the
+//! colors they can be shown in
+
+use crate::{error::AppError, output::Record};
+use serde::{Deserialize, Serialize};
+
+/// Colors which can be given to a tag
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq)]
+pub enum TagColor {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+}
+
+impl std::fmt::Display for TagColor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TagColor::Red => write!(f, "red"),
+            TagColor::Green => write!(f, "green"),
+            TagColor::Yellow => write!(f, "yellow"),
+            TagColor::Blue => write!(f, "blue"),
+            TagColor::Magenta => write!(f, "magenta"),
+            TagColor::Cyan => write!(f, "cyan"),
+        }
+    }
+}
+
+impl std::str::FromStr for TagColor {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "red" => Ok(TagColor::Red),
+            "green" => Ok(TagColor::Green),
+            "yellow" => Ok(TagColor::Yellow),
+            "blue" => Ok(TagColor::Blue),
+            "magenta" => Ok(TagColor::Magenta),
+            "cyan" => Ok(TagColor::Cyan),
+            _ => Err(AppError::InvalidArgument(format!(
+                "{} \nPlease select from following tag colors: \n1) red 2) green 3) yellow 4) blue 5) magenta 6) cyan\n",
+                s
+            ))),
+        }
+    }
+}
+
+/// Tag as listed by `tag list`
+#[derive(Debug, Clone, Serialize)]
+pub struct TagRecord {
+    /// Tag label
+    pub tag: String,
+
+    /// Color of the tag (None if it has no color)
+    pub color: Option<String>,
+
+    /// Number of tasks with the tag
+    pub tasks: usize,
+
+    /// Number of subtasks with the tag
+    pub subtasks: usize,
+}
+
+impl Record for TagRecord {
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.tag.clone(),
+            self.color.clone().unwrap_or("None".to_string()),
+            self.tasks.to_string(),
+            self.subtasks.to_string(),
+        ]
+    }
+}
+
+/// Check that given tag label is usable (letters, digits, `-` and `_`) and convert it to lowercase,
+/// so that `Bug` and `bug` are the same tag
+pub fn validate_tag(label: &str) -> Result<String, AppError> {
+    if label.is_empty()
+        || !label
+            .chars()
+            .all(|x| x.is_alphanumeric() || x == '-' || x == '_')
+    {
+        return Err(AppError::InvalidArgument(format!(
+            "{} - Tags can only contain letters, digits, '-' and '_' (e.g. tech-debt).",
+            label
+        )));
+    }
+    Ok(label.to_lowercase())
+}
+
+/// Add given tag to a list of tags, unless it is already in it. Returns whether it was added.
+pub fn add_tag(tags: &mut Vec<String>, tag: &str) -> bool {
+    if tags.iter().any(|x| x == tag) {
+        return false;
+    }
+    tags.push(tag.to_string());
+    true
+}
+
+/// Replace given tag in a list of tags with another one, dropping it instead if the list already
+/// holds the other tag. Returns whether the list changed.
+pub fn replace_tag(tags: &mut Vec<String>, tag: &str, new_tag: &str) -> bool {
+    let position: usize = match tags.iter().position(|x| x == tag) {
+        Some(s) => s,
+        None => return false,
+    };
+    match tags.iter().any(|x| x == new_tag) {
+        true => {
+            tags.remove(position);
+        }
+        false => tags[position] = new_tag.to_string(),
+    }
+    true
+}
@@ -0,0 +1,634 @@
+//! Defines the full-screen terminal UI, which draws all swimlanes of the active board side by side
+//! as columns of cards
+
+use crate::{
+    cli::parse_deadline,
+    commands::{AppState, SUBTASK_DETAILS_TITLES, TASK_DETAILS_TITLES},
+};
+use chrono::prelude::NaiveDate;
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    layout::{Constraint, Flex, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span, Text},
+    widgets::{Block, Clear, List, ListItem, ListState, Paragraph, Wrap},
+    DefaultTerminal, Frame,
+};
+use rustic_boards::{
+    boards::ItemRecord,
+    error::AppError,
+    items::WorkItem,
+    lock::DataLock,
+    output::{self, Record, Section},
+    swimlanes::{Swimlane, WipCheck},
+    tags::TagColor,
+    utils::{TaskPriority, TimeStamp},
+    workspace::{ItemChanges, MoveOutcome},
+    Board,
+};
+use std::collections::BTreeMap;
+
+/// Labels of the fields in the edit form, in the order they are shown
+const EDIT_FIELDS: [&str; 4] = ["Description", "Priority", "Deadline", "Add Note"];
+
+/// Kind of items shown as cards on the board
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CardKind {
+    Task,
+    Subtask,
+}
+
+/// Form for editing the selected card in place
+#[derive(Debug)]
+struct EditForm {
+    /// Task or SubTask ID being edited
+    item_id: String,
+
+    /// Values of the fields (in the order of `EDIT_FIELDS`)
+    values: [String; 4],
+
+    /// Deadline of the item when the form was opened (kept unless the deadline field is changed)
+    deadline: Option<TimeStamp>,
+
+    /// Deadline field as first shown, to tell whether it was changed
+    deadline_text: String,
+
+    /// Index of the field being typed into
+    field: usize,
+}
+
+/// What the keyboard input currently goes to
+#[derive(Debug)]
+enum Mode {
+    /// Navigating the board
+    Board,
+
+    /// Reading the detail pane of the selected card
+    Details(Vec<String>),
+
+    /// Editing the selected card
+    Edit(EditForm),
+}
+
+/// State of the terminal UI between two key presses
+struct TuiApp {
+    /// Kind of items shown as cards
+    kind: CardKind,
+
+    /// Cards in each swimlane, in the order of the swimlanes on the board
+    columns: Vec<Section<ItemRecord>>,
+
+    /// Index of the selected swimlane
+    lane: usize,
+
+    /// Index of the selected card in the selected swimlane
+    card: usize,
+
+    /// What the keyboard input currently goes to
+    mode: Mode,
+
+    /// Outcome of the last action, shown at the bottom of the screen
+    status: String,
+
+    /// Revision of the data when the board was last read
+    revision: u64,
+
+    /// Whether the user asked to leave the terminal UI
+    quit: bool,
+}
+
+/// Run the terminal UI on the active board until the user quits. `revision` is the revision of the
+/// data when the state was loaded, and is updated with every change made from the terminal UI.
+pub fn run(state: &mut AppState, revision: &mut u64) -> Result<(), AppError> {
+    let mut app: TuiApp = TuiApp {
+        kind: CardKind::Task,
+        columns: Vec::new(),
+        lane: 0,
+        card: 0,
+        mode: Mode::Board,
+        status: String::new(),
+        revision: *revision,
+        quit: false,
+    };
+    app.refresh(&state.board, None)?;
+
+    let mut terminal: DefaultTerminal = match ratatui::try_init() {
+        Ok(s) => s,
+        Err(e) => return Err(AppError::TerminalError(e.to_string())),
+    };
+    let result: Result<(), AppError> = app.event_loop(&mut terminal, state);
+    ratatui::restore();
+
+    *revision = app.revision;
+    result
+}
+
+impl TuiApp {
+    /// Draw the screen and handle key presses until the user quits
+    fn event_loop(
+        &mut self,
+        terminal: &mut DefaultTerminal,
+        state: &mut AppState,
+    ) -> Result<(), AppError> {
+        while !self.quit {
+            if let Err(e) = terminal.draw(|frame| self.draw(frame, &state.board)) {
+                return Err(AppError::TerminalError(e.to_string()));
+            }
+
+            let key: KeyEvent = match event::read() {
+                Ok(Event::Key(s)) if s.kind == KeyEventKind::Press => s,
+                Ok(_) => continue,
+                Err(e) => return Err(AppError::TerminalError(e.to_string())),
+            };
+            if let Err(e) = self.handle_key(key, state) {
+                self.status = e.to_string();
+                self.refresh(&state.board, None)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Read the cards of every swimlane from the board again. The card with given ID stays
+    /// selected (following it into another swimlane), otherwise the selection is kept in range.
+    fn refresh(&mut self, board: &Board, select: Option<&str>) -> Result<(), AppError> {
+        self.columns = match self.kind {
+            CardKind::Task => board.list_tasks("all")?,
+            CardKind::Subtask => board.list_subtasks("all")?,
+        };
+
+        if let Some(item_id) = select {
+            for (lane, section) in self.columns.iter().enumerate() {
+                if let Some(card) = section.records.iter().position(|x| x.id == item_id) {
+                    self.lane = lane;
+                    self.card = card;
+                    return Ok(());
+                }
+            }
+        }
+
+        self.lane = self.lane.min(self.columns.len().saturating_sub(1));
+        let num_cards: usize = self.columns.get(self.lane).map_or(0, |x| x.records.len());
+        self.card = self.card.min(num_cards.saturating_sub(1));
+        Ok(())
+    }
+
+    /// Card which is currently selected (None if the selected swimlane is empty)
+    fn selected(&self) -> Option<&ItemRecord> {
+        self.columns
+            .get(self.lane)
+            .and_then(|x| x.records.get(self.card))
+    }
+
+    /// Make a change to the board while holding the lock on the data directory, the same way as
+    /// a command from the `boards>` prompt. The board is read again first if another process
+    /// changed the data since, and again if the change fails.
+    fn apply<T>(
+        &mut self,
+        state: &mut AppState,
+        change: impl FnOnce(&mut Board) -> Result<T, AppError>,
+    ) -> Result<T, AppError> {
+        let mut lock: DataLock = DataLock::acquire()?;
+        if lock.revision() != self.revision {
+            *state = AppState::load()?;
+        }
+
+        let result: Result<T, AppError> = change(&mut state.board);
+        lock.bump_revision()?;
+        self.revision = lock.revision();
+        if result.is_err() {
+            *state = AppState::load()?;
+        }
+        result
+    }
+
+    /// Handle a key press in the current mode
+    fn handle_key(&mut self, key: KeyEvent, state: &mut AppState) -> Result<(), AppError> {
+        match std::mem::replace(&mut self.mode, Mode::Board) {
+            Mode::Board => self.handle_board_key(key, state),
+            Mode::Details(lines) => match key.code {
+                KeyCode::Char('e') => self.open_edit_form(&state.board),
+                KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => Ok(()),
+                _ => {
+                    self.mode = Mode::Details(lines);
+                    Ok(())
+                }
+            },
+            Mode::Edit(form) => self.handle_edit_key(key, form, state),
+        }
+    }
+
+    /// Handle a key press while navigating the board
+    fn handle_board_key(&mut self, key: KeyEvent, state: &mut AppState) -> Result<(), AppError> {
+        let shift: bool = key.modifiers.contains(KeyModifiers::SHIFT);
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            KeyCode::Left | KeyCode::Right if shift => {
+                self.move_card(key.code == KeyCode::Right, state)?
+            }
+            KeyCode::Char('H') => self.move_card(false, state)?,
+            KeyCode::Char('L') => self.move_card(true, state)?,
+            KeyCode::Left | KeyCode::Char('h') => {
+                self.lane = self.lane.saturating_sub(1);
+                self.card = 0;
+            }
+            KeyCode::Right | KeyCode::Char('l') if self.lane + 1 < self.columns.len() => {
+                self.lane += 1;
+                self.card = 0;
+            }
+            KeyCode::Up | KeyCode::Char('k') => self.card = self.card.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j')
+                if self.card + 1 < self.columns.get(self.lane).map_or(0, |x| x.records.len()) =>
+            {
+                self.card += 1
+            }
+            KeyCode::Enter => self.open_details(&state.board)?,
+            KeyCode::Char('e') => self.open_edit_form(&state.board)?,
+            KeyCode::Tab => {
+                self.kind = match self.kind {
+                    CardKind::Task => CardKind::Subtask,
+                    CardKind::Subtask => CardKind::Task,
+                };
+                self.card = 0;
+                self.refresh(&state.board, None)?;
+            }
+            KeyCode::Char('u') => {
+                self.status = match self.apply(state, |board| board.undo(false))? {
+                    Some(s) => format!("Undone: {}", s.command),
+                    None => "Nothing to undo.".to_string(),
+                };
+                self.refresh(&state.board, None)?;
+            }
+            KeyCode::Char('r') => {
+                self.status = match self.apply(state, |board| board.redo(false))? {
+                    Some(s) => format!("Redone: {}", s.command),
+                    None => "Nothing to redo.".to_string(),
+                };
+                self.refresh(&state.board, None)?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Move the selected card into the next swimlane (or the previous one if `forward` is false)
+    fn move_card(&mut self, forward: bool, state: &mut AppState) -> Result<(), AppError> {
+        let item_id: String = match self.selected() {
+            Some(s) => s.id.clone(),
+            None => return Ok(()),
+        };
+        let target: usize = match forward {
+            true if self.lane + 1 < self.columns.len() => self.lane + 1,
+            false if self.lane > 0 => self.lane - 1,
+            _ => return Ok(()),
+        };
+        let slug: String = state.board.kanban().swimlanes()[target].slug.clone();
+
+        let kind: CardKind = self.kind;
+        let move_outcome: MoveOutcome = self.apply(state, |board| match kind {
+            CardKind::Task => board.move_task(&item_id, &slug, false),
+            CardKind::Subtask => board.move_subtask(&item_id, &slug, false),
+        })?;
+        self.status = match move_outcome.wip_check {
+            WipCheck::Warning(message) | WipCheck::Overridden(message) => {
+                format!("Warning: {}", message)
+            }
+            WipCheck::Allowed if !move_outcome.unblocked.is_empty() => format!(
+                "{} moved to {} swimlane, unblocking {}.",
+                item_id,
+                slug,
+                move_outcome
+                    .unblocked
+                    .iter()
+                    .map(|(x, _)| x.as_str())
+                    .collect::<Vec<&str>>()
+                    .join(", ")
+            ),
+            WipCheck::Allowed => format!("{} moved to {} swimlane.", item_id, slug),
+        };
+        self.refresh(&state.board, Some(&item_id))
+    }
+
+    /// Open the detail pane of the selected card
+    fn open_details(&mut self, board: &Board) -> Result<(), AppError> {
+        let item_id: String = match self.selected() {
+            Some(s) => s.id.clone(),
+            None => return Ok(()),
+        };
+        let (cells, notes, titles): (Vec<String>, Vec<String>, &[&str]) = match self.kind {
+            CardKind::Task => {
+                let details = board.task_details(&item_id)?;
+                (details.cells(), details.notes, &TASK_DETAILS_TITLES)
+            }
+            CardKind::Subtask => {
+                let details = board.subtask_details(&item_id)?;
+                (details.cells(), details.notes, &SUBTASK_DETAILS_TITLES)
+            }
+        };
+
+        let mut lines: Vec<String> = titles
+            .iter()
+            .zip(cells)
+            .map(|(title, value)| format!("{}: {}", title, value))
+            // Values such as the chains of blockers span several lines
+            .flat_map(|x| x.lines().map(String::from).collect::<Vec<String>>())
+            .collect();
+        lines.push(String::new());
+        match notes.is_empty() {
+            true => lines.push("No notes found.".to_string()),
+            false => {
+                lines.push("Additional Notes:".to_string());
+                for (idx, note) in notes.iter().enumerate() {
+                    lines.push(format!("{}) {}", idx + 1, note));
+                }
+            }
+        }
+        self.mode = Mode::Details(lines);
+        Ok(())
+    }
+
+    /// Open the edit form for the selected card, filled in with its current values
+    fn open_edit_form(&mut self, board: &Board) -> Result<(), AppError> {
+        let item_id: String = match self.selected() {
+            Some(s) => s.id.clone(),
+            None => return Ok(()),
+        };
+        let work_item: WorkItem = board.item(&item_id)?;
+        let (description, priority, deadline): (String, TaskPriority, Option<TimeStamp>) = (
+            work_item.description,
+            work_item.priority,
+            work_item.deadline,
+        );
+
+        let deadline_text: String = match TimeStamp::to_optional_naivedate(deadline)? {
+            Some(s) => s.format("%d/%m/%Y").to_string(),
+            None => "none".to_string(),
+        };
+        self.mode = Mode::Edit(EditForm {
+            item_id,
+            values: [
+                description,
+                priority.to_string().to_lowercase(),
+                deadline_text.clone(),
+                String::new(),
+            ],
+            deadline,
+            deadline_text,
+            field: 0,
+        });
+        Ok(())
+    }
+
+    /// Handle a key press in the edit form
+    fn handle_edit_key(
+        &mut self,
+        key: KeyEvent,
+        mut form: EditForm,
+        state: &mut AppState,
+    ) -> Result<(), AppError> {
+        match key.code {
+            KeyCode::Esc => {
+                self.status = "Edit cancelled.".to_string();
+                return Ok(());
+            }
+            KeyCode::Enter => return self.save_edit_form(form, state),
+            KeyCode::Up | KeyCode::BackTab => form.field = form.field.saturating_sub(1),
+            KeyCode::Down | KeyCode::Tab => {
+                form.field = (form.field + 1).min(EDIT_FIELDS.len() - 1)
+            }
+            KeyCode::Backspace => {
+                form.values[form.field].pop();
+            }
+            KeyCode::Char(c) => form.values[form.field].push(c),
+            _ => {}
+        }
+        self.mode = Mode::Edit(form);
+        Ok(())
+    }
+
+    /// Save the values of the edit form to the item being edited. The form stays open if a value
+    /// is not valid.
+    fn save_edit_form(&mut self, form: EditForm, state: &mut AppState) -> Result<(), AppError> {
+        let priority: Result<TaskPriority, AppError> =
+            form.values[1].trim().parse::<TaskPriority>();
+        let deadline: Result<Option<TimeStamp>, AppError> =
+            match form.values[2].trim() == form.deadline_text {
+                true => Ok(form.deadline),
+                false => match form.values[2].trim() {
+                    "" => Ok(None),
+                    value => {
+                        parse_deadline(value).map(|x: Option<NaiveDate>| x.map(TimeStamp::convert))
+                    }
+                },
+            };
+        let (priority, deadline): (TaskPriority, Option<TimeStamp>) = match (priority, deadline) {
+            (Ok(s), Ok(t)) => (s, t),
+            (Err(e), _) | (_, Err(e)) => {
+                self.status = e.to_string();
+                self.mode = Mode::Edit(form);
+                return Ok(());
+            }
+        };
+
+        let notes: Vec<String> = match form.values[3].trim() {
+            "" => Vec::new(),
+            note => vec![note.to_string()],
+        };
+        let changes: ItemChanges = ItemChanges {
+            description: form.values[0].clone(),
+            priority,
+            deadline,
+            notes,
+        };
+        let kind: CardKind = self.kind;
+        let item_id: String = form.item_id;
+        self.apply(state, |board| match kind {
+            CardKind::Task => board.edit_task(&item_id, changes, Vec::new()).map(|_| ()),
+            CardKind::Subtask => board
+                .edit_subtask(&item_id, changes, Vec::new(), None)
+                .map(|_| ()),
+        })?;
+        self.status = format!("{} updated successfully.", item_id);
+        self.refresh(&state.board, Some(&item_id))
+    }
+
+    /// Draw the board, the pane of the current mode on top of it and the status line
+    fn draw(&self, frame: &mut Frame, board: &Board) {
+        let [board_area, status_area] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+
+        let swimlanes: &[Swimlane] = board.kanban().swimlanes();
+        let num_lanes: u32 = swimlanes.len().max(1) as u32;
+        let lane_areas = Layout::horizontal(vec![Constraint::Ratio(1, num_lanes); swimlanes.len()])
+            .split(board_area);
+        for (idx, (swimlane, section)) in swimlanes.iter().zip(&self.columns).enumerate() {
+            self.draw_lane(
+                frame,
+                lane_areas[idx],
+                swimlane,
+                section,
+                board.kanban().tag_colors(),
+                idx == self.lane,
+            );
+        }
+
+        match &self.mode {
+            Mode::Board => {}
+            Mode::Details(lines) => {
+                let area: Rect = popup_area(board_area, 70, 80);
+                let text: Vec<Line> = lines.iter().map(|x| Line::from(x.as_str())).collect();
+                frame.render_widget(Clear, area);
+                frame.render_widget(
+                    Paragraph::new(text)
+                        .wrap(Wrap { trim: false })
+                        .block(Block::bordered().title(" Details ")),
+                    area,
+                );
+            }
+            Mode::Edit(form) => {
+                let area: Rect = popup_area(board_area, 60, 40);
+                let text: Vec<Line> = EDIT_FIELDS
+                    .iter()
+                    .zip(&form.values)
+                    .enumerate()
+                    .map(|(idx, (label, value))| {
+                        let style: Style = match idx == form.field {
+                            true => Style::new().add_modifier(Modifier::REVERSED),
+                            false => Style::new(),
+                        };
+                        Line::from(vec![
+                            Span::styled(
+                                format!("{:<12}", label),
+                                Style::new().add_modifier(Modifier::BOLD),
+                            ),
+                            Span::styled(format!("{}_", value), style),
+                        ])
+                    })
+                    .collect();
+                frame.render_widget(Clear, area);
+                frame.render_widget(
+                    Paragraph::new(text)
+                        .wrap(Wrap { trim: false })
+                        .block(Block::bordered().title(format!(" Edit {} ", form.item_id))),
+                    area,
+                );
+            }
+        }
+
+        let help: &str = match self.mode {
+            Mode::Board => "←/→ lane  ↑/↓ card  Shift+←/→ move  Enter details  e edit  Tab tasks/subtasks  u undo  r redo  q quit",
+            Mode::Details(_) => "e edit  Esc close",
+            Mode::Edit(_) => "↑/↓ field  Enter save  Esc cancel  (priority: high, medium, low; deadline: dd/mm/yyyy or none)",
+        };
+        let status: Line = match self.status.is_empty() {
+            true => Line::from(help).style(Style::new().fg(Color::DarkGray)),
+            false => Line::from(vec![
+                Span::styled(self.status.as_str(), Style::new().fg(Color::Yellow)),
+                Span::styled(format!("  |  {}", help), Style::new().fg(Color::DarkGray)),
+            ]),
+        };
+        frame.render_widget(Paragraph::new(status), status_area);
+    }
+
+    /// Draw a swimlane as a column of cards
+    fn draw_lane(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        swimlane: &Swimlane,
+        section: &Section<ItemRecord>,
+        tag_colors: &BTreeMap<String, TagColor>,
+        focused: bool,
+    ) {
+        let title: String = match swimlane.wip_limit {
+            Some(s) => format!(
+                " {} {}/{} ",
+                swimlane.name,
+                swimlane.items.len(),
+                s.max_items
+            ),
+            None => format!(" {} ({}) ", swimlane.name, section.records.len()),
+        };
+        let border_style: Style = match focused {
+            true => Style::new().fg(Color::Cyan),
+            false => Style::new(),
+        };
+
+        let cards: Vec<ListItem> = section
+            .records
+            .iter()
+            .map(|x| card_text(x, tag_colors))
+            .collect();
+        let mut list_state: ListState = ListState::default();
+        if focused && !section.records.is_empty() {
+            list_state.select(Some(self.card));
+        }
+        frame.render_stateful_widget(
+            List::new(cards)
+                .block(Block::bordered().title(title).border_style(border_style))
+                .highlight_style(Style::new().add_modifier(Modifier::REVERSED)),
+            area,
+            &mut list_state,
+        );
+    }
+}
+
+/// Lines of the card showing a task or subtask (ID, name, priority, deadline, assignee and tags)
+fn card_text<'a>(record: &'a ItemRecord, tag_colors: &BTreeMap<String, TagColor>) -> ListItem<'a> {
+    let priority_color: Color = match record.priority {
+        TaskPriority::High => Color::Red,
+        TaskPriority::Medium => Color::Yellow,
+        TaskPriority::Low => Color::Green,
+    };
+    ListItem::new(Text::from(vec![
+        Line::from(vec![
+            Span::styled(
+                record.id.as_str(),
+                Style::new().add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(" "),
+            Span::styled(record.priority.to_string(), Style::new().fg(priority_color)),
+        ]),
+        Line::from(record.name.as_str()),
+        Line::from(match &record.assignee {
+            Some(s) => format!("Due: {}  @{}", output::display_date(&record.deadline), s),
+            None => format!("Due: {}", output::display_date(&record.deadline)),
+        }),
+        Line::from(
+            record
+                .tags
+                .iter()
+                .map(|x| {
+                    let style: Style = match tag_colors.get(x) {
+                        Some(s) => Style::new().fg(tag_color(*s)),
+                        None => Style::new().fg(Color::DarkGray),
+                    };
+                    Span::styled(format!("#{} ", x), style)
+                })
+                .collect::<Vec<Span>>(),
+        ),
+        Line::from(""),
+    ]))
+}
+
+/// Terminal color to show a tag of given color in
+fn tag_color(color: TagColor) -> Color {
+    match color {
+        TagColor::Red => Color::Red,
+        TagColor::Green => Color::Green,
+        TagColor::Yellow => Color::Yellow,
+        TagColor::Blue => Color::Blue,
+        TagColor::Magenta => Color::Magenta,
+        TagColor::Cyan => Color::Cyan,
+    }
+}
+
+/// Area of given size (in percent of the given area) centred in the given area
+fn popup_area(area: Rect, percent_x: u16, percent_y: u16) -> Rect {
+    let [area] = Layout::vertical([Constraint::Percentage(percent_y)])
+        .flex(Flex::Center)
+        .areas(area);
+    let [area] = Layout::horizontal([Constraint::Percentage(percent_x)])
+        .flex(Flex::Center)
+        .areas(area);
+    area
+}
@@ -1,20 +1,42 @@
 //! Utilities module defining the helper Rust structures and methods for use across other modules
 
+#[cfg(not(windows))]
+use crate::constants::DATA_DIR_NAME;
 use crate::{
-    constants::{ACTIVE_SUBTASKS_PATH, ACTIVE_TASKS_PATH, APP_DIR_PATH},
+    constants::{
+        APP_DIR_PATH, BOARDS_PATH, CONFIG_FILE, DATA_DIR_ENV_VAR, GITIGNORE_FILE, ITEMS_PATH,
+        JOURNAL_FILE, LOCK_FILE, PROJECT_DIR_PATH,
+    },
     error::AppError,
+    migrations,
 };
 use chrono::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::{
+    ffi::OsString,
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        OnceLock,
+    },
+};
 
 /// Rust structure for datetime
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq)]
 pub struct TimeStamp {
     year: i32,
     month: u32,
     day: u32,
 }
 
+impl Default for TimeStamp {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl TimeStamp {
     /// Get current datetime
     pub fn new() -> Self {
@@ -48,10 +70,18 @@ impl TimeStamp {
         };
         Ok(date)
     }
+
+    /// Convert given optional input datetime to chrono NaiveDate
+    pub fn to_optional_naivedate(date: Option<TimeStamp>) -> Result<Option<NaiveDate>, AppError> {
+        match date {
+            Some(s) => Ok(Some(s.to_naivedate()?)),
+            None => Ok(None),
+        }
+    }
 }
 
 /// Possible task priority values for use in Kanban Board
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
 pub enum TaskPriority {
     High,
     Medium,
@@ -68,64 +98,304 @@ impl std::fmt::Display for TaskPriority {
     }
 }
 
-/// Possible task status or swimlane values for use in Kanban Board
-#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Hash)]
-pub enum TaskStatus {
-    ToDo,
-    InProgress,
-    Blocked,
-    InReview,
-    Done,
-}
+impl std::str::FromStr for TaskPriority {
+    type Err = AppError;
 
-impl std::fmt::Display for TaskStatus {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            TaskStatus::ToDo => write!(f, "To-Do"),
-            TaskStatus::InProgress => write!(f, "In Progress"),
-            TaskStatus::Blocked => write!(f, "Blocked"),
-            TaskStatus::InReview => write!(f, "In Review"),
-            TaskStatus::Done => write!(f, "Done"),
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "high" => Ok(TaskPriority::High),
+            "medium" => Ok(TaskPriority::Medium),
+            "low" => Ok(TaskPriority::Low),
+            _ => Err(AppError::InvalidPriorityKeyword(format!(
+                "{} \nPlease select from following options: \n1) high 2) medium 3) low\n",
+                s
+            ))),
         }
     }
 }
 
+/// Data directory passed with `--data-dir` (takes precedence over `RUSTIC_BOARDS_HOME`)
+static DATA_DIR_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+/// Whether `--global` was passed (skip the lookup of a project board)
+static GLOBAL_DATA_DIR: AtomicBool = AtomicBool::new(false);
+
+/// Number of changes this process made to the data directory, so that the lock on it can tell
+/// whether a command changed anything
+static WRITE_COUNT: AtomicU64 = AtomicU64::new(0);
+
+/// Keep all application data in given directory for the rest of the process (`--data-dir`)
+pub fn set_data_dir(data_dir: &str) {
+    let _ = DATA_DIR_OVERRIDE.set(PathBuf::from(data_dir));
+}
+
+/// Use the global application directory even inside a project with its own board (`--global`)
+pub fn use_global_data_dir() {
+    GLOBAL_DATA_DIR.store(true, Ordering::Relaxed);
+}
+
+/// Search upward from the current directory for a project board (a `.rustic_boards` directory),
+/// the same way git looks up `.git`
+pub fn find_project_dir() -> Result<Option<PathBuf>, AppError> {
+    let current_dir: PathBuf = get_current_dir()?;
+    Ok(current_dir
+        .ancestors()
+        .map(|x| x.join(PROJECT_DIR_PATH))
+        .find(|x| x.is_dir()))
+}
+
+/// Create a project board in the current directory, returning its path
+pub fn create_project_dir() -> Result<PathBuf, AppError> {
+    let project_dir: PathBuf = get_current_dir()?.join(PROJECT_DIR_PATH);
+    if project_dir.exists() {
+        return Err(AppError::InvalidArgument(format!(
+            "{} - A project board already exists in this directory.",
+            project_dir.display()
+        )));
+    }
+    create_dir(&project_dir.join(BOARDS_PATH))?;
+
+    // The lock, the undo journal, the configuration (active board) and files left behind by an
+    // interrupted write only make sense on this machine
+    let ignored: String = [LOCK_FILE, JOURNAL_FILE, CONFIG_FILE, "*.tmp"]
+        .map(|x| format!("{}\n", x))
+        .concat();
+    write_file_atomic(&project_dir.join(GITIGNORE_FILE), ignored.as_bytes())?;
+    Ok(project_dir)
+}
+
+/// Count a change made to the data directory (a file written, moved or deleted, or a database
+/// transaction committed)
+pub(crate) fn record_write() {
+    WRITE_COUNT.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Number of changes this process made to the data directory so far
+pub fn write_count() -> u64 {
+    WRITE_COUNT.load(Ordering::Relaxed)
+}
+
+/// Get the current working directory
+fn get_current_dir() -> Result<PathBuf, AppError> {
+    match std::env::current_dir() {
+        Ok(s) => Ok(s),
+        Err(e) => Err(AppError::HomeDirectoryInaccessibleError(format!(
+            "Unable to determine the current directory - {}",
+            e
+        ))),
+    }
+}
+
 /// Get users' home directory on Windows OS-based machine
-fn get_users_home_dir() -> Result<String, AppError> {
+#[cfg(windows)]
+fn get_users_home_dir() -> Result<PathBuf, AppError> {
     match home::home_dir() {
-        Some(path) => return Ok(path.display().to_string()),
-        None => {
-            return Err(AppError::HomeDirectoryInaccessibleError(
-                "Unable to determine user's home directory.".to_string(),
-            ))
+        Some(path) => Ok(path),
+        None => Err(AppError::HomeDirectoryInaccessibleError(
+            "Unable to determine user's home directory.".to_string(),
+        )),
+    }
+}
+
+/// Get the default application directory on Windows (`%USERPROFILE%\.rustic_boards`)
+#[cfg(windows)]
+fn default_app_dir() -> Result<PathBuf, AppError> {
+    Ok(get_users_home_dir()?.join(APP_DIR_PATH))
+}
+
+/// Get the default application directory on other platforms, inside the platform data directory
+/// (`$XDG_DATA_HOME/rustic_boards` or `~/.local/share/rustic_boards` on Linux)
+#[cfg(not(windows))]
+fn default_app_dir() -> Result<PathBuf, AppError> {
+    match dirs::data_dir() {
+        Some(path) => Ok(path.join(DATA_DIR_NAME)),
+        None => Err(AppError::HomeDirectoryInaccessibleError(
+            "Unable to determine user's data directory.".to_string(),
+        )),
+    }
+}
+
+/// Get the application directory used by older versions on other platforms, which joined the home
+/// directory and `.rustic_boards` with a backslash (a sibling of the home directory named
+/// `<home>\.rustic_boards`)
+#[cfg(not(windows))]
+fn legacy_app_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|x| PathBuf::from(format!("{}\\{}", x.display(), APP_DIR_PATH)))
+}
+
+/// Get the application directory used by older versions on Windows, which is still the default
+#[cfg(windows)]
+fn legacy_app_dir() -> Option<PathBuf> {
+    None
+}
+
+/// Get the application directory: `--data-dir` if passed, else `RUSTIC_BOARDS_HOME` if set,
+/// else the project board found from the current directory (unless `--global` was passed),
+/// else the platform default
+pub fn get_app_dir() -> Result<PathBuf, AppError> {
+    Ok(resolve_app_dir()?.0)
+}
+
+/// Get the application directory along with whether it is the platform default
+fn resolve_app_dir() -> Result<(PathBuf, bool), AppError> {
+    if let Some(data_dir) = DATA_DIR_OVERRIDE.get() {
+        return Ok((data_dir.clone(), false));
+    }
+    if let Some(s) = std::env::var_os(DATA_DIR_ENV_VAR) {
+        if !s.is_empty() {
+            return Ok((PathBuf::from(s), false));
         }
     }
+    if !GLOBAL_DATA_DIR.load(Ordering::Relaxed) {
+        if let Some(project_dir) = find_project_dir()? {
+            return Ok((project_dir, false));
+        }
+    }
+    Ok((default_app_dir()?, true))
 }
 
 /// Create new directory based on given path
-fn create_dir(dir_path: &String) -> Result<(), AppError> {
-    match std::fs::create_dir_all(&dir_path) {
-        Ok(_) => return Ok(()),
+pub(crate) fn create_dir(dir_path: &Path) -> Result<(), AppError> {
+    match std::fs::create_dir_all(dir_path) {
+        Ok(_) => Ok(()),
+        Err(e) => Err(AppError::HomeDirectoryPermissionError(format!(
+            "{} - {}",
+            dir_path.display(),
+            e
+        ))),
+    }
+}
+
+/// Create all application directories for storing app information, first moving the boards kept
+/// by older versions into the platform default directory
+pub fn create_app_dirs() -> Result<PathBuf, AppError> {
+    let (app_dir_path, default_dir): (PathBuf, bool) = resolve_app_dir()?;
+    // The boards kept by older versions are moved before anything (e.g. the lock file) is written
+    if default_dir {
+        if let Some(legacy_dir) = legacy_app_dir() {
+            if migrations::migrate_app_dir(&legacy_dir, &app_dir_path)? {
+                eprintln!(
+                    "Moved the boards kept in {} to {}",
+                    legacy_dir.display(),
+                    app_dir_path.display()
+                );
+            }
+        }
+    }
+    create_dir(&app_dir_path)?;
+    create_dir(&app_dir_path.join(BOARDS_PATH))?;
+    Ok(app_dir_path)
+}
+
+/// Get the directory path holding all information for given board
+pub fn get_board_dir(board_name: &str) -> Result<PathBuf, AppError> {
+    Ok(create_app_dirs()?.join(BOARDS_PATH).join(board_name))
+}
+
+/// Create all directories for storing information of given board
+pub fn create_board_dirs(board_name: &str) -> Result<PathBuf, AppError> {
+    let board_dir_path: PathBuf = get_board_dir(board_name)?;
+    create_dir(&board_dir_path)?;
+    create_dir(&board_dir_path.join(ITEMS_PATH))?;
+    Ok(board_dir_path)
+}
+
+/// Write given data to file atomically, by writing it to a temporary file next to it, flushing
+/// that to disk and renaming it over the file, so that a crash never leaves a half-written file
+pub fn write_file_atomic(file_path: &Path, data: &[u8]) -> Result<(), AppError> {
+    let write_error =
+        |e: std::io::Error| AppError::FileWriteError(format!("{} - {}", file_path.display(), e));
+
+    let mut temp_name: OsString = file_path.as_os_str().to_os_string();
+    temp_name.push(".tmp");
+    let temp_path: PathBuf = PathBuf::from(temp_name);
+
+    let mut temp_file: File = File::create(&temp_path).map_err(write_error)?;
+    temp_file.write_all(data).map_err(write_error)?;
+    temp_file.sync_all().map_err(write_error)?;
+    drop(temp_file);
+
+    if let Err(e) = std::fs::rename(&temp_path, file_path) {
+        let _ = std::fs::remove_file(&temp_path);
+        return Err(write_error(e));
+    }
+    record_write();
+
+    // Flush the rename itself too (directories can't be opened for this on Windows)
+    #[cfg(not(windows))]
+    if let Some(dir_path) = file_path.parent() {
+        if let Ok(dir) = File::open(dir_path) {
+            let _ = dir.sync_all();
+        }
+    }
+    Ok(())
+}
+
+/// List the names (without extension) of the files with given extension in given directory
+/// (empty if the directory is not present)
+pub fn list_file_stems(dir_path: &Path, extension: &str) -> Result<Vec<String>, AppError> {
+    Ok(list_dir_entries(dir_path)?
+        .iter()
+        .filter(|x| x.extension().is_some_and(|x| x == extension))
+        .filter_map(|x| x.file_stem().and_then(|x| x.to_str()))
+        .map(|x| x.to_string())
+        .collect())
+}
+
+/// List the names of the sub-directories of given directory, sorted by name (empty if the
+/// directory is not present)
+pub fn list_dir_names(dir_path: &Path) -> Result<Vec<String>, AppError> {
+    let mut dir_names: Vec<String> = list_dir_entries(dir_path)?
+        .iter()
+        .filter(|x| x.is_dir())
+        .filter_map(|x| x.file_name().and_then(|x| x.to_str()))
+        .map(|x| x.to_string())
+        .collect();
+    dir_names.sort();
+    Ok(dir_names)
+}
+
+/// List the paths of all entries in given directory (empty if the directory is not present)
+fn list_dir_entries(dir_path: &Path) -> Result<Vec<PathBuf>, AppError> {
+    if !dir_path.exists() {
+        return Ok(Vec::new());
+    }
+    let entries = match std::fs::read_dir(dir_path) {
+        Ok(s) => s,
         Err(e) => {
-            return Err(AppError::HomeDirectoryPermissionError(format!(
+            return Err(AppError::FileReadError(format!(
                 "{} - {}",
-                dir_path,
-                e.to_string()
+                dir_path.display(),
+                e
             )))
         }
+    };
+
+    let mut entry_paths: Vec<PathBuf> = Vec::new();
+    for entry in entries {
+        match entry {
+            Ok(s) => entry_paths.push(s.path()),
+            Err(e) => {
+                return Err(AppError::FileReadError(format!(
+                    "{} - {}",
+                    dir_path.display(),
+                    e
+                )))
+            }
+        }
     }
+    Ok(entry_paths)
 }
 
-/// Create all application directories for storing app information
-pub fn create_app_dirs() -> Result<String, AppError> {
-    let home_dir: String = get_users_home_dir()?;
-    let app_dir_path: String = format!("{}\\{}", home_dir, APP_DIR_PATH);
-    let tasks_path: String = format!("{}\\{}", &app_dir_path, ACTIVE_TASKS_PATH);
-    let subtasks_path: String = format!("{}\\{}", &app_dir_path, ACTIVE_SUBTASKS_PATH);
-
-    create_dir(&app_dir_path)?;
-    create_dir(&tasks_path)?;
-    create_dir(&subtasks_path)?;
-
-    Ok(app_dir_path)
+/// Create an empty directory for a test under the system temp directory
+#[cfg(test)]
+pub(crate) fn test_dir(name: &str) -> PathBuf {
+    let dir_path: PathBuf = std::env::temp_dir().join(format!(
+        "rustic_boards_test_{}_{}",
+        std::process::id(),
+        name
+    ));
+    let _ = std::fs::remove_dir_all(&dir_path);
+    std::fs::create_dir_all(&dir_path).unwrap();
+    dir_path
 }
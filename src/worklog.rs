@@ -0,0 +1,381 @@
+//! Defines the time logged against tasks and subtasks (worklogs), the timers running on a board and
+//! the reports built from them
+
+use crate::{
+    error::AppError,
+    output::{self, Record},
+    TimeStamp,
+};
+use chrono::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Rust structure for a block of time logged against a task or subtask
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct WorkLog {
+    /// Day the work was done on
+    pub logged_on: TimeStamp,
+
+    /// Time spent, in minutes
+    pub minutes: u32,
+
+    /// What the time was spent on
+    pub message: String,
+
+    /// Handle of the person who logged the time (None if no current user was configured)
+    pub author: Option<String>,
+}
+
+/// Rust structure for a timer running on a task or subtask. Each person (or nobody, when no current
+/// user is configured) can have one timer running on a board at a time.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct RunningTimer {
+    /// Task or SubTask ID the time is tracked for
+    pub item_id: String,
+
+    /// Handle of the person who started the timer
+    pub user: Option<String>,
+
+    /// Time the timer was started (seconds since the Unix epoch)
+    pub started_at: i64,
+}
+
+impl RunningTimer {
+    /// Start a timer on given Task or SubTask ID for given person
+    pub fn new(item_id: &str, user: Option<String>) -> Self {
+        RunningTimer {
+            item_id: item_id.to_string(),
+            user,
+            started_at: Local::now().timestamp(),
+        }
+    }
+
+    /// Local date and time the timer was started
+    pub fn started_on(&self) -> Result<DateTime<Local>, AppError> {
+        match Local.timestamp_opt(self.started_at, 0).single() {
+            Some(s) => Ok(s),
+            None => Err(AppError::NaiveDateConversionError(
+                self.started_at.to_string(),
+            )),
+        }
+    }
+
+    /// Minutes elapsed since the timer was started (rounded to the nearest minute, at least one)
+    pub fn elapsed_minutes(&self) -> u32 {
+        let seconds: i64 = (Local::now().timestamp() - self.started_at).max(0);
+        ((seconds + 30) / 60).clamp(1, u32::MAX as i64) as u32
+    }
+
+    /// Stop the timer, turning the elapsed time into a worklog entry on the day it was started
+    pub fn stop(&self, message: &str) -> Result<WorkLog, AppError> {
+        Ok(WorkLog {
+            logged_on: TimeStamp::convert(self.started_on()?.date_naive()),
+            minutes: self.elapsed_minutes(),
+            message: message.to_string(),
+            author: self.user.clone(),
+        })
+    }
+}
+
+/// Worklog entry as shown by `open task` and `open subtask`
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkLogRecord {
+    /// Day the work was done on
+    #[serde(serialize_with = "output::serialize_date")]
+    pub logged_on: NaiveDate,
+
+    /// Time spent, in minutes
+    pub minutes: u32,
+
+    /// What the time was spent on
+    pub message: String,
+
+    /// Handle of the person who logged the time
+    pub author: Option<String>,
+}
+
+impl WorkLogRecord {
+    /// Create the record for given worklog entry
+    pub fn from_worklog(work_log: &WorkLog) -> Result<WorkLogRecord, AppError> {
+        Ok(WorkLogRecord {
+            logged_on: work_log.logged_on.to_naivedate()?,
+            minutes: work_log.minutes,
+            message: work_log.message.clone(),
+            author: work_log.author.clone(),
+        })
+    }
+}
+
+impl std::fmt::Display for WorkLogRecord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {}",
+            output::display_date(&Some(self.logged_on)),
+            format_duration(self.minutes)
+        )?;
+        if let Some(author) = &self.author {
+            write!(f, " by {}", author)?;
+        }
+        if !self.message.is_empty() {
+            write!(f, " - {}", self.message)?;
+        }
+        Ok(())
+    }
+}
+
+/// Timer as listed by `timer list`
+#[derive(Debug, Clone, Serialize)]
+pub struct TimerRecord {
+    /// Handle of the person who started the timer
+    pub user: Option<String>,
+
+    /// Task or SubTask ID the time is tracked for
+    pub item_id: String,
+
+    /// Local date and time the timer was started
+    pub started_at: String,
+
+    /// Minutes elapsed so far
+    pub minutes: u32,
+}
+
+impl TimerRecord {
+    /// Create the record for given running timer
+    pub fn from_timer(timer: &RunningTimer) -> Result<TimerRecord, AppError> {
+        Ok(TimerRecord {
+            user: timer.user.clone(),
+            item_id: timer.item_id.clone(),
+            started_at: timer.started_on()?.format("%Y-%m-%d %H:%M").to_string(),
+            minutes: timer.elapsed_minutes(),
+        })
+    }
+}
+
+impl Record for TimerRecord {
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.user.clone().unwrap_or("None".to_string()),
+            self.item_id.clone(),
+            self.started_at.clone(),
+            format_duration(self.minutes),
+        ]
+    }
+}
+
+/// Time logged on a day as listed by `timesheet`
+#[derive(Debug, Clone, Serialize)]
+pub struct DayTotalRecord {
+    /// Day the work was done on
+    #[serde(serialize_with = "output::serialize_date")]
+    pub date: NaiveDate,
+
+    /// Time logged on the day, in minutes
+    pub minutes: u32,
+
+    /// Number of worklog entries on the day
+    pub entries: usize,
+}
+
+impl Record for DayTotalRecord {
+    fn cells(&self) -> Vec<String> {
+        vec![
+            output::display_date(&Some(self.date)),
+            format_duration(self.minutes),
+            self.entries.to_string(),
+        ]
+    }
+}
+
+/// Time logged on a task or subtask during a week as listed by `timesheet --week`
+#[derive(Debug, Clone, Serialize)]
+pub struct TimesheetRecord {
+    /// Task or SubTask ID (empty for the row of totals)
+    pub id: String,
+
+    /// Task or SubTask Name ("Total" for the row of totals)
+    pub name: String,
+
+    /// Time logged on each day of the week (yyyy-mm-dd), in minutes
+    pub days: BTreeMap<String, u32>,
+
+    /// Time logged during the week, in minutes
+    pub minutes: u32,
+}
+
+impl TimesheetRecord {
+    /// Create the row for given Task or SubTask ID from its worklog entries during given week
+    pub fn from_worklog(
+        id: &str,
+        name: &str,
+        week: &[NaiveDate],
+        worklog: &[WorkLog],
+    ) -> Result<TimesheetRecord, AppError> {
+        let mut days: BTreeMap<String, u32> = week
+            .iter()
+            .map(|x| (x.format("%Y-%m-%d").to_string(), 0))
+            .collect();
+        for work_log in worklog {
+            let logged_on: String = work_log
+                .logged_on
+                .to_naivedate()?
+                .format("%Y-%m-%d")
+                .to_string();
+            if let Some(minutes) = days.get_mut(&logged_on) {
+                *minutes = minutes.saturating_add(work_log.minutes);
+            }
+        }
+        Ok(TimesheetRecord {
+            id: id.to_string(),
+            name: name.to_string(),
+            minutes: sum_minutes(days.values().copied()),
+            days,
+        })
+    }
+
+    /// Create the row of totals for given rows of given week
+    pub fn total(week: &[NaiveDate], records: &[TimesheetRecord]) -> TimesheetRecord {
+        let mut days: BTreeMap<String, u32> = week
+            .iter()
+            .map(|x| (x.format("%Y-%m-%d").to_string(), 0))
+            .collect();
+        for record in records {
+            for (day, minutes) in &record.days {
+                let total: &mut u32 = days.entry(day.clone()).or_insert(0);
+                *total = total.saturating_add(*minutes);
+            }
+        }
+        TimesheetRecord {
+            id: String::new(),
+            name: "Total".to_string(),
+            minutes: sum_minutes(days.values().copied()),
+            days,
+        }
+    }
+}
+
+impl Record for TimesheetRecord {
+    fn cells(&self) -> Vec<String> {
+        let mut cells: Vec<String> = vec![self.id.clone(), self.name.clone()];
+        cells.extend(self.days.values().map(|x| match x {
+            0 => "-".to_string(),
+            _ => format_duration(*x),
+        }));
+        cells.push(format_duration(self.minutes));
+        cells
+    }
+}
+
+/// Parse a duration such as 1h30m, 2h or 45m into minutes
+pub fn parse_duration(value: &str) -> Result<u32, AppError> {
+    let invalid = || {
+        AppError::InvalidArgument(format!(
+            "{} - Please enter a duration in hours and minutes (e.g. 1h30m, 2h or 45m).",
+            value
+        ))
+    };
+
+    let mut minutes: u32 = 0;
+    let mut number: String = String::new();
+    let mut units_seen: Vec<char> = Vec::new();
+    for x in value.to_lowercase().chars() {
+        match x {
+            '0'..='9' => number.push(x),
+            'h' | 'm' if !number.is_empty() && !units_seen.contains(&x) => {
+                let amount: u32 = number.parse::<u32>().map_err(|_| invalid())?;
+                let factor: u32 = match x {
+                    'h' => 60,
+                    _ => 1,
+                };
+                minutes = amount
+                    .checked_mul(factor)
+                    .and_then(|y| minutes.checked_add(y))
+                    .ok_or_else(invalid)?;
+                units_seen.push(x);
+                number.clear();
+            }
+            _ => return Err(invalid()),
+        }
+    }
+    // Minutes cannot come before hours (e.g. 30m1h)
+    if !number.is_empty() || minutes == 0 || units_seen == ['m', 'h'] {
+        return Err(invalid());
+    }
+    Ok(minutes)
+}
+
+/// Format given minutes as hours and minutes (e.g. 1h30m, 2h or 45m)
+pub fn format_duration(minutes: u32) -> String {
+    match (minutes / 60, minutes % 60) {
+        (0, m) => format!("{}m", m),
+        (h, 0) => format!("{}h", h),
+        (h, m) => format!("{}h{}m", h, m),
+    }
+}
+
+/// Total time of given worklog entries, in minutes
+pub fn total_minutes(worklog: &[WorkLog]) -> u32 {
+    sum_minutes(worklog.iter().map(|x| x.minutes))
+}
+
+/// Add up given amounts of minutes, stopping at the largest amount instead of overflowing
+pub fn sum_minutes(minutes: impl IntoIterator<Item = u32>) -> u32 {
+    minutes
+        .into_iter()
+        .fold(0, |total: u32, x: u32| total.saturating_add(x))
+}
+
+/// Days of the current week, from Monday to Sunday
+pub fn current_week() -> Vec<NaiveDate> {
+    let today: NaiveDate = Local::now().date_naive();
+    let monday: NaiveDate =
+        today - chrono::Days::new(today.weekday().num_days_from_monday() as u64);
+    (0..7).map(|x| monday + chrono::Days::new(x)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn durations_are_parsed_in_hours_and_minutes() {
+        assert_eq!(parse_duration("45m").unwrap(), 45);
+        assert_eq!(parse_duration("2h").unwrap(), 120);
+        assert_eq!(parse_duration("1h30m").unwrap(), 90);
+        assert_eq!(parse_duration("1H05M").unwrap(), 65);
+    }
+
+    #[test]
+    fn invalid_durations_are_rejected() {
+        for value in [
+            "", "0m", "0h0m", "30", "30m1h", "1h1h", "h", "1.5h", "1d", "-5m",
+        ] {
+            assert!(parse_duration(value).is_err(), "{} was accepted", value);
+        }
+        assert!(parse_duration(&format!("{}h", u32::MAX)).is_err());
+    }
+
+    #[test]
+    fn durations_are_formatted_in_hours_and_minutes() {
+        assert_eq!(format_duration(0), "0m");
+        assert_eq!(format_duration(45), "45m");
+        assert_eq!(format_duration(120), "2h");
+        assert_eq!(format_duration(90), "1h30m");
+        for minutes in [1, 59, 60, 61, 1439, 100_000] {
+            assert_eq!(parse_duration(&format_duration(minutes)).unwrap(), minutes);
+        }
+    }
+
+    #[test]
+    fn minutes_are_added_up_without_overflowing() {
+        let work_log = |minutes: u32| WorkLog {
+            logged_on: TimeStamp::new(),
+            minutes,
+            message: String::new(),
+            author: None,
+        };
+        assert_eq!(total_minutes(&[work_log(30), work_log(45)]), 75);
+        assert_eq!(total_minutes(&[work_log(u32::MAX), work_log(1)]), u32::MAX);
+        assert_eq!(sum_minutes([u32::MAX - 1, 1, 1]), u32::MAX);
+    }
+}
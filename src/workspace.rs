@@ -0,0 +1,2534 @@
+//! Defines the service layer of the application: a workspace holding all boards in the data
+//! directory, and a board running every operation on one of them. Both return data instead of
+//! printing it, so that the command line is only one frontend over them.
+
+use crate::{
+    boards::{HiddenItem, HiddenRecord, ItemRecord, KanbanBoard, Shelf, SwimlaneRecord},
+    config::AppConfig,
+    error::AppError,
+    estimates::{self, Estimate, EstimateTotals, EstimateUnit},
+    fsck::{self, Problem},
+    hierarchy::Hierarchy,
+    ids::{self, validate_id_prefix, IdSequence},
+    items::{ItemDetails, WorkItem},
+    journal::{HistoryRecord, Journal, JournalEntry},
+    notes::{NoteRecord, TaskNotes},
+    output::{Record, Section},
+    people::{self, validate_email, validate_handle, Person, PersonRecord},
+    sprints::{Sprint, SprintRecord, SprintReport},
+    storage::{
+        copy_storage, open_backend, open_storage, unit_of_work::UnitOfWork, Storage, StorageBackend,
+    },
+    swimlanes::{LaneStage, Swimlane, WipCheck, WipLimit},
+    tags::{self, validate_tag, TagColor, TagRecord},
+    utils::{create_board_dirs, get_board_dir, record_write},
+    worklog::{self, DayTotalRecord, RunningTimer, TimerRecord, TimesheetRecord, WorkLog},
+    TaskPriority, TimeStamp,
+};
+use chrono::prelude::NaiveDate;
+use serde::Serialize;
+use std::{collections::BTreeMap, path::PathBuf};
+
+/// Details for a new task or subtask
+#[derive(Debug, Clone)]
+pub struct NewItem {
+    /// Name of the item
+    pub name: String,
+
+    /// Description for the item
+    pub description: String,
+
+    /// Priority of the item
+    pub priority: TaskPriority,
+
+    /// Date when the item is supposed to finish
+    pub deadline: Option<TimeStamp>,
+
+    /// Notes to add to the item
+    pub notes: Vec<String>,
+}
+
+/// Changes to the details of an existing task or subtask
+#[derive(Debug, Clone)]
+pub struct ItemChanges {
+    /// New description for the item
+    pub description: String,
+
+    /// New priority of the item
+    pub priority: TaskPriority,
+
+    /// New deadline of the item (None to remove the deadline)
+    pub deadline: Option<TimeStamp>,
+
+    /// Notes to add to the item
+    pub notes: Vec<String>,
+}
+
+/// Outcome of moving a task or subtask into another swimlane
+#[derive(Debug)]
+pub struct MoveOutcome {
+    /// Outcome of checking the WIP limit of the swimlane
+    pub wip_check: WipCheck,
+
+    /// Warning about the unfinished blockers of the item, when the move was forced past them
+    pub blocked: Option<String>,
+
+    /// Dependents moved out of the blocked swimlane as their last blocker got done, along with the
+    /// slug of the swimlane they were moved into
+    pub unblocked: Vec<(String, String)>,
+}
+
+/// Rust structure for the workspace, holding all boards in the application data directory
+#[derive(Debug)]
+pub struct Workspace {
+    /// Application configuration listing the boards
+    config: AppConfig,
+}
+
+/// Board as listed by `board list`
+#[derive(Debug, Clone, Serialize)]
+pub struct BoardRecord {
+    /// Name of the board
+    pub name: String,
+
+    /// Whether commands are executed against the board
+    pub active: bool,
+}
+
+impl Record for BoardRecord {
+    fn cells(&self) -> Vec<String> {
+        let active: &str = match self.active {
+            true => "*",
+            false => "",
+        };
+        vec![self.name.clone(), active.to_string()]
+    }
+}
+
+impl Workspace {
+    /// Open the workspace in the application data directory
+    pub fn open() -> Result<Self, AppError> {
+        Ok(Workspace {
+            config: AppConfig::load()?,
+        })
+    }
+
+    /// Names of all boards in the order they were created
+    pub fn board_names(&self) -> &[String] {
+        &self.config.boards
+    }
+
+    /// Fetch all boards, marking the active board
+    pub fn list_boards(&self) -> Vec<BoardRecord> {
+        self.config
+            .boards
+            .iter()
+            .map(|x| BoardRecord {
+                name: x.clone(),
+                active: x == &self.config.active_board,
+            })
+            .collect()
+    }
+
+    /// Name of the board which commands are executed against
+    pub fn active_board(&self) -> &str {
+        &self.config.active_board
+    }
+
+    /// Storage backend holding the data of all boards
+    pub fn storage_backend(&self) -> StorageBackend {
+        self.config.storage_backend
+    }
+
+    /// Open the active board with the configured storage backend
+    pub fn open_active_board(&self) -> Result<Board, AppError> {
+        Board::open(&self.config.active_board, open_storage(&self.config)?)
+    }
+
+    /// Open the board with given name with the configured storage backend
+    pub fn open_board(&self, board_name: &str) -> Result<Board, AppError> {
+        self.check_board(board_name)?;
+        Board::open(
+            board_name,
+            open_backend(self.config.storage_backend, board_name)?,
+        )
+    }
+
+    /// Create a new board with given name, whose Task IDs get given prefix (TASK by default)
+    pub fn create_board(&mut self, board_name: &str, prefix: Option<&str>) -> Result<(), AppError> {
+        validate_board_name(board_name)?;
+        if self.config.has_board(board_name) {
+            return Err(AppError::InvalidArgument(format!(
+                "Board {} already exists.",
+                board_name
+            )));
+        }
+
+        if let Some(prefix) = prefix {
+            let new_boards: KanbanBoard = KanbanBoard {
+                id_sequence: IdSequence::new(&validate_id_prefix(prefix)?),
+                ..KanbanBoard::new()
+            };
+            new_boards.save(open_backend(self.config.storage_backend, board_name)?.as_ref())?;
+        } else {
+            create_board_dirs(board_name)?;
+        }
+        let mut config: AppConfig = self.config.clone();
+        config.boards.push(board_name.to_string());
+        self.save_config(config)
+    }
+
+    /// Make the board with given name the active board
+    pub fn use_board(&mut self, board_name: &str) -> Result<(), AppError> {
+        self.check_board(board_name)?;
+        let mut config: AppConfig = self.config.clone();
+        config.active_board = board_name.to_string();
+        self.save_config(config)
+    }
+
+    /// Delete the board with given name along with all its tasks, subtasks and notes.
+    /// The active board can't be deleted.
+    pub fn delete_board(&mut self, board_name: &str) -> Result<(), AppError> {
+        self.check_board(board_name)?;
+        if self.config.active_board == board_name {
+            return Err(AppError::InvalidArgument(format!(
+                "Board {} is in use. Please switch to another board before deleting it.",
+                board_name
+            )));
+        }
+
+        let board_dir: PathBuf = get_board_dir(board_name)?;
+        // Part of the board may be gone even when removing the rest fails
+        let removed: std::io::Result<()> = std::fs::remove_dir_all(&board_dir);
+        record_write();
+        if let Err(e) = removed {
+            return Err(AppError::FileDeleteError(format!(
+                "{} - {}",
+                board_dir.display(),
+                e
+            )));
+        }
+        let mut config: AppConfig = self.config.clone();
+        config.boards.retain(|x| x != board_name);
+        self.save_config(config)
+    }
+
+    /// Rename the board with given name
+    pub fn rename_board(&mut self, board_name: &str, new_name: &str) -> Result<(), AppError> {
+        validate_board_name(new_name)?;
+        self.check_board(board_name)?;
+        if self.config.has_board(new_name) {
+            return Err(AppError::InvalidArgument(format!(
+                "Board {} already exists.",
+                new_name
+            )));
+        }
+
+        let board_dir: PathBuf = get_board_dir(board_name)?;
+        let new_dir: PathBuf = get_board_dir(new_name)?;
+        if let Err(e) = std::fs::rename(&board_dir, &new_dir) {
+            return Err(AppError::FileWriteError(format!(
+                "{} - {}",
+                new_dir.display(),
+                e
+            )));
+        }
+        record_write();
+        let mut config: AppConfig = self.config.clone();
+        for name in config.boards.iter_mut() {
+            if name == board_name {
+                *name = new_name.to_string();
+            }
+        }
+        if config.active_board == board_name {
+            config.active_board = new_name.to_string();
+        }
+        self.save_config(config)
+    }
+
+    /// Copy the data of all boards to given storage backend and switch to it.
+    /// Returns the number of tasks and subtasks copied (None if the backend is already in use).
+    pub fn use_storage(&mut self, backend: StorageBackend) -> Result<Option<usize>, AppError> {
+        if self.config.storage_backend == backend {
+            return Ok(None);
+        }
+
+        let mut items_copied: usize = 0;
+        for board_name in &self.config.boards {
+            let old_storage: Box<dyn Storage> =
+                open_backend(self.config.storage_backend, board_name)?;
+            let new_storage: Box<dyn Storage> = open_backend(backend, board_name)?;
+            items_copied += copy_storage(old_storage.as_ref(), new_storage.as_ref())?;
+        }
+        let mut config: AppConfig = self.config.clone();
+        config.storage_backend = backend;
+        self.save_config(config)?;
+        Ok(Some(items_copied))
+    }
+
+    /// Store given application configuration, keeping it in place of the current one only once it
+    /// was written
+    fn save_config(&mut self, config: AppConfig) -> Result<(), AppError> {
+        config.write_to_file()?;
+        self.config = config;
+        Ok(())
+    }
+
+    /// Check that a board with given name exists
+    fn check_board(&self, board_name: &str) -> Result<(), AppError> {
+        match self.config.has_board(board_name) {
+            true => Ok(()),
+            false => Err(AppError::BoardNotFound(board_name.to_string())),
+        }
+    }
+}
+
+/// Check that given board name can be used as a directory name
+fn validate_board_name(board_name: &str) -> Result<(), AppError> {
+    if board_name.is_empty()
+        || !board_name
+            .chars()
+            .all(|x| x.is_ascii_alphanumeric() || x == '-' || x == '_')
+    {
+        return Err(AppError::InvalidArgument(format!(
+            "{} - Board names can only contain letters, digits, '-' and '_'.",
+            board_name
+        )));
+    }
+    Ok(())
+}
+
+/// Rust structure for a board, holding its data in memory along with the storage it is kept in.
+/// Every change is written through a unit of work and recorded in the journal of the board.
+pub struct Board {
+    /// Name of the board
+    name: String,
+
+    /// Storage backend holding the Kanban Board data
+    storage: Box<dyn Storage>,
+
+    /// Kanban Board with the swimlanes
+    kanban: KanbanBoard,
+
+    /// Nesting of the tasks and subtasks
+    hierarchy: Hierarchy,
+
+    /// Notes for Tasks and SubTasks
+    task_notes: TaskNotes,
+
+    /// Operation journal of the board (for undo and redo)
+    journal: Journal,
+}
+
+impl Board {
+    /// Open the board with given name kept in given storage (setting up a blank Kanban Board on
+    /// first use of the board)
+    pub fn open(board_name: &str, storage: Box<dyn Storage>) -> Result<Self, AppError> {
+        Board::open_with(board_name, storage, Journal::load(board_name)?)
+    }
+
+    /// Open the board with given name kept in given storage, recording its changes in given
+    /// journal
+    fn open_with(
+        board_name: &str,
+        storage: Box<dyn Storage>,
+        journal: Journal,
+    ) -> Result<Self, AppError> {
+        let kanban: KanbanBoard = match KanbanBoard::load(storage.as_ref())? {
+            Some(s) => s,
+            None => {
+                let kanban: KanbanBoard = KanbanBoard::new();
+                kanban.save(storage.as_ref())?;
+                kanban
+            }
+        };
+
+        Ok(Board {
+            name: board_name.to_string(),
+            hierarchy: Hierarchy::load(storage.as_ref())?,
+            task_notes: TaskNotes::load(storage.as_ref())?.unwrap_or_default(),
+            journal,
+            kanban,
+            storage,
+        })
+    }
+
+    /// Read the data of the board again from its storage (e.g. after another process changed it)
+    pub fn reload(&mut self) -> Result<(), AppError> {
+        self.kanban = KanbanBoard::load(self.storage.as_ref())?.unwrap_or_default();
+        self.hierarchy = Hierarchy::load(self.storage.as_ref())?;
+        self.task_notes = TaskNotes::load(self.storage.as_ref())?.unwrap_or_default();
+        self.journal.reload()?;
+        Ok(())
+    }
+
+    /// Run given change on the board. When it fails, the board is read again from its storage, so
+    /// that the Kanban Board, nesting and notes held in memory never keep a change which was not
+    /// committed.
+    fn apply<T>(
+        &mut self,
+        change: impl FnOnce(&mut Board) -> Result<T, AppError>,
+    ) -> Result<T, AppError> {
+        match change(self) {
+            Ok(s) => Ok(s),
+            Err(e) => match self.reload() {
+                Ok(_) => Err(e),
+                Err(reload_error) => Err(AppError::TransactionError(format!(
+                    "{} (reading the board again also failed: {})",
+                    e, reload_error
+                ))),
+            },
+        }
+    }
+
+    /// Name of the board
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Storage holding the data of the board
+    pub fn storage(&self) -> &dyn Storage {
+        self.storage.as_ref()
+    }
+
+    /// Kanban Board with the swimlanes
+    pub fn kanban(&self) -> &KanbanBoard {
+        &self.kanban
+    }
+
+    /// Nesting of the tasks and subtasks
+    pub fn hierarchy(&self) -> &Hierarchy {
+        &self.hierarchy
+    }
+
+    /// Operation journal of the board
+    pub fn journal(&self) -> &Journal {
+        &self.journal
+    }
+
+    /// Fetch the task or subtask with given Task or SubTask ID
+    pub fn item(&self, item_id: &str) -> Result<WorkItem, AppError> {
+        match WorkItem::check_if_exists(self.storage(), item_id)? {
+            true => WorkItem::get(self.storage(), item_id),
+            false => Err(AppError::TaskNotFound(item_id.to_string())),
+        }
+    }
+
+    /// Fetch the task with given Task ID (an item which is not nested under another one)
+    pub fn task(&self, task_id: &str) -> Result<WorkItem, AppError> {
+        let work_item: WorkItem = self.item(task_id)?;
+        match work_item.parent {
+            None => Ok(work_item),
+            Some(_) => Err(AppError::TaskNotFound(format!(
+                "{} is a subtask, not a task",
+                task_id
+            ))),
+        }
+    }
+
+    /// Fetch the subtask with given SubTask ID (an item nested under a task or another subtask)
+    pub fn subtask(&self, subtask_id: &str) -> Result<WorkItem, AppError> {
+        let work_item: WorkItem = self.item(subtask_id)?;
+        match work_item.parent {
+            Some(_) => Ok(work_item),
+            None => Err(AppError::TaskNotFound(format!(
+                "{} is a task, not a subtask",
+                subtask_id
+            ))),
+        }
+    }
+
+    /// Fetch all information for given Task ID, along with its subtasks, progress and notes
+    pub fn task_details(&self, task_id: &str) -> Result<ItemDetails, AppError> {
+        self.task(task_id)?;
+        WorkItem::get_details(
+            self.storage(),
+            &self.kanban,
+            &self.hierarchy,
+            &self.task_notes,
+            task_id,
+        )
+    }
+
+    /// Fetch all information for given SubTask ID, along with its parent, subtasks, progress and
+    /// notes
+    pub fn subtask_details(&self, subtask_id: &str) -> Result<ItemDetails, AppError> {
+        self.subtask(subtask_id)?;
+        WorkItem::get_details(
+            self.storage(),
+            &self.kanban,
+            &self.hierarchy,
+            &self.task_notes,
+            subtask_id,
+        )
+    }
+
+    /// Fetch all tasks in swimlane with given slug (or in all swimlanes for "all"), grouped by
+    /// swimlane
+    pub fn list_tasks(&self, swimlanes: &str) -> Result<Vec<Section<ItemRecord>>, AppError> {
+        self.kanban
+            .list_tasks(self.storage(), &self.hierarchy, swimlanes)
+    }
+
+    /// Fetch all subtasks in swimlane with given slug (or in all swimlanes for "all"), grouped by
+    /// swimlane
+    pub fn list_subtasks(&self, swimlanes: &str) -> Result<Vec<Section<ItemRecord>>, AppError> {
+        self.kanban
+            .list_subtasks(self.storage(), &self.hierarchy, swimlanes)
+    }
+
+    /// Fetch the open tasks and subtasks matching given deadline keyword (past-deadline, today,
+    /// tomorrow, after-tomorrow, no-deadline), grouped by swimlane
+    pub fn find_by_deadline(&self, keyword: &str) -> Result<Vec<Section<ItemRecord>>, AppError> {
+        self.kanban
+            .find_by_deadline(self.storage(), &self.hierarchy, keyword)
+    }
+
+    /// Fetch the open tasks and subtasks with given priority keyword (high, medium, low), grouped
+    /// by swimlane
+    pub fn find_by_priority(&self, keyword: &str) -> Result<Vec<Section<ItemRecord>>, AppError> {
+        self.kanban
+            .find_by_priority(self.storage(), &self.hierarchy, keyword)
+    }
+
+    /// Fetch the open tasks and subtasks with given tag, grouped by swimlane
+    pub fn find_by_tag(&self, tag: &str) -> Result<Vec<Section<ItemRecord>>, AppError> {
+        self.kanban
+            .find_by_tag(self.storage(), &self.hierarchy, tag)
+    }
+
+    /// Fetch all tags used on the board or given a color, along with the number of tasks and
+    /// subtasks on the board which have them
+    pub fn list_tags(&self) -> Result<Vec<TagRecord>, AppError> {
+        let mut tag_records: Vec<TagRecord> = Vec::new();
+        let mut count = |tag: &str, is_task: bool| {
+            let position: usize = match tag_records.iter().position(|x| x.tag == tag) {
+                Some(s) => s,
+                None => {
+                    tag_records.push(TagRecord {
+                        tag: tag.to_string(),
+                        color: None,
+                        tasks: 0,
+                        subtasks: 0,
+                    });
+                    tag_records.len() - 1
+                }
+            };
+            match is_task {
+                true => tag_records[position].tasks += 1,
+                false => tag_records[position].subtasks += 1,
+            }
+        };
+
+        for item_id in self.kanban.get_all_items() {
+            if !WorkItem::check_if_exists(self.storage(), &item_id)? {
+                continue;
+            }
+            let work_item: WorkItem = WorkItem::get(self.storage(), &item_id)?;
+            for tag in &work_item.tags {
+                count(tag, work_item.parent.is_none());
+            }
+        }
+        for tag in self.kanban.tag_colors().keys() {
+            if !tag_records.iter().any(|x| x.tag == *tag) {
+                tag_records.push(TagRecord {
+                    tag: tag.clone(),
+                    color: None,
+                    tasks: 0,
+                    subtasks: 0,
+                });
+            }
+        }
+        for tag_record in tag_records.iter_mut() {
+            tag_record.color = self
+                .kanban
+                .tag_colors()
+                .get(&tag_record.tag)
+                .map(|x| x.to_string());
+        }
+
+        tag_records.sort_by(|x, y| x.tag.cmp(&y.tag));
+        Ok(tag_records)
+    }
+
+    /// Fetch the open tasks and subtasks assigned to the person with given handle (or to nobody if
+    /// None), grouped by swimlane
+    pub fn find_by_assignee(
+        &self,
+        handle: Option<&str>,
+    ) -> Result<Vec<Section<ItemRecord>>, AppError> {
+        self.kanban
+            .find_by_assignee(self.storage(), &self.hierarchy, handle)
+    }
+
+    /// Fetch the open tasks and subtasks estimated in given unit (or not estimated at all if None),
+    /// grouped by swimlane
+    pub fn find_by_estimate(
+        &self,
+        unit: Option<EstimateUnit>,
+    ) -> Result<Vec<Section<ItemRecord>>, AppError> {
+        self.kanban
+            .find_by_estimate(self.storage(), &self.hierarchy, unit)
+    }
+
+    /// Fetch the open tasks and subtasks assigned to the current user, grouped by swimlane
+    pub fn mine(&self) -> Result<Vec<Section<ItemRecord>>, AppError> {
+        let handle: String = people::current_user()?;
+        self.find_by_assignee(Some(&handle))
+    }
+
+    /// Fetch all tasks and subtasks in the trash or the archive, in the order they were hidden
+    pub fn list_hidden(&self, shelf: Shelf) -> Result<Vec<HiddenRecord>, AppError> {
+        self.kanban.hidden_records(self.storage(), shelf)
+    }
+
+    /// Fetch all swimlanes on the board in order, along with the time logged on their items
+    pub fn list_swimlanes(&self) -> Result<Vec<SwimlaneRecord>, AppError> {
+        self.kanban.swimlane_records(self.storage())
+    }
+
+    /// Fetch the commands recorded in the journal of the board, oldest first
+    pub fn history(&self) -> Vec<HistoryRecord> {
+        self.journal.history()
+    }
+
+    /// Fetch all people registered on the board, along with the number of tasks and subtasks on
+    /// the board assigned to them
+    pub fn list_people(&self) -> Result<Vec<PersonRecord>, AppError> {
+        let mut person_records: Vec<PersonRecord> = self
+            .kanban
+            .people()
+            .iter()
+            .map(|x| PersonRecord {
+                handle: x.handle.clone(),
+                name: x.name.clone(),
+                email: x.email.clone(),
+                tasks: 0,
+                subtasks: 0,
+            })
+            .collect();
+
+        for item_id in self.kanban.get_all_items() {
+            if !WorkItem::check_if_exists(self.storage(), &item_id)? {
+                continue;
+            }
+            let work_item: WorkItem = WorkItem::get(self.storage(), &item_id)?;
+            let (assignee, is_task): (Option<String>, bool) =
+                (work_item.assignee, work_item.parent.is_none());
+            let person_record: &mut PersonRecord = match person_records
+                .iter_mut()
+                .find(|x| Some(&x.handle) == assignee.as_ref())
+            {
+                Some(s) => s,
+                None => continue,
+            };
+            match is_task {
+                true => person_record.tasks += 1,
+                false => person_record.subtasks += 1,
+            }
+        }
+
+        Ok(person_records)
+    }
+
+    /// Fetch the notes of given Task or SubTask ID
+    pub fn notes(&self, item_id: &str) -> Result<Vec<String>, AppError> {
+        self.check_item(item_id)?;
+        Ok(self.task_notes.get_notes(item_id.to_string()))
+    }
+
+    /// Fetch the notes of given Task or SubTask ID as records, oldest first
+    pub fn list_notes(&self, item_id: &str) -> Result<Vec<NoteRecord>, AppError> {
+        Ok(self
+            .notes(item_id)?
+            .into_iter()
+            .enumerate()
+            .map(|(idx, note)| NoteRecord {
+                item_id: item_id.to_string(),
+                number: idx + 1,
+                note,
+            })
+            .collect())
+    }
+
+    /// Fetch the tasks and subtasks in the trash or the archive
+    pub fn hidden_items(&self, shelf: Shelf) -> &[HiddenItem] {
+        self.kanban.shelf(shelf)
+    }
+
+    /// Check the board for orphans, duplicates, status mismatches, missing parents, parent cycles
+    /// and stray notes
+    pub fn check(&self) -> Result<Vec<Problem>, AppError> {
+        fsck::check_board(self.storage(), &self.kanban, &self.task_notes)
+    }
+
+    /// Add a new task to the first swimlane, along with new subtasks nested under it.
+    /// Returns the ID of the task and the IDs of the subtasks.
+    pub fn add_task(
+        &mut self,
+        task: NewItem,
+        subtasks: Vec<NewItem>,
+    ) -> Result<(String, Vec<String>), AppError> {
+        self.apply(|board| {
+            let unit: UnitOfWork = UnitOfWork::new(board.storage.as_ref());
+            let work_item: WorkItem = WorkItem::new(
+                board.kanban.next_task_id(&unit)?,
+                task.name,
+                task.description,
+                task.deadline,
+                task.priority,
+                board.kanban.first_swimlane()?,
+                None,
+            )?;
+            work_item.save(&unit)?;
+            board
+                .kanban
+                .add_to_board(&unit, work_item.id.clone(), &work_item.status)?;
+            board
+                .task_notes
+                .add_new_note(&unit, work_item.id.clone(), task.notes)?;
+
+            let mut subtasks_list: Vec<String> = Vec::new();
+            for subtask in subtasks {
+                subtasks_list.push(create_subtask(
+                    &unit,
+                    &mut board.kanban,
+                    &mut board.hierarchy,
+                    &mut board.task_notes,
+                    &work_item.id,
+                    subtask,
+                )?);
+            }
+            board.journal.record("add task", unit.commit()?)?;
+            Ok((work_item.id, subtasks_list))
+        })
+    }
+
+    /// Add a new subtask to the first swimlane, nested under given parent task or subtask.
+    /// Returns the ID of the subtask.
+    pub fn add_subtask(&mut self, parent: &str, subtask: NewItem) -> Result<String, AppError> {
+        self.apply(|board| {
+            board.check_parent(parent)?;
+            let unit: UnitOfWork = UnitOfWork::new(board.storage.as_ref());
+            let subtask_id: String = create_subtask(
+                &unit,
+                &mut board.kanban,
+                &mut board.hierarchy,
+                &mut board.task_notes,
+                parent,
+                subtask,
+            )?;
+            board.journal.record("add subtask", unit.commit()?)?;
+            Ok(subtask_id)
+        })
+    }
+
+    /// Change the details of given task, and add new subtasks nested under it.
+    /// Returns the IDs of the new subtasks.
+    pub fn edit_task(
+        &mut self,
+        task_id: &str,
+        changes: ItemChanges,
+        subtasks: Vec<NewItem>,
+    ) -> Result<Vec<String>, AppError> {
+        self.apply(|board| {
+            let work_item: WorkItem = board.task(task_id)?;
+            board.edit_item(
+                work_item,
+                changes,
+                subtasks,
+                None,
+                &format!("edit task {}", task_id),
+            )
+        })
+    }
+
+    /// Change the details of given subtask, add new subtasks nested under it, and move it under
+    /// another parent task or subtask if one is given. Returns the IDs of the new subtasks.
+    pub fn edit_subtask(
+        &mut self,
+        subtask_id: &str,
+        changes: ItemChanges,
+        subtasks: Vec<NewItem>,
+        parent: Option<&str>,
+    ) -> Result<Vec<String>, AppError> {
+        self.apply(|board| {
+            let work_item: WorkItem = board.subtask(subtask_id)?;
+            if let Some(parent) = parent {
+                board.check_new_parent(subtask_id, parent)?;
+            }
+            board.edit_item(
+                work_item,
+                changes,
+                subtasks,
+                parent,
+                &format!("edit subtask {}", subtask_id),
+            )
+        })
+    }
+
+    /// Move given subtask under another parent task or subtask
+    pub fn link_subtask(&mut self, subtask_id: &str, parent: &str) -> Result<(), AppError> {
+        self.apply(|board| {
+            let mut work_item: WorkItem = board.subtask(subtask_id)?;
+            board.check_new_parent(subtask_id, parent)?;
+            let unit: UnitOfWork = UnitOfWork::new(board.storage.as_ref());
+            work_item.parent = Some(parent.to_string());
+            work_item.save(&unit)?;
+            board
+                .hierarchy
+                .set_parent(subtask_id, Some(parent.to_string()));
+            board
+                .journal
+                .record(&format!("link subtask {}", subtask_id), unit.commit()?)
+        })
+    }
+
+    /// Move given task into the swimlane with given slug. Beyond a strict WIP limit of the swimlane,
+    /// or into a started or done swimlane while the task has unfinished blockers, the move is
+    /// refused unless `force` is set, in which case a note is recorded on the task. Moving a task
+    /// into a done swimlane moves its dependents without other unfinished blockers out of the
+    /// blocked swimlane.
+    pub fn move_task(
+        &mut self,
+        task_id: &str,
+        swimlane: &str,
+        force: bool,
+    ) -> Result<MoveOutcome, AppError> {
+        self.apply(|board| {
+            board.task(task_id)?;
+            board.kanban.check_on_board(task_id)?;
+            let unit: UnitOfWork = UnitOfWork::new(board.storage.as_ref());
+            let move_outcome: MoveOutcome = move_item(
+                &unit,
+                &mut board.kanban,
+                &mut board.task_notes,
+                task_id,
+                swimlane,
+                force,
+            )?;
+            board.journal.record(
+                &format!("move task {} {}", task_id, swimlane),
+                unit.commit()?,
+            )?;
+            Ok(move_outcome)
+        })
+    }
+
+    /// Move given subtask into the swimlane with given slug (see `move_task`)
+    pub fn move_subtask(
+        &mut self,
+        subtask_id: &str,
+        swimlane: &str,
+        force: bool,
+    ) -> Result<MoveOutcome, AppError> {
+        self.apply(|board| {
+            board.subtask(subtask_id)?;
+            board.kanban.check_on_board(subtask_id)?;
+            let unit: UnitOfWork = UnitOfWork::new(board.storage.as_ref());
+            let move_outcome: MoveOutcome = move_item(
+                &unit,
+                &mut board.kanban,
+                &mut board.task_notes,
+                subtask_id,
+                swimlane,
+                force,
+            )?;
+            board.journal.record(
+                &format!("move subtask {} {}", subtask_id, swimlane),
+                unit.commit()?,
+            )?;
+            Ok(move_outcome)
+        })
+    }
+
+    /// Move given task to the trash, along with its subtasks (at every depth) which are still on
+    /// the board
+    pub fn delete_task(&mut self, task_id: &str) -> Result<(), AppError> {
+        self.apply(|board| {
+            board.task(task_id)?;
+            let unit: UnitOfWork = UnitOfWork::new(board.storage.as_ref());
+            hide_subtree(
+                &unit,
+                &mut board.kanban,
+                &board.hierarchy,
+                task_id,
+                Shelf::Trash,
+            )?;
+            board
+                .journal
+                .record(&format!("delete task {}", task_id), unit.commit()?)
+        })
+    }
+
+    /// Move given subtask to the trash, along with its own subtasks which are still on the board
+    pub fn delete_subtask(&mut self, subtask_id: &str) -> Result<(), AppError> {
+        self.apply(|board| {
+            board.subtask(subtask_id)?;
+            let unit: UnitOfWork = UnitOfWork::new(board.storage.as_ref());
+            hide_subtree(
+                &unit,
+                &mut board.kanban,
+                &board.hierarchy,
+                subtask_id,
+                Shelf::Trash,
+            )?;
+            board
+                .journal
+                .record(&format!("delete subtask {}", subtask_id), unit.commit()?)
+        })
+    }
+
+    /// Put given Task or SubTask ID back from the trash or the archive, along with the subtasks
+    /// taken off with it. Returns the IDs put back along with the slug of the swimlane they are in.
+    pub fn restore(
+        &mut self,
+        item_id: &str,
+        shelf: Shelf,
+    ) -> Result<Vec<(String, String)>, AppError> {
+        self.apply(|board| {
+            let unit: UnitOfWork = UnitOfWork::new(board.storage.as_ref());
+            let restored: Vec<(String, String)> =
+                restore_items(&unit, &mut board.kanban, &board.hierarchy, item_id, shelf)?;
+            let command: String = match shelf {
+                Shelf::Trash => format!("restore {}", item_id),
+                Shelf::Archive => format!("archive restore {}", item_id),
+            };
+            board.journal.record(&command, unit.commit()?)?;
+            Ok(restored)
+        })
+    }
+
+    /// Delete the tasks and subtasks in the trash for good, along with their notes.
+    /// Returns the IDs deleted.
+    pub fn empty_trash(&mut self) -> Result<Vec<String>, AppError> {
+        self.apply(|board| {
+            let unit: UnitOfWork = UnitOfWork::new(board.storage.as_ref());
+            let trashed_items: Vec<String> = board
+                .kanban
+                .clear_shelf(&unit, Shelf::Trash)?
+                .into_iter()
+                .map(|x| x.item_id)
+                .collect();
+            for item_id in &trashed_items {
+                if WorkItem::check_if_exists(&unit, item_id)? {
+                    WorkItem::delete(&unit, item_id)?;
+                }
+                board.task_notes.delete_notes(&unit, item_id)?;
+            }
+            board.hierarchy.forget(&trashed_items);
+            board.kanban.forget_dependencies(&unit, &trashed_items)?;
+            board.kanban.forget_timers(&unit, &trashed_items)?;
+            board.kanban.forget_sprint_items(&unit, &trashed_items)?;
+            board.journal.record("trash empty", unit.commit()?)?;
+            Ok(trashed_items)
+        })
+    }
+
+    /// Archive given task (which must be in a done swimlane), along with its subtasks (at every
+    /// depth) which are still on the board
+    pub fn archive_task(&mut self, task_id: &str) -> Result<(), AppError> {
+        self.apply(|board| {
+            let work_item: WorkItem = board.task(task_id)?;
+            board.kanban.check_on_board(task_id)?;
+            if board.kanban.get_swimlane(&work_item.status)?.stage != LaneStage::Done {
+                return Err(AppError::InvalidArgument(format!(
+                    "{} is not in a done swimlane. Only completed tasks can be archived.",
+                    task_id
+                )));
+            }
+            let unit: UnitOfWork = UnitOfWork::new(board.storage.as_ref());
+            hide_subtree(
+                &unit,
+                &mut board.kanban,
+                &board.hierarchy,
+                task_id,
+                Shelf::Archive,
+            )?;
+            board
+                .journal
+                .record(&format!("archive task {}", task_id), unit.commit()?)
+        })
+    }
+
+    /// Archive all tasks in done swimlanes. Returns the IDs of the tasks archived.
+    pub fn archive_done(&mut self) -> Result<Vec<String>, AppError> {
+        self.apply(|board| {
+            let unit: UnitOfWork = UnitOfWork::new(board.storage.as_ref());
+            let tasks_list: Vec<String> = board.kanban.get_done_tasks(&unit)?;
+            for task_id in &tasks_list {
+                hide_subtree(
+                    &unit,
+                    &mut board.kanban,
+                    &board.hierarchy,
+                    task_id,
+                    Shelf::Archive,
+                )?;
+            }
+            board.journal.record("archive done", unit.commit()?)?;
+            Ok(tasks_list)
+        })
+    }
+
+    /// Add notes to given Task or SubTask ID
+    pub fn add_notes(&mut self, item_id: &str, notes: Vec<String>) -> Result<(), AppError> {
+        self.apply(|board| {
+            board.check_item(item_id)?;
+            if notes.is_empty() {
+                return Err(AppError::MissingArgument(
+                    "Please pass the note to add with --note".to_string(),
+                ));
+            }
+            let unit: UnitOfWork = UnitOfWork::new(board.storage.as_ref());
+            board
+                .task_notes
+                .add_new_note(&unit, item_id.to_string(), notes)?;
+            board
+                .journal
+                .record(&format!("add notes {}", item_id), unit.commit()?)
+        })
+    }
+
+    /// Register a new person on the board. Returns the handle as it is stored (in lowercase).
+    pub fn add_person(
+        &mut self,
+        handle: &str,
+        name: &str,
+        email: Option<&str>,
+    ) -> Result<String, AppError> {
+        self.apply(|board| {
+            let handle: String = validate_handle(handle)?;
+            let email: Option<String> = match email {
+                Some(s) => Some(validate_email(s)?),
+                None => None,
+            };
+            let person: Person = Person {
+                handle: handle.clone(),
+                name: name.trim().to_string(),
+                email,
+            };
+
+            let unit: UnitOfWork = UnitOfWork::new(board.storage.as_ref());
+            board.kanban.add_person(&unit, person)?;
+            board
+                .journal
+                .record(&format!("user add {}", handle), unit.commit()?)?;
+            Ok(handle)
+        })
+    }
+
+    /// Remove the person with given handle from the board. A person still assigned to tasks or
+    /// subtasks (including those in the trash and the archive) cannot be removed.
+    pub fn remove_person(&mut self, handle: &str) -> Result<(), AppError> {
+        self.apply(|board| {
+            let handle: String = board.kanban.get_person(handle)?.handle.clone();
+            for item_id in board.kanban.get_stored_items() {
+                let assignee: Option<String> =
+                    match WorkItem::check_if_exists(board.storage(), &item_id)? {
+                        true => WorkItem::get(board.storage(), &item_id)?.assignee,
+                        false => None,
+                    };
+                if assignee.as_ref() == Some(&handle) {
+                    return Err(AppError::InvalidArgument(format!(
+                        "{} is still assigned to {}. Please reassign it first.",
+                        handle, item_id
+                    )));
+                }
+            }
+
+            let unit: UnitOfWork = UnitOfWork::new(board.storage.as_ref());
+            board.kanban.remove_person(&unit, &handle)?;
+            board
+                .journal
+                .record(&format!("user remove {}", handle), unit.commit()?)
+        })
+    }
+
+    /// Assign a Task or SubTask ID (which must be on the board) to the person with given handle
+    /// (None to unassign it). Returns the handle as it is stored (in lowercase).
+    pub fn assign(
+        &mut self,
+        item_id: &str,
+        handle: Option<&str>,
+    ) -> Result<Option<String>, AppError> {
+        self.apply(|board| {
+            let assignee: Option<String> = match handle {
+                Some(s) => Some(board.kanban.get_person(s)?.handle.clone()),
+                None => None,
+            };
+
+            let unit: UnitOfWork = UnitOfWork::new(board.storage.as_ref());
+            let mut work_item: WorkItem = board.item(item_id)?;
+            board.kanban.check_on_board(item_id)?;
+            work_item.assignee = assignee.clone();
+            work_item.save(&unit)?;
+
+            let label: String = match &assignee {
+                Some(s) => format!("assign {} {}", item_id, s),
+                None => format!("assign {} none", item_id),
+            };
+            board.journal.record(&label, unit.commit()?)?;
+            Ok(assignee)
+        })
+    }
+
+    /// Estimate a Task or SubTask ID (which must be on the board) in story points (e.g. 5 or 5pt)
+    /// or time (e.g. 1h30m), with all of the effort remaining (None to remove the estimate).
+    /// Returns the estimate.
+    pub fn set_estimate(
+        &mut self,
+        item_id: &str,
+        effort: Option<&str>,
+    ) -> Result<Option<Estimate>, AppError> {
+        self.apply(|board| {
+            let estimate: Option<Estimate> = match effort {
+                Some(s) => match estimates::parse_effort(s)? {
+                    (_, 0) => {
+                        return Err(AppError::InvalidArgument(format!(
+                            "{} - Please enter an estimate above zero.",
+                            s
+                        )))
+                    }
+                    (unit, original) => Some(Estimate::new(unit, original)),
+                },
+                None => None,
+            };
+
+            let unit: UnitOfWork = UnitOfWork::new(board.storage.as_ref());
+            let mut work_item: WorkItem = board.item(item_id)?;
+            board.kanban.check_on_board(item_id)?;
+            work_item.estimate = estimate;
+            work_item.save(&unit)?;
+
+            let label: String = match &estimate {
+                Some(s) => format!("estimate {} {}", item_id, s),
+                None => format!("estimate {} none", item_id),
+            };
+            board.journal.record(&label, unit.commit()?)?;
+            Ok(estimate)
+        })
+    }
+
+    /// Set the effort remaining on an estimated Task or SubTask ID (which must be on the board), in
+    /// the unit of its estimate. Returns the updated estimate.
+    pub fn set_remaining(&mut self, item_id: &str, effort: &str) -> Result<Estimate, AppError> {
+        self.apply(|board| {
+            let mut work_item: WorkItem = board.item(item_id)?;
+            board.kanban.check_on_board(item_id)?;
+            let estimate: &mut Estimate = match work_item.estimate.as_mut() {
+                Some(s) => s,
+                None => {
+                    return Err(AppError::InvalidArgument(format!(
+                        "{} has no estimate. Please estimate it first.",
+                        item_id
+                    )))
+                }
+            };
+            let (effort_unit, remaining): (EstimateUnit, u32) = estimates::parse_effort(effort)?;
+            if effort_unit != estimate.unit {
+                return Err(AppError::InvalidArgument(format!(
+                    "{} is estimated in {} - Please enter the remaining effort in {} as well.",
+                    item_id, estimate.unit, estimate.unit
+                )));
+            }
+            estimate.remaining = remaining;
+            let estimate: Estimate = *estimate;
+
+            let unit: UnitOfWork = UnitOfWork::new(board.storage.as_ref());
+            work_item.save(&unit)?;
+            board.journal.record(
+                &format!(
+                    "remaining {} {}",
+                    item_id,
+                    estimates::format_effort(estimate.unit, remaining)
+                ),
+                unit.commit()?,
+            )?;
+            Ok(estimate)
+        })
+    }
+
+    /// Put given tag on a Task or SubTask ID (which must be on the board), giving the tag a color
+    /// on the board when one is passed. Returns the tag as it is stored (in lowercase).
+    pub fn add_tag(
+        &mut self,
+        item_id: &str,
+        tag: &str,
+        color: Option<TagColor>,
+    ) -> Result<String, AppError> {
+        self.apply(|board| {
+            let tag: String = validate_tag(tag)?;
+            board.kanban.check_on_board(item_id)?;
+            let unit: UnitOfWork = UnitOfWork::new(board.storage.as_ref());
+            let added: bool = board.retag_item(&unit, item_id, |x| tags::add_tag(x, &tag))?;
+            if !added && color.is_none() {
+                return Err(AppError::InvalidArgument(format!(
+                    "{} already has tag {}.",
+                    item_id, tag
+                )));
+            }
+            if color.is_some() {
+                board.kanban.set_tag_color(&unit, &tag, color)?;
+            }
+            board
+                .journal
+                .record(&format!("tag add {} {}", item_id, tag), unit.commit()?)?;
+            Ok(tag)
+        })
+    }
+
+    /// Take given tag off a Task or SubTask ID
+    pub fn remove_tag(&mut self, item_id: &str, tag: &str) -> Result<(), AppError> {
+        self.apply(|board| {
+            let tag: String = validate_tag(tag)?;
+            let unit: UnitOfWork = UnitOfWork::new(board.storage.as_ref());
+            let removed: bool = board.retag_item(&unit, item_id, |x| {
+                let num_tags: usize = x.len();
+                x.retain(|y| *y != tag);
+                x.len() != num_tags
+            })?;
+            if !removed {
+                return Err(AppError::InvalidArgument(format!(
+                    "{} does not have tag {}.",
+                    item_id, tag
+                )));
+            }
+            board
+                .journal
+                .record(&format!("tag remove {} {}", item_id, tag), unit.commit()?)
+        })
+    }
+
+    /// Set the color of given tag on the board (None to remove its color)
+    pub fn set_tag_color(
+        &mut self,
+        tag: &str,
+        color: Option<TagColor>,
+    ) -> Result<String, AppError> {
+        self.apply(|board| {
+            let tag: String = validate_tag(tag)?;
+            let unit: UnitOfWork = UnitOfWork::new(board.storage.as_ref());
+            board.kanban.set_tag_color(&unit, &tag, color)?;
+            let color_name: String = match color {
+                Some(s) => s.to_string(),
+                None => "none".to_string(),
+            };
+            board
+                .journal
+                .record(&format!("tag color {} {}", tag, color_name), unit.commit()?)?;
+            Ok(tag)
+        })
+    }
+
+    /// Rename given tag on every task and subtask of the board (including those in the trash and
+    /// the archive). Returns the number of items changed.
+    pub fn rename_tag(&mut self, tag: &str, new_tag: &str) -> Result<usize, AppError> {
+        self.apply(|board| {
+            let (tag, new_tag): (String, String) = (validate_tag(tag)?, validate_tag(new_tag)?);
+            // Tags only left on items in the trash or the archive are taken as well
+            if board.list_tags()?.iter().any(|x| x.tag == new_tag)
+                || board.tag_in_storage(&new_tag)?
+            {
+                return Err(AppError::InvalidArgument(format!(
+                    "Tag {} already exists. Please use tag merge to combine the two tags.",
+                    new_tag
+                )));
+            }
+            board.replace_tag(&tag, &new_tag, &format!("tag rename {} {}", tag, new_tag))
+        })
+    }
+
+    /// Merge given tag into another one on every task and subtask of the board (including those
+    /// in the trash and the archive). Returns the number of items changed.
+    pub fn merge_tag(&mut self, tag: &str, into: &str) -> Result<usize, AppError> {
+        self.apply(|board| {
+            let (tag, into): (String, String) = (validate_tag(tag)?, validate_tag(into)?);
+            if tag == into {
+                return Err(AppError::InvalidArgument(format!(
+                    "{} - A tag cannot be merged into itself.",
+                    tag
+                )));
+            }
+            board.replace_tag(&tag, &into, &format!("tag merge {} {}", tag, into))
+        })
+    }
+
+    /// Add a new swimlane with given name at the end of the board (holding tasks and subtasks
+    /// waiting on their blockers when `blocked` is set). Returns its slug.
+    pub fn add_swimlane(
+        &mut self,
+        name: &str,
+        lane_stage: LaneStage,
+        blocked: bool,
+    ) -> Result<String, AppError> {
+        self.apply(|board| {
+            let swimlane: Swimlane = Swimlane::new(name, lane_stage, blocked)?;
+            let slug: String = swimlane.slug.clone();
+            let unit: UnitOfWork = UnitOfWork::new(board.storage.as_ref());
+            board.kanban.add_swimlane(&unit, swimlane)?;
+            board
+                .journal
+                .record(&format!("lane add {}", name), unit.commit()?)?;
+            Ok(slug)
+        })
+    }
+
+    /// Rename the swimlane with given slug, moving the tasks and subtasks in it along (including
+    /// those in the trash or the archive which go back into it). Returns the new slug.
+    pub fn rename_swimlane(&mut self, slug: &str, name: &str) -> Result<String, AppError> {
+        self.apply(|board| {
+            let unit: UnitOfWork = UnitOfWork::new(board.storage.as_ref());
+            let swimlane: Swimlane = board.kanban.rename_swimlane(&unit, slug, name)?;
+            let hidden_items: Vec<String> = [Shelf::Trash, Shelf::Archive]
+                .iter()
+                .flat_map(|x| board.kanban.shelf(*x))
+                .filter(|x| x.swimlane == swimlane.slug)
+                .map(|x| x.item_id.clone())
+                .collect();
+            for item_id in swimlane.items.iter().chain(hidden_items.iter()) {
+                if WorkItem::check_if_exists(&unit, item_id)? {
+                    let mut work_item: WorkItem = WorkItem::get(&unit, item_id)?;
+                    work_item.status = swimlane.slug.clone();
+                    work_item.save(&unit)?;
+                }
+            }
+            board
+                .journal
+                .record(&format!("lane rename {} {}", slug, name), unit.commit()?)?;
+            Ok(swimlane.slug)
+        })
+    }
+
+    /// Move the swimlane with given slug to given position on the board (starting at 1)
+    pub fn reorder_swimlane(&mut self, slug: &str, position: usize) -> Result<(), AppError> {
+        self.apply(|board| {
+            let unit: UnitOfWork = UnitOfWork::new(board.storage.as_ref());
+            board.kanban.reorder_swimlane(&unit, slug, position)?;
+            board.journal.record(
+                &format!("lane reorder {} {}", slug, position),
+                unit.commit()?,
+            )
+        })
+    }
+
+    /// Change which stage of work the swimlane with given slug represents. The tasks and subtasks
+    /// in it get their started and completed dates as if they were just moved into it.
+    pub fn mark_swimlane(&mut self, slug: &str, lane_stage: LaneStage) -> Result<(), AppError> {
+        self.apply(|board| {
+            let unit: UnitOfWork = UnitOfWork::new(board.storage.as_ref());
+            let previous_stage: LaneStage = board.kanban.get_swimlane(slug)?.stage;
+            board.kanban.mark_swimlane(&unit, slug, lane_stage)?;
+            if lane_stage != previous_stage {
+                let swimlane: Swimlane = board.kanban.get_swimlane(slug)?.clone();
+                for item_id in &swimlane.items {
+                    if WorkItem::check_if_exists(&unit, item_id)? {
+                        WorkItem::change_swimlane(&unit, item_id, &swimlane)?;
+                    }
+                }
+            }
+            board.journal.record(
+                &format!("lane mark {} {}", slug, lane_stage),
+                unit.commit()?,
+            )
+        })
+    }
+
+    /// Set whether the swimlane with given slug holds tasks and subtasks waiting on their blockers
+    pub fn block_swimlane(&mut self, slug: &str, blocked: bool) -> Result<(), AppError> {
+        self.apply(|board| {
+            let unit: UnitOfWork = UnitOfWork::new(board.storage.as_ref());
+            board.kanban.block_swimlane(&unit, slug, blocked)?;
+            let command: &str = match blocked {
+                true => "lane block",
+                false => "lane unblock",
+            };
+            board
+                .journal
+                .record(&format!("{} {}", command, slug), unit.commit()?)
+        })
+    }
+
+    /// Set the WIP limit of the swimlane with given slug (None to remove the limit)
+    pub fn set_wip_limit(
+        &mut self,
+        slug: &str,
+        wip_limit: Option<WipLimit>,
+    ) -> Result<(), AppError> {
+        self.apply(|board| {
+            let unit: UnitOfWork = UnitOfWork::new(board.storage.as_ref());
+            board.kanban.set_wip_limit(&unit, slug, wip_limit)?;
+            let max_items: String = match wip_limit {
+                Some(s) => s.max_items.to_string(),
+                None => "none".to_string(),
+            };
+            board.journal.record(
+                &format!("lane limit {} {}", slug, max_items),
+                unit.commit()?,
+            )
+        })
+    }
+
+    /// Remove the swimlane with given slug, moving the tasks and subtasks in it into the swimlane
+    /// `into` (required unless the swimlane is empty). Returns the outcome of checking the WIP
+    /// limit for each item moved (see `move_task`).
+    pub fn remove_swimlane(
+        &mut self,
+        slug: &str,
+        into: Option<&str>,
+        force: bool,
+    ) -> Result<Vec<WipCheck>, AppError> {
+        self.apply(|board| {
+            let unit: UnitOfWork = UnitOfWork::new(board.storage.as_ref());
+            let items_list: Vec<String> = board.kanban.get_swimlane(slug)?.items.clone();
+            let mut wip_checks: Vec<WipCheck> = Vec::new();
+            if !items_list.is_empty() {
+                let into: &str = match into {
+                    Some(s) if s == slug => {
+                        return Err(AppError::InvalidArgument(format!(
+                            "{} - Tasks cannot be moved into the swimlane being removed.",
+                            s
+                        )))
+                    }
+                    Some(s) => s,
+                    None => {
+                        return Err(AppError::MissingArgument(format!(
+                            "{} - Swimlane is not empty. Please pass --into <Swimlane>.",
+                            slug
+                        )))
+                    }
+                };
+                let target: Swimlane = board.kanban.get_swimlane(into)?.clone();
+                for item_id in items_list {
+                    wip_checks.push(enforce_wip_limit(
+                        &unit,
+                        &board.kanban,
+                        &mut board.task_notes,
+                        &item_id,
+                        into,
+                        force,
+                    )?);
+                    board
+                        .kanban
+                        .update_board(&unit, item_id.clone(), slug, &target.slug)?;
+                    if WorkItem::check_if_exists(&unit, &item_id)? {
+                        WorkItem::change_swimlane(&unit, &item_id, &target)?;
+                    }
+                }
+            }
+            board.kanban.remove_swimlane(&unit, slug)?;
+            board
+                .journal
+                .record(&format!("lane remove {}", slug), unit.commit()?)?;
+            Ok(wip_checks)
+        })
+    }
+
+    /// Set the prefix of new Task IDs on the board. Returns the prefix as it is used.
+    pub fn set_id_prefix(&mut self, prefix: &str) -> Result<String, AppError> {
+        self.apply(|board| {
+            let prefix: String = validate_id_prefix(prefix)?;
+            let unit: UnitOfWork = UnitOfWork::new(board.storage.as_ref());
+            board.kanban.set_id_prefix(&unit, &prefix)?;
+            board
+                .journal
+                .record(&format!("board prefix {}", prefix), unit.commit()?)?;
+            Ok(prefix)
+        })
+    }
+
+    /// Re-key all tasks and subtasks on the board to sequential IDs (only report what would change
+    /// when `check_only` is set). Returns the current and new ID of each item re-keyed.
+    pub fn rekey(&mut self, check_only: bool) -> Result<Vec<(String, String)>, AppError> {
+        self.apply(|board| {
+            let unit: UnitOfWork = UnitOfWork::new(board.storage.as_ref());
+            let changes: Vec<(String, String)> = ids::rekey_board(
+                &unit,
+                &mut board.kanban,
+                &board.hierarchy,
+                &mut board.task_notes,
+                check_only,
+            )?;
+            board.hierarchy = Hierarchy::load(&unit)?;
+            board.journal.record("board rekey", unit.commit()?)?;
+            Ok(changes)
+        })
+    }
+
+    /// Apply the repairs for given problems found by `check`
+    pub fn repair(&mut self, problems: &[Problem]) -> Result<(), AppError> {
+        self.apply(|board| {
+            let unit: UnitOfWork = UnitOfWork::new(board.storage.as_ref());
+            fsck::repair_board(&unit, &mut board.kanban, &mut board.task_notes, problems)?;
+            board.hierarchy = Hierarchy::load(&unit)?;
+            board.journal.record("fsck --repair", unit.commit()?)
+        })
+    }
+
+    /// Undo the most recent change to the board. Returns the undone journal entry (None if there
+    /// is nothing to undo). Changes made since outside the journal are only overwritten with
+    /// `force`.
+    pub fn undo(&mut self, force: bool) -> Result<Option<JournalEntry>, AppError> {
+        self.apply(|board| {
+            let entry: Option<JournalEntry> = board.journal.undo(board.storage.as_ref(), force)?;
+            if entry.is_some() {
+                board.reload()?;
+            }
+            Ok(entry)
+        })
+    }
+
+    /// Redo the most recently undone change to the board. Returns the redone journal entry (None if
+    /// there is nothing to redo). Changes made since outside the journal are only overwritten with
+    /// `force`.
+    pub fn redo(&mut self, force: bool) -> Result<Option<JournalEntry>, AppError> {
+        self.apply(|board| {
+            let entry: Option<JournalEntry> = board.journal.redo(board.storage.as_ref(), force)?;
+            if entry.is_some() {
+                board.reload()?;
+            }
+            Ok(entry)
+        })
+    }
+
+    /// Record that a Task or SubTask ID depends on (is blocked by) another one
+    pub fn add_dependency(&mut self, item_id: &str, blocker_id: &str) -> Result<(), AppError> {
+        self.apply(|board| {
+            board.check_item(item_id)?;
+            board.check_item(blocker_id)?;
+            let unit: UnitOfWork = UnitOfWork::new(board.storage.as_ref());
+            board.kanban.add_dependency(&unit, item_id, blocker_id)?;
+            board.journal.record(
+                &format!("depends {} on {}", item_id, blocker_id),
+                unit.commit()?,
+            )
+        })
+    }
+
+    /// Remove the dependency of a Task or SubTask ID on another one
+    pub fn remove_dependency(&mut self, item_id: &str, blocker_id: &str) -> Result<(), AppError> {
+        self.apply(|board| {
+            let unit: UnitOfWork = UnitOfWork::new(board.storage.as_ref());
+            board.kanban.remove_dependency(&unit, item_id, blocker_id)?;
+            board.journal.record(
+                &format!("undepend {} on {}", item_id, blocker_id),
+                unit.commit()?,
+            )
+        })
+    }
+
+    /// Fetch the timers running on the board
+    pub fn timers(&self) -> Result<Vec<TimerRecord>, AppError> {
+        self.kanban
+            .timers()
+            .iter()
+            .map(TimerRecord::from_timer)
+            .collect()
+    }
+
+    /// Start a timer on given Task or SubTask ID (which must be on the board) for the current user.
+    /// Returns the current user (None when no current user is configured).
+    pub fn start_timer(&mut self, item_id: &str) -> Result<Option<String>, AppError> {
+        self.apply(|board| {
+            board.check_item(item_id)?;
+            board.kanban.check_on_board(item_id)?;
+            let user: Option<String> = people::optional_current_user()?;
+
+            let unit: UnitOfWork = UnitOfWork::new(board.storage.as_ref());
+            board.kanban.start_timer(&unit, item_id, user.clone())?;
+            board
+                .journal
+                .record(&format!("timer start {}", item_id), unit.commit()?)?;
+            Ok(user)
+        })
+    }
+
+    /// Stop the timer of the current user, logging the time elapsed against its Task or SubTask ID
+    /// on the day it was started. Returns the Task or SubTask ID and the time logged.
+    pub fn stop_timer(&mut self, message: &str) -> Result<(String, WorkLog), AppError> {
+        self.apply(|board| {
+            let user: Option<String> = people::optional_current_user()?;
+
+            let unit: UnitOfWork = UnitOfWork::new(board.storage.as_ref());
+            let timer: RunningTimer = board.kanban.stop_timer(&unit, &user)?;
+            let work_log: WorkLog = timer.stop(message)?;
+            WorkItem::log_work(&unit, &timer.item_id, work_log.clone())?;
+            board
+                .journal
+                .record(&format!("timer stop {}", timer.item_id), unit.commit()?)?;
+            Ok((timer.item_id, work_log))
+        })
+    }
+
+    /// Log time spent today on given Task or SubTask ID (which must be on the board) by the current
+    /// user (if one is configured)
+    pub fn log_work(
+        &mut self,
+        item_id: &str,
+        minutes: u32,
+        message: &str,
+    ) -> Result<WorkLog, AppError> {
+        self.apply(|board| {
+            board.check_item(item_id)?;
+            board.kanban.check_on_board(item_id)?;
+            let work_log: WorkLog = WorkLog {
+                logged_on: TimeStamp::new(),
+                minutes,
+                message: message.to_string(),
+                author: people::optional_current_user()?,
+            };
+
+            let unit: UnitOfWork = UnitOfWork::new(board.storage.as_ref());
+            WorkItem::log_work(&unit, item_id, work_log.clone())?;
+            board.journal.record(
+                &format!("log {} {}", item_id, worklog::format_duration(minutes)),
+                unit.commit()?,
+            )?;
+            Ok(work_log)
+        })
+    }
+
+    /// Fetch the time logged on each day against the tasks and subtasks of the board (including
+    /// those in the archive, but not those in the trash), oldest day first
+    pub fn timesheet(&self) -> Result<Vec<DayTotalRecord>, AppError> {
+        let mut day_totals: BTreeMap<NaiveDate, DayTotalRecord> = BTreeMap::new();
+        for work_item in self.logged_items()? {
+            for work_log in &work_item.worklog {
+                let date: NaiveDate = work_log.logged_on.to_naivedate()?;
+                let day_total: &mut DayTotalRecord =
+                    day_totals.entry(date).or_insert(DayTotalRecord {
+                        date,
+                        minutes: 0,
+                        entries: 0,
+                    });
+                day_total.minutes = day_total.minutes.saturating_add(work_log.minutes);
+                day_total.entries += 1;
+            }
+        }
+        Ok(day_totals.into_values().collect())
+    }
+
+    /// Fetch the time logged during the current week against each task and subtask of the board
+    /// (leaving out those without time logged in the week), followed by the row of totals. Returns
+    /// the days of the week along with the rows.
+    pub fn weekly_timesheet(&self) -> Result<(Vec<NaiveDate>, Vec<TimesheetRecord>), AppError> {
+        let week: Vec<NaiveDate> = worklog::current_week();
+        let mut timesheet_records: Vec<TimesheetRecord> = Vec::new();
+        for work_item in self.logged_items()? {
+            let timesheet_record: TimesheetRecord = TimesheetRecord::from_worklog(
+                &work_item.id,
+                &work_item.name,
+                &week,
+                &work_item.worklog,
+            )?;
+            if timesheet_record.minutes > 0 {
+                timesheet_records.push(timesheet_record);
+            }
+        }
+        timesheet_records.push(TimesheetRecord::total(&week, &timesheet_records));
+        Ok((week, timesheet_records))
+    }
+
+    /// Fetch the sprints of the board in the order they were created, along with the number of their
+    /// tasks which are done and the estimates of their tasks
+    pub fn sprints(&self) -> Result<Vec<SprintRecord>, AppError> {
+        let mut sprint_records: Vec<SprintRecord> = Vec::new();
+        for sprint in self.kanban.sprints() {
+            let completed: usize = match sprint.is_open() {
+                true => sprint
+                    .items
+                    .iter()
+                    .filter(|x| self.kanban.is_completed(x))
+                    .count(),
+                false => sprint.completed.len(),
+            };
+            let mut estimate: EstimateTotals = EstimateTotals::default();
+            for item_id in &sprint.items {
+                if WorkItem::check_if_exists(self.storage(), item_id)? {
+                    let work_item: WorkItem = WorkItem::get(self.storage(), item_id)?;
+                    estimate.add_totals(&WorkItem::rolled_up_estimate(
+                        self.storage(),
+                        &self.kanban,
+                        &self.hierarchy,
+                        &work_item,
+                    )?);
+                }
+            }
+            sprint_records.push(SprintRecord::from_sprint(sprint, completed, estimate)?);
+        }
+        Ok(sprint_records)
+    }
+
+    /// Add a new sprint with given goal to the board, starting on given day (by default the day
+    /// after the last sprint ends, or today) and ending on given day (by default two weeks later)
+    pub fn create_sprint(
+        &mut self,
+        goal: &str,
+        start_on: Option<NaiveDate>,
+        end_on: Option<NaiveDate>,
+    ) -> Result<Sprint, AppError> {
+        self.apply(|board| {
+            let unit: UnitOfWork = UnitOfWork::new(board.storage.as_ref());
+            let sprint: Sprint = board.kanban.add_sprint(&unit, goal, start_on, end_on)?;
+            board
+                .journal
+                .record(&format!("sprint create {}", sprint.number), unit.commit()?)?;
+            Ok(sprint)
+        })
+    }
+
+    /// Plan given Task IDs (which must be on the board and in the backlog) into the open sprint
+    /// with given number, or into the current sprint if None. Returns the number of the sprint.
+    pub fn plan_sprint(&mut self, task_ids: &[&str], number: Option<u32>) -> Result<u32, AppError> {
+        self.apply(|board| {
+            let sprint: &Sprint = match number {
+                Some(s) => board.kanban.get_sprint(s)?,
+                None => board.kanban.current_sprint()?,
+            };
+            if !sprint.is_open() {
+                return Err(AppError::InvalidArgument(format!(
+                    "Sprint {} is closed - Please plan tasks into an open sprint.",
+                    sprint.number
+                )));
+            }
+            let number: u32 = sprint.number;
+            for (idx, task_id) in task_ids.iter().enumerate() {
+                board.task(task_id)?;
+                board.kanban.check_on_board(task_id)?;
+                if let Some(sprint) = board.kanban.item_sprint(task_id) {
+                    return Err(AppError::InvalidArgument(format!(
+                        "{} is already planned into sprint {}.",
+                        task_id, sprint.number
+                    )));
+                }
+                if task_ids[..idx].contains(task_id) {
+                    return Err(AppError::InvalidArgument(format!(
+                        "{} was passed more than once.",
+                        task_id
+                    )));
+                }
+            }
+
+            let unit: UnitOfWork = UnitOfWork::new(board.storage.as_ref());
+            for task_id in task_ids {
+                board.kanban.plan_sprint(&unit, number, task_id)?;
+            }
+            board.journal.record(
+                &format!("sprint plan {}", task_ids.join(" ")),
+                unit.commit()?,
+            )?;
+            Ok(number)
+        })
+    }
+
+    /// Take given Task ID out of the open sprint it is planned into, putting it back in the
+    /// backlog. Returns the number of the sprint.
+    pub fn unplan_sprint(&mut self, task_id: &str) -> Result<u32, AppError> {
+        self.apply(|board| {
+            board.task(task_id)?;
+            let unit: UnitOfWork = UnitOfWork::new(board.storage.as_ref());
+            let number: u32 = board.kanban.unplan_sprint(&unit, task_id)?;
+            board
+                .journal
+                .record(&format!("sprint unplan {}", task_id), unit.commit()?)?;
+            Ok(number)
+        })
+    }
+
+    /// Fetch the sprint with given number (or the current sprint if None) along with its tasks on
+    /// the board, grouped by swimlane
+    pub fn sprint_tasks(
+        &self,
+        number: Option<u32>,
+    ) -> Result<(Sprint, Vec<Section<ItemRecord>>), AppError> {
+        let sprint: &Sprint = match number {
+            Some(s) => self.kanban.get_sprint(s)?,
+            None => self.kanban.current_sprint()?,
+        };
+        let sections: Vec<Section<ItemRecord>> =
+            self.kanban
+                .list_sprint_tasks(self.storage(), &self.hierarchy, sprint)?;
+        Ok((sprint.clone(), sections))
+    }
+
+    /// Close the current sprint, reporting which of its tasks were completed and which were not.
+    /// The unfinished tasks are carried over to the next open sprint, or put back in the backlog
+    /// if there is none or `to_backlog` is set. Tasks in the trash are left out of both.
+    pub fn close_sprint(&mut self, to_backlog: bool) -> Result<SprintReport, AppError> {
+        self.apply(|board| {
+            let sprint: Sprint = board.kanban.current_sprint()?.clone();
+            let carried_into: Option<u32> = match to_backlog {
+                true => None,
+                false => board
+                    .kanban
+                    .sprints()
+                    .iter()
+                    .find(|x| x.is_open() && x.number != sprint.number)
+                    .map(|x| x.number),
+            };
+
+            let mut completed: Vec<ItemRecord> = Vec::new();
+            let mut carried_over: Vec<ItemRecord> = Vec::new();
+            for item_id in &sprint.items {
+                if !WorkItem::check_if_exists(board.storage(), item_id)?
+                    || matches!(board.kanban.find_hidden(item_id), Some((Shelf::Trash, _)))
+                {
+                    continue;
+                }
+                let work_item: WorkItem = WorkItem::get(board.storage(), item_id)?;
+                let item_record: ItemRecord = ItemRecord::from_item(
+                    board.storage(),
+                    &board.kanban,
+                    &board.hierarchy,
+                    work_item,
+                )?;
+                match board.kanban.is_completed(item_id) {
+                    true => completed.push(item_record),
+                    false => carried_over.push(item_record),
+                }
+            }
+
+            let unit: UnitOfWork = UnitOfWork::new(board.storage.as_ref());
+            board.kanban.close_sprint(
+                &unit,
+                sprint.number,
+                completed.iter().map(|x| x.id.clone()).collect(),
+                carried_over.iter().map(|x| x.id.clone()).collect(),
+                carried_into,
+            )?;
+            board
+                .journal
+                .record(&format!("sprint close {}", sprint.number), unit.commit()?)?;
+            Ok(SprintReport {
+                sprint: board.kanban.get_sprint(sprint.number)?.clone(),
+                completed,
+                carried_over,
+                carried_into,
+            })
+        })
+    }
+
+    /// Fetch the tasks and subtasks of the board with time logged against them (including those in
+    /// the archive, but not those in the trash), in ID order
+    fn logged_items(&self) -> Result<Vec<WorkItem>, AppError> {
+        let mut item_ids: Vec<String> = self
+            .kanban
+            .get_stored_items()
+            .into_iter()
+            .filter(|x| !matches!(self.kanban.find_hidden(x), Some((Shelf::Trash, _))))
+            .collect();
+        item_ids.sort_by_key(|x| ids::id_sort_key(x));
+
+        let mut work_items: Vec<WorkItem> = Vec::new();
+        for item_id in item_ids {
+            if !WorkItem::check_if_exists(self.storage(), &item_id)? {
+                continue;
+            }
+            let work_item: WorkItem = WorkItem::get(self.storage(), &item_id)?;
+            if !work_item.worklog.is_empty() {
+                work_items.push(work_item);
+            }
+        }
+        Ok(work_items)
+    }
+
+    /// Change the tags of given Task or SubTask ID. Returns whether `change` changed them.
+    fn retag_item(
+        &self,
+        storage: &dyn Storage,
+        item_id: &str,
+        change: impl FnOnce(&mut Vec<String>) -> bool,
+    ) -> Result<bool, AppError> {
+        if !WorkItem::check_if_exists(storage, item_id)? {
+            return Err(AppError::TaskNotFound(item_id.to_string()));
+        }
+        let mut work_item: WorkItem = WorkItem::get(storage, item_id)?;
+        if !change(&mut work_item.tags) {
+            return Ok(false);
+        }
+        work_item.save(storage)?;
+        Ok(true)
+    }
+
+    /// Replace given tag with another one on every stored task and subtask of the board, handing
+    /// its color over as well. Returns the number of items changed.
+    fn replace_tag(&mut self, tag: &str, new_tag: &str, label: &str) -> Result<usize, AppError> {
+        if !self.list_tags()?.iter().any(|x| x.tag == tag) && !self.tag_in_storage(tag)? {
+            return Err(AppError::InvalidArgument(format!("Tag {} not found.", tag)));
+        }
+
+        let unit: UnitOfWork = UnitOfWork::new(self.storage.as_ref());
+        let mut items_changed: usize = 0;
+        for item_id in self.kanban.get_stored_items() {
+            if self.retag_item(&unit, &item_id, |x| tags::replace_tag(x, tag, new_tag))? {
+                items_changed += 1;
+            }
+        }
+        self.kanban.move_tag_color(&unit, tag, new_tag)?;
+        self.journal.record(label, unit.commit()?)?;
+        Ok(items_changed)
+    }
+
+    /// Check if given tag is on any stored task or subtask of the board, including those in the
+    /// trash and the archive
+    fn tag_in_storage(&self, tag: &str) -> Result<bool, AppError> {
+        for item_id in self.kanban.get_stored_items() {
+            let item_tags: Vec<String> = match WorkItem::check_if_exists(self.storage(), &item_id)?
+            {
+                true => WorkItem::get(self.storage(), &item_id)?.tags,
+                false => Vec::new(),
+            };
+            if item_tags.iter().any(|x| x == tag) {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Check that a task or subtask exists for given ID
+    fn check_item(&self, item_id: &str) -> Result<(), AppError> {
+        self.item(item_id).map(|_| ())
+    }
+
+    /// Check that given Task or SubTask ID exists and is on the board, so that subtasks can be
+    /// nested under it
+    fn check_parent(&self, parent: &str) -> Result<(), AppError> {
+        self.check_item(parent)?;
+        self.kanban.check_on_board(parent)
+    }
+
+    /// Check that given SubTask ID can be moved under another parent, which must not be the
+    /// subtask itself or nested under it
+    fn check_new_parent(&self, subtask_id: &str, parent: &str) -> Result<(), AppError> {
+        self.check_parent(parent)?;
+        if parent == subtask_id || self.hierarchy.is_under(parent, subtask_id) {
+            return Err(AppError::InvalidArgument(format!(
+                "{} is nested under {}. A subtask cannot be moved under itself or its own subtasks.",
+                parent, subtask_id
+            )));
+        }
+        Ok(())
+    }
+
+    /// Change the details of given item, add new subtasks nested under it, and move it under
+    /// given parent if one is given. Returns the IDs of the new subtasks.
+    fn edit_item(
+        &mut self,
+        mut work_item: WorkItem,
+        changes: ItemChanges,
+        subtasks: Vec<NewItem>,
+        parent: Option<&str>,
+        label: &str,
+    ) -> Result<Vec<String>, AppError> {
+        let unit: UnitOfWork = UnitOfWork::new(self.storage.as_ref());
+        self.task_notes
+            .add_new_note(&unit, work_item.id.clone(), changes.notes)?;
+
+        let mut subtasks_list: Vec<String> = Vec::new();
+        for subtask in subtasks {
+            subtasks_list.push(create_subtask(
+                &unit,
+                &mut self.kanban,
+                &mut self.hierarchy,
+                &mut self.task_notes,
+                &work_item.id,
+                subtask,
+            )?);
+        }
+        if let Some(parent) = parent {
+            work_item.parent = Some(parent.to_string());
+            self.hierarchy
+                .set_parent(&work_item.id, Some(parent.to_string()));
+        }
+        work_item.description = changes.description;
+        work_item.priority = changes.priority;
+        work_item.deadline = changes.deadline;
+        work_item.save(&unit)?;
+        self.journal.record(label, unit.commit()?)?;
+        Ok(subtasks_list)
+    }
+}
+
+/// Create given subtask in the first swimlane with an ID under given parent Task or SubTask ID,
+/// nested under it. Returns the ID of the subtask.
+fn create_subtask(
+    storage: &dyn Storage,
+    boards: &mut KanbanBoard,
+    hierarchy: &mut Hierarchy,
+    task_notes: &mut TaskNotes,
+    parent: &str,
+    subtask: NewItem,
+) -> Result<String, AppError> {
+    let work_item: WorkItem = WorkItem::new(
+        boards.next_subtask_id(storage, parent)?,
+        subtask.name,
+        subtask.description,
+        subtask.deadline,
+        subtask.priority,
+        boards.first_swimlane()?,
+        Some(parent.to_string()),
+    )?;
+    work_item.save(storage)?;
+    boards.add_to_board(storage, work_item.id.clone(), &work_item.status)?;
+    hierarchy.set_parent(&work_item.id, Some(parent.to_string()));
+    task_notes.add_new_note(storage, work_item.id.clone(), subtask.notes)?;
+    Ok(work_item.id)
+}
+
+/// Take given Task or SubTask ID off the board into the trash or the archive, along with its
+/// subtasks (at every depth) which are still on the board
+fn hide_subtree(
+    storage: &dyn Storage,
+    boards: &mut KanbanBoard,
+    hierarchy: &Hierarchy,
+    item_id: &str,
+    shelf: Shelf,
+) -> Result<(), AppError> {
+    let work_item: WorkItem = WorkItem::get(storage, item_id)?;
+    boards.hide_item(storage, item_id, &work_item.status, shelf, None)?;
+    for (subtask_id, _) in hierarchy.descendants(item_id) {
+        if boards.find_hidden(&subtask_id).is_some()
+            || !WorkItem::check_if_exists(storage, &subtask_id)?
+        {
+            continue;
+        }
+        let subtask_item: WorkItem = WorkItem::get(storage, &subtask_id)?;
+        boards.hide_item(
+            storage,
+            &subtask_id,
+            &subtask_item.status,
+            shelf,
+            Some(item_id),
+        )?;
+    }
+    Ok(())
+}
+
+/// Put given Task or SubTask ID back from the trash or the archive, along with the subtasks taken
+/// off with it. A subtask can't be put back while one of its parents is off the board.
+/// Returns the IDs put back along with the slug of the swimlane they are in.
+fn restore_items(
+    storage: &dyn Storage,
+    boards: &mut KanbanBoard,
+    hierarchy: &Hierarchy,
+    item_id: &str,
+    shelf: Shelf,
+) -> Result<Vec<(String, String)>, AppError> {
+    for parent_id in hierarchy.ancestors(item_id) {
+        if let Some((parent_shelf, _)) = boards.find_hidden(&parent_id) {
+            return Err(AppError::InvalidArgument(format!(
+                "Parent {} of {} is in the {}. Please restore it first.",
+                parent_id, item_id, parent_shelf
+            )));
+        }
+    }
+
+    let restored: Vec<(String, String)> = boards.restore_item(storage, item_id, shelf)?;
+    for (restored_id, slug) in &restored {
+        if WorkItem::check_if_exists(storage, restored_id)?
+            && WorkItem::get(storage, restored_id)?.status != *slug
+        {
+            WorkItem::change_swimlane(storage, restored_id, boards.get_swimlane(slug)?)?;
+        }
+    }
+    Ok(restored)
+}
+
+/// Check the WIP limit of the swimlane a task or subtask is moved into, recording a note on the
+/// item when a strict limit is overridden with `force`
+fn enforce_wip_limit(
+    storage: &dyn Storage,
+    boards: &KanbanBoard,
+    task_notes: &mut TaskNotes,
+    item_id: &str,
+    swimlane: &str,
+    force: bool,
+) -> Result<WipCheck, AppError> {
+    let wip_check: WipCheck = boards.check_wip_limit(item_id, swimlane, force)?;
+    if let WipCheck::Overridden(message) = &wip_check {
+        record_override(storage, task_notes, item_id, "WIP", message)?;
+    }
+    Ok(wip_check)
+}
+
+/// Move given Task or SubTask ID into the swimlane with given slug after checking its blockers and
+/// the WIP limit, and move its dependents out of the blocked swimlane when it gets done
+fn move_item(
+    storage: &dyn Storage,
+    boards: &mut KanbanBoard,
+    task_notes: &mut TaskNotes,
+    item_id: &str,
+    swimlane: &str,
+    force: bool,
+) -> Result<MoveOutcome, AppError> {
+    let blocked: Option<String> = boards.check_blockers(item_id, swimlane, force)?;
+    let wip_check: WipCheck =
+        enforce_wip_limit(storage, boards, task_notes, item_id, swimlane, force)?;
+    if let Some(message) = &blocked {
+        record_override(storage, task_notes, item_id, "Dependency", message)?;
+    }
+    change_swimlane(storage, boards, item_id, swimlane)?;
+
+    let mut unblocked: Vec<(String, String)> = Vec::new();
+    let target: Option<String> = boards.unblocked_swimlane().map(|x| x.slug.clone());
+    if let (LaneStage::Done, Some(target)) = (boards.get_swimlane(swimlane)?.stage, target) {
+        for dependent in boards.unblocked_dependents(item_id) {
+            // Dependents stay blocked rather than going beyond a strict WIP limit
+            if boards.check_wip_limit(&dependent, &target, false).is_err() {
+                continue;
+            }
+            change_swimlane(storage, boards, &dependent, &target)?;
+            unblocked.push((dependent, target.clone()));
+        }
+    }
+
+    Ok(MoveOutcome {
+        wip_check,
+        blocked,
+        unblocked,
+    })
+}
+
+/// Record a note on given Task or SubTask ID about a check overridden with `force`
+fn record_override(
+    storage: &dyn Storage,
+    task_notes: &mut TaskNotes,
+    item_id: &str,
+    check: &str,
+    message: &str,
+) -> Result<(), AppError> {
+    let today: String = TimeStamp::new()
+        .to_naivedate()?
+        .format("%b %e, %Y")
+        .to_string();
+    task_notes.add_new_note(
+        storage,
+        item_id.to_string(),
+        vec![format!("[{} override] {} ({})", check, message, today)],
+    )
+}
+
+/// Move given Task or SubTask ID from its current swimlane into the swimlane with given slug
+fn change_swimlane(
+    storage: &dyn Storage,
+    boards: &mut KanbanBoard,
+    item_id: &str,
+    slug: &str,
+) -> Result<(), AppError> {
+    let work_item: WorkItem = WorkItem::get(storage, item_id)?;
+    boards.update_board(storage, item_id.to_string(), &work_item.status, slug)?;
+    WorkItem::change_swimlane(storage, item_id, boards.get_swimlane(slug)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        constants::ITEMS_PATH,
+        storage::bincode_store::BincodeStorage,
+        utils::{create_dir, test_dir},
+    };
+
+    /// Open a blank board kept with the bincode storage in a fresh test directory
+    fn test_board(name: &str) -> Board {
+        let board_dir: PathBuf = test_dir(name);
+        create_dir(&board_dir.join(ITEMS_PATH)).unwrap();
+        Board::open_with(
+            name,
+            Box::new(BincodeStorage::new(board_dir.clone())),
+            Journal::load_from(board_dir).unwrap(),
+        )
+        .unwrap()
+    }
+
+    /// Details for a new task or subtask with given name
+    fn new_item(name: &str) -> NewItem {
+        NewItem {
+            name: name.to_string(),
+            description: String::new(),
+            priority: TaskPriority::Medium,
+            deadline: None,
+            notes: Vec::new(),
+        }
+    }
+
+    /// Add tasks with given names to the first swimlane of given board, returning their IDs
+    fn add_tasks(board: &mut Board, names: &[&str]) -> Vec<String> {
+        names
+            .iter()
+            .map(|x| board.add_task(new_item(x), Vec::new()).unwrap().0)
+            .collect()
+    }
+
+    #[test]
+    fn move_beyond_strict_wip_limit_is_refused_unless_forced() {
+        let mut board: Board = test_board("workspace_wip_strict");
+        let task_ids: Vec<String> = add_tasks(&mut board, &["First", "Second"]);
+        let wip_limit: WipLimit = WipLimit {
+            max_items: 1,
+            strict: true,
+        };
+        board.set_wip_limit("in-progress", Some(wip_limit)).unwrap();
+        board.move_task(&task_ids[0], "in-progress", false).unwrap();
+
+        assert!(matches!(
+            board.move_task(&task_ids[1], "in-progress", false),
+            Err(AppError::WipLimitExceeded(_))
+        ));
+        assert_eq!(board.task(&task_ids[1]).unwrap().status, "to-do");
+        assert_eq!(
+            board
+                .kanban()
+                .get_swimlane("in-progress")
+                .unwrap()
+                .items
+                .len(),
+            1
+        );
+
+        let move_outcome: MoveOutcome = board.move_task(&task_ids[1], "in-progress", true).unwrap();
+        assert!(matches!(move_outcome.wip_check, WipCheck::Overridden(_)));
+        assert_eq!(board.task(&task_ids[1]).unwrap().status, "in-progress");
+        let notes: Vec<String> = board.notes(&task_ids[1]).unwrap();
+        assert_eq!(notes.len(), 1);
+        assert!(notes[0].starts_with("[WIP override] Swimlane in-progress is at its WIP limit"));
+    }
+
+    #[test]
+    fn move_beyond_soft_wip_limit_is_allowed_with_a_warning() {
+        let mut board: Board = test_board("workspace_wip_soft");
+        let task_ids: Vec<String> = add_tasks(&mut board, &["First", "Second"]);
+        let wip_limit: WipLimit = WipLimit {
+            max_items: 1,
+            strict: false,
+        };
+        board.set_wip_limit("in-progress", Some(wip_limit)).unwrap();
+        board.move_task(&task_ids[0], "in-progress", false).unwrap();
+
+        let move_outcome: MoveOutcome =
+            board.move_task(&task_ids[1], "in-progress", false).unwrap();
+        assert!(matches!(move_outcome.wip_check, WipCheck::Warning(_)));
+        assert_eq!(board.task(&task_ids[1]).unwrap().status, "in-progress");
+        assert!(board.notes(&task_ids[1]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn deleted_task_is_restored_along_with_its_subtasks() {
+        let mut board: Board = test_board("workspace_trash");
+        let task_id: String = add_tasks(&mut board, &["Parent"]).remove(0);
+        let subtask_id: String = board.add_subtask(&task_id, new_item("Child")).unwrap();
+        board.move_task(&task_id, "in-progress", false).unwrap();
+
+        board.delete_task(&task_id).unwrap();
+        let trashed: Vec<&str> = board
+            .hidden_items(Shelf::Trash)
+            .iter()
+            .map(|x| x.item_id.as_str())
+            .collect();
+        assert_eq!(trashed, [task_id.as_str(), subtask_id.as_str()]);
+        assert!(board
+            .kanban()
+            .get_swimlane("to-do")
+            .unwrap()
+            .items
+            .is_empty());
+        assert!(board
+            .kanban()
+            .get_swimlane("in-progress")
+            .unwrap()
+            .items
+            .is_empty());
+
+        let restored: Vec<(String, String)> = board.restore(&task_id, Shelf::Trash).unwrap();
+        assert_eq!(
+            restored,
+            [
+                (task_id.clone(), "in-progress".to_string()),
+                (subtask_id.clone(), "to-do".to_string())
+            ]
+        );
+        assert!(board.hidden_items(Shelf::Trash).is_empty());
+        assert_eq!(
+            board.kanban().get_swimlane("in-progress").unwrap().items,
+            [task_id.as_str()]
+        );
+        assert_eq!(
+            board.kanban().get_swimlane("to-do").unwrap().items,
+            [subtask_id]
+        );
+
+        board.delete_task(&task_id).unwrap();
+        assert_eq!(board.empty_trash().unwrap().len(), 2);
+        assert!(board.hidden_items(Shelf::Trash).is_empty());
+        assert!(matches!(
+            board.task(&task_id),
+            Err(AppError::TaskNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn archived_task_is_restored_into_the_done_swimlane() {
+        let mut board: Board = test_board("workspace_archive");
+        let task_id: String = add_tasks(&mut board, &["Shipped"]).remove(0);
+
+        assert!(matches!(
+            board.archive_task(&task_id),
+            Err(AppError::InvalidArgument(_))
+        ));
+        board.move_task(&task_id, "done", false).unwrap();
+        board.archive_task(&task_id).unwrap();
+        assert_eq!(board.hidden_items(Shelf::Archive)[0].item_id, task_id);
+        assert!(board
+            .kanban()
+            .get_swimlane("done")
+            .unwrap()
+            .items
+            .is_empty());
+        assert!(matches!(
+            board.restore(&task_id, Shelf::Trash),
+            Err(AppError::TaskNotFound(_))
+        ));
+
+        let restored: Vec<(String, String)> = board.restore(&task_id, Shelf::Archive).unwrap();
+        assert_eq!(restored, [(task_id.clone(), "done".to_string())]);
+        assert!(board.hidden_items(Shelf::Archive).is_empty());
+        assert_eq!(
+            board.kanban().get_swimlane("done").unwrap().items,
+            [task_id.as_str()]
+        );
+        assert_eq!(board.archive_done().unwrap(), [task_id]);
+    }
+
+    #[test]
+    fn task_is_restored_into_the_first_swimlane_once_its_swimlane_is_removed() {
+        let mut board: Board = test_board("workspace_restore_removed_lane");
+        let task_id: String = add_tasks(&mut board, &["Reviewed"]).remove(0);
+        board.move_task(&task_id, "in-review", false).unwrap();
+        board.delete_task(&task_id).unwrap();
+        board.remove_swimlane("in-review", None, false).unwrap();
+
+        let restored: Vec<(String, String)> = board.restore(&task_id, Shelf::Trash).unwrap();
+        assert_eq!(restored, [(task_id.clone(), "to-do".to_string())]);
+        assert_eq!(
+            board.kanban().get_swimlane("to-do").unwrap().items,
+            [task_id.as_str()]
+        );
+        assert_eq!(board.task(&task_id).unwrap().status, "to-do");
+        assert!(board.check().unwrap().is_empty());
+    }
+
+    #[test]
+    fn subtask_cannot_be_linked_under_itself_or_its_subtasks() {
+        let mut board: Board = test_board("workspace_link");
+        let task_ids: Vec<String> = add_tasks(&mut board, &["First", "Second"]);
+        let subtask_id: String = board.add_subtask(&task_ids[0], new_item("Child")).unwrap();
+        let nested_id: String = board
+            .add_subtask(&subtask_id, new_item("Grandchild"))
+            .unwrap();
+
+        assert!(matches!(
+            board.link_subtask(&subtask_id, &subtask_id),
+            Err(AppError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            board.link_subtask(&subtask_id, &nested_id),
+            Err(AppError::InvalidArgument(_))
+        ));
+        assert_eq!(
+            board.hierarchy().parent(&subtask_id),
+            Some(task_ids[0].clone())
+        );
+
+        board.link_subtask(&subtask_id, &task_ids[1]).unwrap();
+        assert_eq!(
+            board.hierarchy().parent(&subtask_id),
+            Some(task_ids[1].clone())
+        );
+        assert_eq!(
+            board.subtask(&subtask_id).unwrap().parent,
+            Some(task_ids[1].clone())
+        );
+        assert!(board.hierarchy().is_under(&nested_id, &task_ids[1]));
+    }
+
+    #[test]
+    fn subtasks_follow_their_parent_into_the_trash() {
+        let mut board: Board = test_board("workspace_delete_parent");
+        let task_id: String = add_tasks(&mut board, &["Parent"]).remove(0);
+        let subtask_id: String = board.add_subtask(&task_id, new_item("Child")).unwrap();
+        let nested_id: String = board
+            .add_subtask(&subtask_id, new_item("Grandchild"))
+            .unwrap();
+
+        board.delete_task(&task_id).unwrap();
+        let hidden_with: Vec<(&str, Option<&str>)> = board
+            .hidden_items(Shelf::Trash)
+            .iter()
+            .map(|x| (x.item_id.as_str(), x.hidden_with.as_deref()))
+            .collect();
+        assert_eq!(
+            hidden_with,
+            [
+                (task_id.as_str(), None),
+                (subtask_id.as_str(), Some(task_id.as_str())),
+                (nested_id.as_str(), Some(task_id.as_str()))
+            ]
+        );
+        assert!(matches!(
+            board.restore(&nested_id, Shelf::Trash),
+            Err(AppError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            board.add_subtask(&subtask_id, new_item("Another")),
+            Err(AppError::InvalidArgument(_))
+        ));
+
+        assert_eq!(board.empty_trash().unwrap().len(), 3);
+        assert!(board.hierarchy().children(&task_id).is_empty());
+        assert!(matches!(
+            board.subtask(&nested_id),
+            Err(AppError::TaskNotFound(_))
+        ));
+        assert!(board.check().unwrap().is_empty());
+    }
+
+    /// IDs of given records
+    fn record_ids(item_records: &[ItemRecord]) -> Vec<&str> {
+        item_records.iter().map(|x| x.id.as_str()).collect()
+    }
+
+    #[test]
+    fn unfinished_sprint_tasks_are_carried_over_to_the_next_sprint() {
+        let mut board: Board = test_board("workspace_sprint_carry_over");
+        let task_ids: Vec<String> = add_tasks(&mut board, &["Done", "Archived", "Started"]);
+        board.create_sprint("First", None, None).unwrap();
+        board.create_sprint("Second", None, None).unwrap();
+        let task_refs: Vec<&str> = task_ids.iter().map(|x| x.as_str()).collect();
+        assert_eq!(board.plan_sprint(&task_refs, None).unwrap(), 1);
+        board.move_task(&task_ids[0], "done", false).unwrap();
+        board.move_task(&task_ids[1], "done", false).unwrap();
+        board.archive_task(&task_ids[1]).unwrap();
+        board.move_task(&task_ids[2], "in-progress", false).unwrap();
+
+        let sprint_report: SprintReport = board.close_sprint(false).unwrap();
+        assert_eq!(sprint_report.sprint.number, 1);
+        assert!(!sprint_report.sprint.is_open());
+        assert_eq!(
+            record_ids(&sprint_report.completed),
+            [&task_ids[0], &task_ids[1]]
+        );
+        assert_eq!(record_ids(&sprint_report.carried_over), [&task_ids[2]]);
+        assert_eq!(sprint_report.carried_into, Some(2));
+        assert_eq!(board.kanban().item_sprint(&task_ids[2]).unwrap().number, 2);
+
+        let sprint_records: Vec<SprintRecord> = board.sprints().unwrap();
+        assert_eq!(
+            (sprint_records[0].tasks, sprint_records[0].completed),
+            (3, 2)
+        );
+        assert_eq!(
+            (sprint_records[1].tasks, sprint_records[1].completed),
+            (1, 0)
+        );
+        assert_eq!(board.kanban().current_sprint().unwrap().number, 2);
+    }
+
+    #[test]
+    fn unfinished_sprint_tasks_go_back_to_the_backlog_when_asked() {
+        let mut board: Board = test_board("workspace_sprint_backlog");
+        let task_ids: Vec<String> = add_tasks(&mut board, &["Done", "Pending", "Trashed"]);
+        board.create_sprint("First", None, None).unwrap();
+        board.create_sprint("Second", None, None).unwrap();
+        let task_refs: Vec<&str> = task_ids.iter().map(|x| x.as_str()).collect();
+        board.plan_sprint(&task_refs, Some(1)).unwrap();
+        board.move_task(&task_ids[0], "done", false).unwrap();
+        board.delete_task(&task_ids[2]).unwrap();
+
+        let sprint_report: SprintReport = board.close_sprint(true).unwrap();
+        assert_eq!(record_ids(&sprint_report.completed), [&task_ids[0]]);
+        assert_eq!(record_ids(&sprint_report.carried_over), [&task_ids[1]]);
+        assert_eq!(sprint_report.carried_into, None);
+        assert!(board.kanban().item_sprint(&task_ids[1]).is_none());
+        assert!(board.kanban().get_sprint(2).unwrap().items.is_empty());
+
+        // With no other open sprint, unfinished tasks go back to the backlog as well
+        board.plan_sprint(&[&task_ids[1]], None).unwrap();
+        let sprint_report: SprintReport = board.close_sprint(false).unwrap();
+        assert_eq!(record_ids(&sprint_report.carried_over), [&task_ids[1]]);
+        assert_eq!(sprint_report.carried_into, None);
+        assert!(board.kanban().item_sprint(&task_ids[1]).is_none());
+    }
+
+    #[test]
+    fn renamed_swimlane_takes_its_items_along() {
+        let mut board: Board = test_board("workspace_lane_rename");
+        let task_ids: Vec<String> = add_tasks(&mut board, &["Kept", "Trashed"]);
+        board.move_task(&task_ids[0], "in-review", false).unwrap();
+        board.move_task(&task_ids[1], "in-review", false).unwrap();
+        board.delete_task(&task_ids[1]).unwrap();
+
+        assert_eq!(board.rename_swimlane("in-review", "QA").unwrap(), "qa");
+        assert!(board.kanban().get_swimlane("in-review").is_err());
+        assert_eq!(
+            board.kanban().get_swimlane("qa").unwrap().items,
+            [task_ids[0].as_str()]
+        );
+        assert_eq!(board.task(&task_ids[0]).unwrap().status, "qa");
+        assert_eq!(board.hidden_items(Shelf::Trash)[0].swimlane, "qa");
+        assert_eq!(board.task(&task_ids[1]).unwrap().status, "qa");
+
+        let restored: Vec<(String, String)> = board.restore(&task_ids[1], Shelf::Trash).unwrap();
+        assert_eq!(restored, [(task_ids[1].clone(), "qa".to_string())]);
+        assert!(board.check().unwrap().is_empty());
+    }
+
+    #[test]
+    fn removed_swimlane_moves_its_items_into_another() {
+        let mut board: Board = test_board("workspace_lane_remove");
+        let task_ids: Vec<String> = add_tasks(&mut board, &["First", "Second"]);
+        board.move_task(&task_ids[0], "in-review", false).unwrap();
+        board.move_task(&task_ids[1], "in-review", false).unwrap();
+
+        assert!(matches!(
+            board.remove_swimlane("in-review", None, false),
+            Err(AppError::MissingArgument(_))
+        ));
+        assert!(matches!(
+            board.remove_swimlane("in-review", Some("in-review"), false),
+            Err(AppError::InvalidArgument(_))
+        ));
+        board
+            .remove_swimlane("in-review", Some("done"), false)
+            .unwrap();
+        assert!(board.kanban().get_swimlane("in-review").is_err());
+        assert_eq!(board.kanban().get_swimlane("done").unwrap().items, task_ids);
+        for task_id in &task_ids {
+            let work_item: WorkItem = board.task(task_id).unwrap();
+            assert_eq!(work_item.status, "done");
+            assert!(work_item.completed_on.is_some());
+        }
+        assert!(board.check().unwrap().is_empty());
+    }
+
+    #[test]
+    fn marked_swimlane_updates_the_dates_of_its_items() {
+        let mut board: Board = test_board("workspace_lane_mark");
+        let task_id: String = add_tasks(&mut board, &["Waiting"]).remove(0);
+        let work_item: WorkItem = board.task(&task_id).unwrap();
+        assert_eq!((work_item.started_on, work_item.completed_on), (None, None));
+
+        board.mark_swimlane("to-do", LaneStage::Started).unwrap();
+        let work_item: WorkItem = board.task(&task_id).unwrap();
+        assert_eq!(work_item.started_on, Some(TimeStamp::new()));
+        assert_eq!(work_item.completed_on, None);
+
+        board.mark_swimlane("to-do", LaneStage::Done).unwrap();
+        let work_item: WorkItem = board.task(&task_id).unwrap();
+        assert_eq!(work_item.started_on, Some(TimeStamp::new()));
+        assert_eq!(work_item.completed_on, Some(TimeStamp::new()));
+        assert!(board.kanban().is_completed(&task_id));
+        assert_eq!(
+            board.kanban().get_swimlane("to-do").unwrap().stage,
+            LaneStage::Done
+        );
+    }
+}